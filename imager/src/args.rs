@@ -1,20 +1,36 @@
 use {
     std::{
-        collections::HashMap,
+        collections::{
+            BTreeSet,
+            HashMap,
+        },
         env,
         path::PathBuf,
     },
+    super::{
+        fault,
+        time,
+        time_zone,
+    },
 };
 
 #[derive(Debug)]
 pub enum Args {
     Read {
         image: PathBuf,
+        time_zone: time_zone::TimeZone,
     },
     Write {
         boot_sector: Vec<PathBuf>,
         root_directory: PathBuf,
         has_volume_guid: bool,
+        // None if the size is not specified.
+        volume_size: Option<usize>,
+        output: Option<PathBuf>,
+        bad_clusters: BTreeSet<u32>,
+        faults: BTreeSet<fault::Fault>,
+        time_zone: time_zone::TimeZone,
+        rounding: time::Rounding,
     },
 }
 
@@ -33,6 +49,18 @@ impl Args {
         let root_directory: Option<&String> = args.get("-r");
         let has_volume_guid: Option<&String> = args.get("-v");
         let image: Option<&String> = args.get("-i");
+        let volume_size: Option<&String> = args.get("-s");
+        let output: Option<&String> = args.get("-o");
+        let bad_clusters: Option<&String> = args.get("-c");
+        let faults: Option<&String> = args.get("-f");
+        let time_zone: time_zone::TimeZone = match args.get("-t") {
+            Some(time_zone) => time_zone::TimeZone::new(time_zone),
+            None => time_zone::TimeZone::utc(),
+        };
+        let rounding: time::Rounding = match args.get("-g") {
+            Some(rounding) => time::Rounding::from(rounding.as_str()),
+            None => time::Rounding::Down,
+        };
         match (boot_sector, root_directory, has_volume_guid, image) {
             (Some(boot_sector), Some(root_directory), Some(has_volume_guid), None) => {
                 let boot_sector: Vec<PathBuf> = boot_sector
@@ -52,16 +80,36 @@ impl Args {
                     'F' => false,
                     _ => panic!("Can't interpret args."),
                 };
+                let volume_size: Option<usize> = volume_size.map(|volume_size| parse_size(volume_size));
+                let output: Option<PathBuf> = output.map(PathBuf::from);
+                let bad_clusters: BTreeSet<u32> = match bad_clusters {
+                    Some(bad_clusters) => parse_clusters(bad_clusters),
+                    None => BTreeSet::new(),
+                };
+                let faults: BTreeSet<fault::Fault> = match faults {
+                    Some(faults) => faults
+                        .split(',')
+                        .map(fault::Fault::from)
+                        .collect(),
+                    None => BTreeSet::new(),
+                };
                 Self::Write {
                     boot_sector,
                     root_directory,
                     has_volume_guid,
+                    volume_size,
+                    output,
+                    bad_clusters,
+                    faults,
+                    time_zone,
+                    rounding,
                 }
             },
             (None, None, None, Some(image)) => {
                 let image = PathBuf::from(image);
                 Self::Read {
                     image,
+                    time_zone,
                 }
             },
             _ => {
@@ -71,3 +119,43 @@ impl Args {
     }
 }
 
+// Clusters are listed as comma separated cluster numbers or ranges such as "0x10,0x20-0x2f".
+fn parse_clusters(clusters: &str) -> BTreeSet<u32> {
+    clusters
+        .split(',')
+        .map(|clusters| {
+            let (first, last): (u32, u32) = match clusters.split_once('-') {
+                Some((first, last)) => (parse_cluster(first), parse_cluster(last)),
+                None => (parse_cluster(clusters), parse_cluster(clusters)),
+            };
+            first..=last
+        })
+        .flatten()
+        .collect()
+}
+
+fn parse_cluster(cluster: &str) -> u32 {
+    let cluster: u32 = match cluster.strip_prefix("0x") {
+        Some(cluster) => u32::from_str_radix(cluster, 0x10),
+        None => cluster.parse(),
+    }.expect("Can't interpret args.");
+    assert!(2 <= cluster, "Cluster numbers start from 2.");
+    cluster
+}
+
+// A size is a number of bytes optionally followed by a binary unit such as "32G".
+fn parse_size(size: &str) -> usize {
+    let (number, unit): (&str, usize) = match size.chars().last() {
+        Some('K') | Some('k') => (&size[..size.len() - 1], 1 << 10),
+        Some('M') | Some('m') => (&size[..size.len() - 1], 1 << 20),
+        Some('G') | Some('g') => (&size[..size.len() - 1], 1 << 30),
+        Some('T') | Some('t') => (&size[..size.len() - 1], 1 << 40),
+        _ => (size, 1),
+    };
+    let number: usize = number
+        .parse()
+        .expect("Can't interpret args.");
+    number
+        .checked_mul(unit)
+        .expect("Can't interpret args.")
+}
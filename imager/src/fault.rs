@@ -0,0 +1,34 @@
+use std::fmt;
+
+// A fault is deliberately injected into a generated image to test file system drivers and checkers.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Fault {
+    // The boot checksum of an exFAT doesn't match the main boot region.
+    BadChecksum,
+    // The second FAT doesn't mirror the first FAT.
+    BrokenFatMirror,
+    // A cluster is allocated but doesn't belong to any file or directory.
+    OrphanCluster,
+}
+
+impl From<&str> for Fault {
+    fn from(fault: &str) -> Self {
+        match fault {
+            "bad_checksum" => Self::BadChecksum,
+            "broken_fat_mirror" => Self::BrokenFatMirror,
+            "orphan_cluster" => Self::OrphanCluster,
+            _ => panic!("Can't interpret a fault \"{}\".", fault),
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fault: &str = match self {
+            Self::BadChecksum => "bad_checksum",
+            Self::BrokenFatMirror => "broken_fat_mirror",
+            Self::OrphanCluster => "orphan_cluster",
+        };
+        write!(f, "{}", fault)
+    }
+}
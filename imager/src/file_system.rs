@@ -4,12 +4,19 @@ mod file_system_type;
 
 use {
     std::{
-        collections::HashMap,
+        collections::{
+            BTreeSet,
+            HashMap,
+        },
         fmt,
         fs,
         path::PathBuf,
     },
-    super::rand,
+    super::{
+        fault,
+        output,
+        rand,
+    },
 };
 
 #[derive(Debug)]
@@ -23,7 +30,15 @@ pub enum FileSystem {
 }
 
 impl FileSystem {
-    pub fn new(boot_sector_candidates: Vec<PathBuf>, source_directory: PathBuf, has_volume_guid: bool, rand_generator: &mut rand::Generator) -> Self {
+    pub fn new(
+        boot_sector_candidates: Vec<PathBuf>,
+        source_directory: PathBuf,
+        has_volume_guid: bool,
+        volume_size: Option<usize>,
+        bad_clusters: &BTreeSet<u32>,
+        faults: &BTreeSet<fault::Fault>,
+        rand_generator: &mut rand::Generator,
+    ) -> Result<Self, String> {
         let boot_sector_candidates: HashMap<file_system_type::FileSystemType, PathBuf> = boot_sector_candidates
             .into_iter()
             .map(|boot_sector| {
@@ -48,10 +63,10 @@ impl FileSystem {
                 None,
                 None,
             ) => {
-                let content = exfat::Exfat::new(exfat_boot_sector, &source_directory, has_volume_guid, rand_generator);
-                Self::Exfat {
+                let content = exfat::Exfat::new(exfat_boot_sector, &source_directory, has_volume_guid, volume_size, bad_clusters, faults, rand_generator)?;
+                Ok(Self::Exfat {
                     content,
-                }
+                })
             },
             (
                 None,
@@ -64,14 +79,29 @@ impl FileSystem {
                     fat16_boot_sector.clone(),
                     fat32_boot_sector.clone(),
                 ];
-                let content = fat::Fat::new(boot_sector_candidates, &source_directory);
-                Self::Fat {
-                    content,
+                // The FAT type depends on the number of clusters, so the size of a FAT file system always fits the files.
+                if volume_size.is_some() {
+                    return Err("Can't specify the size of a FAT file system.".to_string());
                 }
+                let content = fat::Fat::new(boot_sector_candidates, &source_directory, bad_clusters, faults)?;
+                Ok(Self::Fat {
+                    content,
+                })
             },
             _ => panic!("Can't generate a file system."),
         }
     }
+
+    pub fn write(&self, output: &mut output::Output) {
+        match self {
+            Self::Exfat {
+                content,
+            } => content.write(output),
+            Self::Fat {
+                content,
+            } => content.write(output),
+        }
+    }
 }
 
 impl From<&Vec<u8>> for FileSystem {
@@ -96,19 +126,6 @@ impl From<&Vec<u8>> for FileSystem {
     }
 }
 
-impl Into<Vec<u8>> for &FileSystem {
-    fn into(self) -> Vec<u8> {
-        match self {
-            FileSystem::Exfat {
-                content,
-            } => content.into(),
-            FileSystem::Fat {
-                content,
-            } => content.into(),
-        }
-    }
-}
-
 impl fmt::Display for FileSystem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
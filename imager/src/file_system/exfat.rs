@@ -12,17 +12,23 @@ mod upcase_table;
 
 use {
     std::{
+        collections::BTreeSet,
         fmt,
         path::PathBuf,
         rc::Rc,
     },
     super::super::{
+        fault,
         guid,
+        output,
         rand,
     },
 };
 
+// The volume grows beyond the default size if the files don't fit in it.
+const DEFAULT_VOLUME_SIZE: usize = 1 << 20;
 const NUM_OF_EXTENDED_BOOT_SECTORS: usize = 0x8;
+const ORPHAN_CLUSTER: &[u8] = b"THEOS orphan cluster";
 
 #[derive(Debug)]
 pub struct Exfat {
@@ -31,6 +37,7 @@ pub struct Exfat {
     clusters: cluster::Clusters,
     extended_boot_sectors: [extended_boot_sector::ExtendedBootSector; NUM_OF_EXTENDED_BOOT_SECTORS],
     fat: fat::Fat,
+    faults: BTreeSet<fault::Fault>,
     oem_parameters: oem_parameter::OemParameters,
     reserved_sector: reserved_sector::ReservedSector,
     root_directory: Rc<node::Node>,
@@ -41,27 +48,59 @@ impl Exfat {
         self.root_directory.allocation_bitmap(&self.clusters)
     }
 
-    pub fn new(boot_sector: &PathBuf, source_directory: &PathBuf, has_volume_guid: bool, rand_generator: &mut rand::Generator) -> Self {
+    pub fn new(
+        boot_sector: &PathBuf,
+        source_directory: &PathBuf,
+        has_volume_guid: bool,
+        volume_size: Option<usize>,
+        bad_clusters: &BTreeSet<u32>,
+        faults: &BTreeSet<fault::Fault>,
+        rand_generator: &mut rand::Generator,
+    ) -> Result<Self, String> {
         let boot_sector = boot_sector::BootSector::new(boot_sector);
-        let mut clusters = cluster::Clusters::new(boot_sector.cluster_size());
+        if faults.contains(&fault::Fault::BrokenFatMirror) && boot_sector.num_of_fats() < 2 {
+            return Err("Can't break the FAT mirror of an exFAT with only one FAT.".to_string());
+        }
+        let volume_size_lower_limit: usize = volume_size.unwrap_or(DEFAULT_VOLUME_SIZE);
+        let cluster_count: usize = boot_sector.cluster_count_of(volume_size_lower_limit);
+        if let Some(bad_cluster) = bad_clusters.range(cluster::FIRST_CLUSTER_NUMBER + cluster_count as u32..).next() {
+            return Err(format!("Can't mark cluster {:#x} as bad outside the cluster heap of an exFAT of {} bytes.", bad_cluster, volume_size_lower_limit));
+        }
+        let mut clusters = cluster::Clusters::new(boot_sector.cluster_size(), bad_clusters);
+        if faults.contains(&fault::Fault::OrphanCluster) {
+            clusters.append(&ORPHAN_CLUSTER.to_vec(), 0);
+        }
         let extended_boot_sectors = [extended_boot_sector::ExtendedBootSector::new(boot_sector.bytes_per_sector()); NUM_OF_EXTENDED_BOOT_SECTORS];
         let upcase_table = upcase_table::UpcaseTable::new();
-        let root_directory = node::Node::root_directory(&source_directory, &boot_sector, &mut clusters, &upcase_table, has_volume_guid, rand_generator);
+        let root_directory = node::Node::root_directory(&source_directory, &boot_sector, &mut clusters, &upcase_table, has_volume_guid, cluster_count, rand_generator);
+        if let Some(volume_size) = volume_size {
+            if cluster_count < clusters.number_of_clusters() {
+                return Err(format!("Can't fit {} in an exFAT of {} bytes.", source_directory.display(), volume_size));
+            }
+        }
+        clusters.fix_size(cluster_count.max(clusters.number_of_clusters()), 0);
         let oem_parameters = oem_parameter::OemParameters::null(boot_sector.bytes_per_sector());
         let reserved_sector = reserved_sector::ReservedSector::new(boot_sector.bytes_per_sector());
         let fat = fat::Fat::new(&clusters, boot_sector.bytes_per_sector());
-        let boot_sector: boot_sector::BootSector = boot_sector.fix(&fat, &root_directory, &clusters);
+        let boot_sector: boot_sector::BootSector = boot_sector.fix(&fat, &root_directory, &clusters, volume_size_lower_limit);
         let boot_checksum = boot_checksum::BootChecksum::new(&boot_sector, &extended_boot_sectors, &oem_parameters, &reserved_sector, boot_sector.bytes_per_sector());
-        Self {
+        let boot_checksum: boot_checksum::BootChecksum = if faults.contains(&fault::Fault::BadChecksum) {
+            boot_checksum.corrupt()
+        } else {
+            boot_checksum
+        };
+        let faults: BTreeSet<fault::Fault> = faults.clone();
+        Ok(Self {
             boot_checksum,
             boot_sector,
             clusters,
             extended_boot_sectors,
             fat,
+            faults,
             oem_parameters,
             reserved_sector,
             root_directory,
-        }
+        })
     }
 
     pub fn volume_guid(&self) -> Option<guid::Guid> {
@@ -71,6 +110,48 @@ impl Exfat {
     pub fn volume_label(&self) -> String {
         self.root_directory.volume_label()
     }
+
+    pub fn write(&self, output: &mut output::Output) {
+        let boot_sector: Vec<u8> = (&self.boot_sector).into();
+        let extended_boot_sectors: Vec<u8> = self.extended_boot_sectors
+            .iter()
+            .map(|extended_boot_sector| Into::<Vec<u8>>::into(extended_boot_sector).into_iter())
+            .flatten()
+            .collect();
+        let oem_parameters: Vec<u8> = (&self.oem_parameters).into();
+        let reserved_sector: Vec<u8> = (&self.reserved_sector).into();
+        let boot_checksum: Vec<u8> = (&self.boot_checksum).into();
+        let boot_region: Vec<u8> = vec![
+            boot_sector,
+            extended_boot_sectors,
+            oem_parameters,
+            reserved_sector,
+            boot_checksum,
+        ].concat();
+        let main_boot_region: &Vec<u8> = &boot_region;
+        let backup_boot_region: &Vec<u8> = &boot_region;
+        output.write(main_boot_region);
+        output.write(backup_boot_region);
+        let fat: Vec<u8> = (&self.fat).into();
+        let fat_mirror: Vec<u8> = if self.faults.contains(&fault::Fault::BrokenFatMirror) {
+            self.fat.broken_mirror()
+        } else {
+            fat.clone()
+        };
+        let num_of_fats: usize = self.boot_sector.num_of_fats();
+        (0..num_of_fats).for_each(|i| output.write(match i {
+            0 => &fat,
+            _ => &fat_mirror,
+        }));
+        let written_length: usize = main_boot_region.len() + backup_boot_region.len() + num_of_fats * fat.len();
+        let cluster_heap_offset: usize = self.boot_sector.cluster_heap_offset() as usize;
+        let cluster_heap_offset: usize = cluster_heap_offset * self.boot_sector.bytes_per_sector();
+        output.write_zeros(cluster_heap_offset - written_length);
+        self.clusters.write(output);
+        let cluster_heap_size: usize = self.boot_sector.cluster_count() as usize * self.boot_sector.cluster_size();
+        let volume_length: usize = self.boot_sector.volume_length() as usize * self.boot_sector.bytes_per_sector();
+        output.write_zeros(volume_length - cluster_heap_offset - cluster_heap_size);
+    }
 }
 
 impl From<&Vec<u8>> for Exfat {
@@ -128,12 +209,14 @@ impl From<&Vec<u8>> for Exfat {
         let root_directory = node::Node::read_root_directory(&clusters, &fat, first_cluster_of_root_directory, cluster_size);
         let allocation_bitmap: allocation_bitmap::AllocationBitmap = root_directory.allocation_bitmap(&clusters);
         clusters.set_used_flags(&allocation_bitmap);
+        let faults: BTreeSet<fault::Fault> = BTreeSet::new();
         Self {
             boot_checksum,
             boot_sector,
             clusters,
             extended_boot_sectors,
             fat,
+            faults,
             oem_parameters,
             reserved_sector,
             root_directory,
@@ -141,40 +224,6 @@ impl From<&Vec<u8>> for Exfat {
     }
 }
 
-impl Into<Vec<u8>> for &Exfat {
-    fn into(self) -> Vec<u8> {
-        let mut bytes: Vec<u8> = vec![];
-        let mut boot_sector: Vec<u8> = (&self.boot_sector).into();
-        let mut extended_boot_sectors: Vec<u8> = self.extended_boot_sectors
-            .iter()
-            .map(|extended_boot_sector| Into::<Vec<u8>>::into(extended_boot_sector).into_iter())
-            .flatten()
-            .collect();
-        let mut oem_parameters: Vec<u8> = (&self.oem_parameters).into();
-        let mut reserved_sector: Vec<u8> = (&self.reserved_sector).into();
-        let mut boot_checksum: Vec<u8> = (&self.boot_checksum).into();
-        bytes.append(&mut boot_sector);
-        bytes.append(&mut extended_boot_sectors);
-        bytes.append(&mut oem_parameters);
-        bytes.append(&mut reserved_sector);
-        bytes.append(&mut boot_checksum);
-        bytes.append(&mut bytes.clone());
-        let fat: Vec<u8> = (&self.fat).into();
-        let num_of_fats: usize = self.boot_sector.num_of_fats();
-        let mut fat: Vec<u8> = (0..num_of_fats)
-            .map(|_| fat.clone().into_iter())
-            .flatten()
-            .collect();
-        bytes.append(&mut fat);
-        let cluster_heap_offset: usize = self.boot_sector.cluster_heap_offset() as usize;
-        let cluster_heap_offset: usize = cluster_heap_offset * self.boot_sector.bytes_per_sector();
-        bytes.resize(cluster_heap_offset, 0x00);
-        let mut clusters: Vec<u8> = (&self.clusters).into();
-        bytes.append(&mut clusters);
-        bytes
-    }
-}
-
 impl fmt::Display for Exfat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let boot_sector: String = format!("{}", self.boot_sector)
@@ -232,6 +281,17 @@ impl fmt::Display for Exfat {
             }))
             .collect::<Vec<String>>()
             .join("\n");
+        let bad_clusters: String = self.fat
+            .bad_clusters()
+            .iter()
+            .map(|cluster_number| format!("cluster[{}] is bad.", cluster_number))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let faults: String = self.faults
+            .iter()
+            .map(|fault| format!("fault: {}", fault))
+            .collect::<Vec<String>>()
+            .join("\n");
         let root_directory: String = format!("{}", self.root_directory);
         let exfat: Vec<String> = vec![
             boot_sector,
@@ -243,9 +303,15 @@ impl fmt::Display for Exfat {
             volume_guid,
             volume_label,
             cluster_used_flags,
+            bad_clusters,
+            faults,
             root_directory,
         ];
-        let exfat: String = exfat.join("\n");
+        let exfat: String = exfat
+            .into_iter()
+            .filter(|element| !element.is_empty())
+            .collect::<Vec<String>>()
+            .join("\n");
         write!(f, "{}", exfat)
     }
 }
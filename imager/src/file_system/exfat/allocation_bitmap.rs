@@ -8,7 +8,9 @@ use {
 
 #[derive(Debug)]
 pub struct AllocationBitmap {
+    // Clusters absent from the bitmap are available.
     bitmap: HashMap<u32, bool>,
+    num_of_clusters: usize,
 }
 
 impl AllocationBitmap {
@@ -16,10 +18,16 @@ impl AllocationBitmap {
         &self.bitmap
     }
 
-    pub fn new(clusters: &cluster::Clusters) -> Self {
-        let bitmap: HashMap<u32, bool> = clusters.used_flags();
+    // The reserved clusters and the bad clusters are used even before they are appended.
+    pub fn new(clusters: &cluster::Clusters, num_of_clusters: usize, reserved_clusters: &[u32]) -> Self {
+        let mut bitmap: HashMap<u32, bool> = clusters.used_flags();
+        bitmap.extend(reserved_clusters
+            .iter()
+            .chain(clusters.specified_bad_clusters())
+            .map(|cluster_number| (*cluster_number, true)));
         Self {
             bitmap,
+            num_of_clusters,
         }
     }
 
@@ -37,23 +45,21 @@ impl AllocationBitmap {
             .collect();
         Self {
             bitmap,
+            num_of_clusters,
         }
     }
 }
 
 impl Into<Vec<u8>> for &AllocationBitmap {
     fn into(self) -> Vec<u8> {
-        let max_cluster_number: u32 = self.bitmap
-            .iter()
-            .map(|(cluster_number, _)| *cluster_number)
-            .max()
-            .expect("Can't extract max cluster number.");
-        let mut bitmap: Vec<bool> = (0..=max_cluster_number - cluster::FIRST_CLUSTER_NUMBER)
+        let mut bitmap: Vec<bool> = (0..self.num_of_clusters)
             .map(|_| false)
             .collect();
         self.bitmap
             .iter()
-            .for_each( |(cluster_number, unavailability)| bitmap[(*cluster_number - cluster::FIRST_CLUSTER_NUMBER) as usize] = *unavailability);
+            .for_each(|(cluster_number, unavailability)| if let Some(bit) = bitmap.get_mut((*cluster_number - cluster::FIRST_CLUSTER_NUMBER) as usize) {
+                *bit = *unavailability;
+            });
         let mut bytes: Vec<u8> = (0..(bitmap.len() + 7) / 8)
             .map(|_| 0xff)
             .collect();
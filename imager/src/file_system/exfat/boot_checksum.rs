@@ -18,6 +18,15 @@ pub struct BootChecksum {
 }
 
 impl BootChecksum {
+    pub fn corrupt(self) -> Self {
+        let size: usize = self.size;
+        let checksum: u32 = !self.checksum;
+        Self {
+            size,
+            checksum,
+        }
+    }
+
     pub fn new(
         boot_sector: &boot_sector::BootSector,
         extended_boot_sector: &[extended_boot_sector::ExtendedBootSector],
@@ -54,6 +54,19 @@ impl BootSector {
         self.cluster_count
     }
 
+    // The number of clusters of a volume of the size.
+    pub fn cluster_count_of(&self, volume_size: usize) -> usize {
+        let cluster_size: usize = self.cluster_size();
+        let fits = |cluster_count: usize| self.cluster_heap_offset_of(cluster_count) + cluster_count * cluster_size <= volume_size;
+        // The FATs grow with the clusters, so start from an underestimate and add clusters while they fit.
+        let cluster_count: usize = volume_size / cluster_size;
+        let mut cluster_count: usize = volume_size.saturating_sub(self.cluster_heap_offset_of(cluster_count)) / cluster_size;
+        while fits(cluster_count + 1) {
+            cluster_count += 1;
+        }
+        cluster_count
+    }
+
     pub fn cluster_heap_offset(&self) -> u32 {
         self.cluster_heap_offset
     }
@@ -62,18 +75,18 @@ impl BootSector {
         self.bytes_per_sector() * self.sectors_per_cluster()
     }
 
-    pub fn fix(self, fat: &fat::Fat, root_directory: &node::Node, clusters: &cluster::Clusters) -> Self {
+    pub fn fix(self, fat: &fat::Fat, root_directory: &node::Node, clusters: &cluster::Clusters, volume_size: usize) -> Self {
         let jump_boot: [u8; 0x3] = self.jump_boot;
         let file_system_name: [u8; 0x8] = self.file_system_name;
         let must_be_zero: [u8; 0x35] = self.must_be_zero;
         let partition_offset: u64 = self.partition_offset;
-        // let volume_length = ;
         let fat_offset: u32 = self.fat_offset;
         let fat_length: u32 = fat.sectors_per_fat() as u32;
         let num_of_fats: u8 = self.num_of_fats;
         let cluster_heap_offset: u32 = (((fat_offset as usize) + (fat_length as usize) * (num_of_fats as usize) + self.sectors_per_cluster() - 1) / self.sectors_per_cluster() * self.sectors_per_cluster()) as u32;
         let cluster_count: u32 = clusters.number_of_clusters() as u32;
-        let volume_length: u64 = ((cluster_heap_offset as usize) + (cluster_count as usize) * self.sectors_per_cluster()) as u64;
+        // The space after the cluster heap too small for a cluster remains in the volume.
+        let volume_length: u64 = ((cluster_heap_offset as usize) + (cluster_count as usize) * self.sectors_per_cluster()).max(volume_size / self.bytes_per_sector()) as u64;
         let first_cluster_of_root_directory: u32 = root_directory.first_cluster();
         let volume_serial_number: u32 = time::Time::current_time().unix_timestamp() as u32;
         let file_system_revision: u16 = self.file_system_revision;
@@ -137,6 +150,19 @@ impl BootSector {
     pub fn sectors_per_cluster(&self) -> usize {
         1 << self.sectors_per_cluster_shift
     }
+
+    pub fn volume_length(&self) -> u64 {
+        self.volume_length
+    }
+
+    // Bytes before the cluster heap of a volume with the clusters.
+    // Each cluster and the two reserved entries take 4 bytes of each FAT.
+    fn cluster_heap_offset_of(&self, cluster_count: usize) -> usize {
+        let fat_length: usize = ((cluster_count + 2) * 4 + self.bytes_per_sector() - 1) / self.bytes_per_sector();
+        let cluster_heap_offset: usize = self.fat_offset as usize + fat_length * self.num_of_fats();
+        let cluster_heap_offset: usize = (cluster_heap_offset + self.sectors_per_cluster() - 1) / self.sectors_per_cluster() * self.sectors_per_cluster();
+        cluster_heap_offset * self.bytes_per_sector()
+    }
 }
 
 impl From<&Vec<u8>> for BootSector {
@@ -1,12 +1,17 @@
 use {
-    std::collections::{
-        HashMap,
-        VecDeque,
+    std::{
+        collections::{
+            BTreeSet,
+            HashMap,
+            VecDeque,
+        },
+        ops::Range,
     },
     super::{
         allocation_bitmap,
         fat,
         upcase_table,
+        super::super::output,
     },
 };
 
@@ -14,6 +19,9 @@ pub const FIRST_CLUSTER_NUMBER: u32 = 2;
 
 #[derive(Debug)]
 pub struct Clusters {
+    // Available clusters are filled with zeros when the image is written, so only their numbers are kept.
+    available_clusters: Vec<Range<u32>>,
+    bad_clusters: BTreeSet<u32>,
     cluster_size: usize,
     clusters: Vec<Cluster>,
     next_cluster_number: u32,
@@ -36,14 +44,18 @@ impl Clusters {
         cluster_number
     }
 
-    pub fn append_available_cluster(&mut self) {
-        let cluster = Cluster::available_cluster(self);
-        self.clusters.push(cluster);
+    pub fn bad_clusters(&self) -> BTreeSet<u32> {
+        self.clusters
+            .iter()
+            .filter(|cluster| cluster.bad)
+            .map(|cluster| cluster.cluster_number)
+            .collect()
     }
 
     pub fn cluster_chain(&self) -> HashMap<u32, Option<u32>> {
         self.clusters
             .iter()
+            .filter(|cluster| !cluster.bad)
             .map(|cluster| cluster.cluster_chain())
             .fold(HashMap::<u32, Option<u32>>::new(), |cluster_chains, cluster_chain| {
                 let mut cluster_chains: HashMap<u32, Option<u32>> = cluster_chains;
@@ -65,9 +77,19 @@ impl Clusters {
         }
     }
 
-    pub fn fix_size(&mut self, size: usize) {
-        while self.number_of_clusters() * self.cluster_size < size {
-            self.append_available_cluster();
+    // Fill the cluster heap with available clusters and bad clusters leaving the reserved clusters which aren't bad at the end.
+    pub fn fix_size(&mut self, cluster_count: usize, reserved_clusters: usize) {
+        let end: u32 = FIRST_CLUSTER_NUMBER + cluster_count as u32;
+        let mut fixed_end: u32 = end - reserved_clusters.min(cluster_count) as u32;
+        while FIRST_CLUSTER_NUMBER < fixed_end && ((end - fixed_end) as usize) < reserved_clusters + self.bad_clusters.range(fixed_end..end).count() {
+            fixed_end -= 1;
+        }
+        while self.next_cluster_number < fixed_end {
+            if self.bad_clusters.contains(&self.next_cluster_number) {
+                self.append_bad_cluster();
+            } else {
+                self.append_available_cluster();
+            }
         }
     }
 
@@ -75,21 +97,38 @@ impl Clusters {
         self.clusters.len()
     }
 
-    pub fn new(cluster_size: usize) -> Self {
+    pub fn new(cluster_size: usize, bad_clusters: &BTreeSet<u32>) -> Self {
+        let available_clusters: Vec<Range<u32>> = vec![];
+        let bad_clusters: BTreeSet<u32> = bad_clusters.clone();
         let clusters: Vec<Cluster> = vec![];
         let next_cluster_number: u32 = FIRST_CLUSTER_NUMBER;
         Self {
+            available_clusters,
+            bad_clusters,
             cluster_size,
             clusters,
             next_cluster_number,
         }
     }
 
+    // The clusters allocated next.
+    pub fn next_cluster_numbers(&self, number_of_clusters: usize) -> Vec<u32> {
+        (self.next_cluster_number..)
+            .filter(|cluster_number| !self.bad_clusters.contains(cluster_number))
+            .take(number_of_clusters)
+            .collect()
+    }
+
     pub fn number_of_clusters(&self) -> usize {
-        self.clusters
+        let number_of_available_clusters: usize = self.available_clusters
+            .iter()
+            .map(|available_clusters| available_clusters.len())
+            .sum();
+        let number_of_allocated_clusters: usize = self.clusters
             .iter()
             .map(|cluster| cluster.number_of_clusters())
-            .sum()
+            .sum();
+        number_of_available_clusters + number_of_allocated_clusters
     }
 
     pub fn number_of_used_clusters(&self) -> usize {
@@ -105,6 +144,7 @@ impl Clusters {
             .enumerate()
             .map(|(cluster, bytes)| ((cluster as u32) + FIRST_CLUSTER_NUMBER, bytes.to_vec()))
             .collect();
+        let bad_clusters: BTreeSet<u32> = fat.bad_clusters().clone();
         let fat: HashMap<u32, Vec<u32>> = fat.to_chains();
         let clusters: Vec<Vec<(u32, Vec<u8>)>> = fat
             .into_iter()
@@ -127,7 +167,10 @@ impl Clusters {
             .map(|cluster| cluster.cluster_number)
             .max()
             .expect("Can't read clusters.") + 1;
+        let available_clusters: Vec<Range<u32>> = vec![];
         Self {
+            available_clusters,
+            bad_clusters,
             cluster_size,
             clusters,
             next_cluster_number,
@@ -140,6 +183,11 @@ impl Clusters {
             .for_each(|cluster| cluster.set_used_flag(allocation_bitmap));
     }
 
+    // Bad clusters including the ones not appended yet.
+    pub fn specified_bad_clusters(&self) -> &BTreeSet<u32> {
+        &self.bad_clusters
+    }
+
     pub fn upcase_table(&self, first_cluster: u32, data_length: usize) -> upcase_table::UpcaseTable {
         let upcase_table: Vec<u8> = self.cluster_chain_bytes(first_cluster)[0..data_length].to_vec();
         upcase_table::UpcaseTable::from(&upcase_table)
@@ -155,52 +203,78 @@ impl Clusters {
             .collect()
     }
 
-    fn cluster_bytes(&self, cluster_number: u32) -> Option<Vec<u8>> {
-        self.clusters
+    pub fn write(&self, output: &mut output::Output) {
+        let cluster_bytes: HashMap<u32, &Vec<u8>> = self.clusters
             .iter()
-            .find_map(|cluster| cluster.bytes(cluster_number))
+            .map(|cluster| cluster.bytes_chain())
+            .flatten()
+            .collect();
+        (FIRST_CLUSTER_NUMBER..=self.max_cluster_number())
+            .for_each(|cluster_number| match cluster_bytes.get(&cluster_number) {
+                Some(bytes) if bytes.len() == self.cluster_size => output.write(bytes),
+                _ => output.write_zeros(self.cluster_size),
+            });
+    }
+
+    fn allocate_cluster_number(&mut self) -> u32 {
+        while self.bad_clusters.contains(&self.next_cluster_number) {
+            self.append_bad_cluster();
+        }
+        let cluster_number: u32 = self.next_cluster_number;
+        self.next_cluster_number += 1;
+        cluster_number
+    }
+
+    fn append_available_cluster(&mut self) {
+        let cluster_number: u32 = self.allocate_cluster_number();
+        match self.available_clusters.last_mut() {
+            Some(available_clusters) if available_clusters.end == cluster_number => available_clusters.end += 1,
+            _ => self.available_clusters.push(cluster_number..cluster_number + 1),
+        }
+    }
+
+    fn append_bad_cluster(&mut self) {
+        let cluster = Cluster::bad_cluster(self);
+        self.clusters.push(cluster);
     }
 
     fn max_cluster_number(&self) -> u32 {
         self.clusters
             .iter()
             .map(|cluster| cluster.max_cluster_number())
+            .chain(self.available_clusters
+                .iter()
+                .map(|available_clusters| available_clusters.end - 1))
             .max()
             .expect("Can't get max cluster number.")
     }
 }
 
-impl Into<Vec<u8>> for &Clusters {
-    fn into(self) -> Vec<u8> {
-        (FIRST_CLUSTER_NUMBER..=self.max_cluster_number())
-            .map(|cluster_number| match self.cluster_bytes(cluster_number) {
-                Some(bytes) => bytes,
-                None => (0..self.cluster_size)
-                    .map(|_| 0u8)
-                    .collect::<Vec<u8>>(),
-            })
-            .flatten()
-            .collect()
-    }
-}
-
 #[derive(Debug)]
 struct Cluster {
     cluster_number: u32,
     bytes: Vec<u8>,
     next_cluster: Option<Box<Self>>,
-    used: Option<bool>
+    used: Option<bool>,
+    bad: bool,
 }
 
 impl Cluster {
-    fn available_cluster(clusters: &mut Clusters) -> Self {
-        let blank: u8 = 0x00;
-        let cluster: Vec<u8> = (0..clusters.cluster_size)
-            .map(|_| blank)
-            .collect();
-        let mut cluster: Self = Self::new(clusters, &cluster, blank).expect("Can't create an available cluster.");
-        cluster.used = Some(false);
-        cluster
+    fn bad_cluster(clusters: &mut Clusters) -> Self {
+        let cluster_number: u32 = clusters.next_cluster_number;
+        clusters.next_cluster_number += 1;
+        // A bad cluster is never allocated to a file or a directory, so it is marked as in use in the allocation bitmap.
+        let bytes: Vec<u8> = vec![];
+        let next_cluster: Option<Box<Self>> = None;
+        let used: Option<bool> = Some(true);
+        let bad: bool = true;
+        Self {
+            cluster_number,
+            bytes,
+            next_cluster,
+            used,
+            bad,
+        }
     }
 
     fn cluster_chain(&self) -> HashMap<u32, Option<u32>> {
@@ -224,15 +298,13 @@ impl Cluster {
         bytes
     }
 
-    fn bytes(&self, cluster_number: u32) -> Option<Vec<u8>> {
-        if cluster_number == self.cluster_number {
-            Some(self.bytes.clone())
-        } else {
-            match &self.next_cluster {
-                Some(next_cluster) => next_cluster.bytes(cluster_number),
-                None => None,
-            }
-        }
+    fn bytes_chain(&self) -> Vec<(u32, &Vec<u8>)> {
+        let mut bytes_chain: Vec<(u32, &Vec<u8>)> = match &self.next_cluster {
+            Some(next_cluster) => next_cluster.bytes_chain(),
+            None => vec![],
+        };
+        bytes_chain.push((self.cluster_number, &self.bytes));
+        bytes_chain
     }
 
     fn max_cluster_number(&self) -> u32 {
@@ -254,8 +326,7 @@ impl Cluster {
         if bytes.len() == 0 {
             return None;
         }
-        let cluster_number: u32 = clusters.next_cluster_number;
-        clusters.next_cluster_number += 1;
+        let cluster_number: u32 = clusters.allocate_cluster_number();
         let remaining_bytes: Vec<u8> = if clusters.cluster_size < bytes.len() {
             bytes.split_off(clusters.cluster_size)
         } else {
@@ -267,11 +338,13 @@ impl Cluster {
             None => None,
         };
         let used: Option<bool> = Some(true);
+        let bad: bool = false;
         Some(Self {
             cluster_number,
             bytes,
             next_cluster,
             used,
+            bad,
         })
     }
 
@@ -301,11 +374,13 @@ impl Cluster {
             _ => Some(Box::new(Self::read(clusters))),
         };
         let used: Option<bool> = None;
+        let bad: bool = false;
         Self {
             cluster_number,
             bytes,
             next_cluster,
-            used
+            used,
+            bad,
         }
     }
 
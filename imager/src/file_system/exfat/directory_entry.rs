@@ -86,44 +86,37 @@ impl DirectoryEntry {
         }
     }
 
-    pub fn allocation_bitmaps(clusters: &mut cluster::Clusters, root_directory_entries: &Vec<Self>, num_of_fats: usize) -> Vec<Self> {
-        let num_of_clusters: usize = clusters.len();
+    // The allocation bitmaps and the root directory are placed after the available clusters filling the volume.
+    pub fn allocation_bitmaps(clusters: &mut cluster::Clusters, root_directory_entries: &Vec<Self>, num_of_fats: usize, cluster_count: usize) -> Vec<Self> {
         let cluster_size: usize = clusters.cluster_size();
-        let bits_per_cluster: usize = 8 * cluster_size;
-        let allocation_bitmap_directory_entry: usize = 1;
-        let num_of_root_directory_entries: usize = root_directory_entries.len() + allocation_bitmap_directory_entry;
-        let size_of_root_directory_entries: usize = DIRECTORY_ENTRY_SIZE * num_of_root_directory_entries;
-        let num_of_clusters_of_root_directory: usize = (size_of_root_directory_entries + cluster_size - 1) / cluster_size;
-        let num_of_clusters: usize = num_of_clusters + num_of_clusters_of_root_directory;
-        let mut num_of_allocation_bitmap_clusters: usize = 0;
-        loop {
-            let num_of_clusters: usize = num_of_clusters + num_of_fats * num_of_allocation_bitmap_clusters;
-            let necessary_allocation_bitmap_clusters: usize = (num_of_clusters + bits_per_cluster - 1) / bits_per_cluster;
-            if necessary_allocation_bitmap_clusters <= num_of_allocation_bitmap_clusters {
-                break;
-            }
-            num_of_allocation_bitmap_clusters += 1;
-        }
-        let allocation_bitmaps: Vec<allocation_bitmap::AllocationBitmap> = (0..num_of_fats)
-            .map(|_| allocation_bitmap::AllocationBitmap::new(clusters))
-            .collect();
-        let allocation_bitmaps: Vec<Vec<u8>> = allocation_bitmaps
-            .into_iter()
-            .map(|ref allocation_bitmap| allocation_bitmap.into())
-            .collect();
-        let allocation_bitmap_length: usize = allocation_bitmaps
+        let root_directory_size: usize = root_directory_entries
             .iter()
-            .map(|allocation_bitmap| allocation_bitmap.len())
-            .max()
-            .expect("Can't get allocation_bitmap_length.");
-        let allocation_bitmaps: Vec<u32> = allocation_bitmaps
-            .into_iter()
-            .map(|allocation_bitmap| clusters.append(&allocation_bitmap, 0xff))
-            .collect();
-        allocation_bitmaps
-            .into_iter()
-            .enumerate()
-            .map(|(i, cluster_number)| Self::allocation_bitmap(i, cluster_number, allocation_bitmap_length))
+            .map(|directory_entry| Into::<Vec<u8>>::into(directory_entry).len())
+            .sum::<usize>() + num_of_fats * DIRECTORY_ENTRY_SIZE;
+        let root_directory_clusters: usize = (root_directory_size + cluster_size - 1) / cluster_size;
+        let allocation_bitmap_clusters = |cluster_count: usize| ((cluster_count + 7) / 8 + cluster_size - 1) / cluster_size;
+        clusters.fix_size(cluster_count, num_of_fats * allocation_bitmap_clusters(cluster_count) + root_directory_clusters);
+        // The volume grows if the clusters don't fit in it.
+        let mut cluster_count: usize = cluster_count;
+        let reserved_clusters: Vec<u32> = loop {
+            let reserved_clusters: Vec<u32> = clusters.next_cluster_numbers(num_of_fats * allocation_bitmap_clusters(cluster_count) + root_directory_clusters);
+            let necessary_cluster_count: usize = match reserved_clusters.last() {
+                Some(last_cluster) => (last_cluster - cluster::FIRST_CLUSTER_NUMBER + 1) as usize,
+                None => 0,
+            };
+            if necessary_cluster_count <= cluster_count {
+                break reserved_clusters;
+            }
+            cluster_count = necessary_cluster_count;
+        };
+        let allocation_bitmap = allocation_bitmap::AllocationBitmap::new(clusters, cluster_count, &reserved_clusters);
+        let allocation_bitmap: Vec<u8> = (&allocation_bitmap).into();
+        let allocation_bitmap_length: usize = allocation_bitmap.len();
+        (0..num_of_fats)
+            .map(|i| {
+                let cluster_number: u32 = clusters.append(&allocation_bitmap, 0xff);
+                Self::allocation_bitmap(i, cluster_number, allocation_bitmap_length)
+            })
             .collect()
     }
 
@@ -77,7 +77,7 @@ impl From<&DirectoryEntry> for RawFile {
                 let reserved_1: u16 = 0;
                 let create_timestamp: u32 = create_time.fat_timestamp();
                 let last_modified_timestamp: u32 = modified_time.fat_timestamp();
-                let last_accessed_timestamp: u32 = accessed_time.fat_timestamp();
+                let last_accessed_timestamp: u32 = accessed_time.rounded_fat_timestamp();
                 let create_10ms_increment: u8 = create_time.get_10ms_increment();
                 let last_modified_10ms_increment: u8 = modified_time.get_10ms_increment();
                 let create_utc_offset: i8 = create_time.utc_offset();
@@ -1,22 +1,48 @@
 use {
     std::{
-        collections::HashMap,
+        collections::{
+            BTreeSet,
+            HashMap,
+        },
         mem,
     },
     super::cluster,
 };
 
+const BAD_CLUSTER: u32 = 0xfffffff7;
+
 #[derive(Debug)]
 pub struct Fat {
+    bad_clusters: BTreeSet<u32>,
+    // Available clusters are absent from the cluster chain.
     cluster_chain: HashMap<u32, Option<u32>>,
+    cluster_count: u32,
     sector_size: usize,
 }
 
 impl Fat {
+    pub fn bad_clusters(&self) -> &BTreeSet<u32> {
+        &self.bad_clusters
+    }
+
+    // The broken mirror has lost all cluster chains recorded in the FAT.
+    pub fn broken_mirror(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.into();
+        let reserved_entries_size: usize = 2 * mem::size_of::<u32>();
+        bytes[reserved_entries_size..]
+            .iter_mut()
+            .for_each(|byte| *byte = 0x00);
+        bytes
+    }
+
     pub fn new(clusters: &cluster::Clusters, sector_size: usize) -> Self {
+        let bad_clusters: BTreeSet<u32> = clusters.bad_clusters();
         let cluster_chain: HashMap<u32, Option<u32>> = clusters.cluster_chain();
+        let cluster_count: u32 = clusters.number_of_clusters() as u32;
         Self {
+            bad_clusters,
             cluster_chain,
+            cluster_count,
             sector_size,
         }
     }
@@ -33,10 +59,16 @@ impl Fat {
                 }
             })
             .collect();
+        let bad_clusters: BTreeSet<u32> = (0..cluster_chain.len())
+            .filter(|cluster_number| cluster_chain[*cluster_number] == BAD_CLUSTER)
+            .map(|cluster_number| cluster_number as u32)
+            .filter(|cluster_number| cluster::FIRST_CLUSTER_NUMBER <= *cluster_number && *cluster_number < cluster_count + cluster::FIRST_CLUSTER_NUMBER)
+            .collect();
         let cluster_chain: HashMap<u32, Option<u32>> = (0..cluster_chain.len())
+            .filter(|cluster_number| cluster_chain[*cluster_number] != BAD_CLUSTER)
             .map(|cluster_number| {
                 let next_cluster_number: u32 = cluster_chain[cluster_number];
-                let next_cluster_number: Option<u32> = if 2 <= next_cluster_number && next_cluster_number < 0xfffffff8 {
+                let next_cluster_number: Option<u32> = if 2 <= next_cluster_number && next_cluster_number < BAD_CLUSTER {
                     Some(next_cluster_number)
                 } else {
                     None
@@ -47,7 +79,9 @@ impl Fat {
             .filter(|(cluster_number, _)| cluster::FIRST_CLUSTER_NUMBER <= *cluster_number && *cluster_number < cluster_count + cluster::FIRST_CLUSTER_NUMBER)
             .collect();
         Self {
+            bad_clusters,
             cluster_chain,
+            cluster_count,
             sector_size,
         }
     }
@@ -93,10 +127,7 @@ impl Fat {
 
 impl Into<Vec<u8>> for &Fat {
     fn into(self) -> Vec<u8> {
-        let max_cluster_number: u32 = *self.cluster_chain
-            .keys()
-            .max()
-            .expect("Can't find max cluster number.");
+        let max_cluster_number: u32 = cluster::FIRST_CLUSTER_NUMBER + self.cluster_count - 1;
         let cluster_chain: Vec<u32> = (0..=max_cluster_number)
             .map(|cluster_number| match cluster_number {
                 0 => 0xfffffff8,
@@ -106,7 +137,11 @@ impl Into<Vec<u8>> for &Fat {
                         Some(next_cluster_number) => *next_cluster_number,
                         None => 0xffffffff,
                     },
-                    None => 0xffffffff,
+                    None => if self.bad_clusters.contains(&cluster_number) {
+                        BAD_CLUSTER
+                    } else {
+                        0xffffffff
+                    },
                 },
             })
             .collect();
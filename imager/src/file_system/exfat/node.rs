@@ -66,6 +66,7 @@ impl Content {
         clusters: &mut cluster::Clusters,
         upcase_table: &upcase_table::UpcaseTable,
         has_volume_guid: bool,
+        cluster_count: usize,
         rand_generator: &mut rand::Generator,
     ) -> (Self, u32, usize) {
         if source.is_file() {
@@ -85,7 +86,7 @@ impl Content {
                         let source: &PathBuf = &directory.path();
                         let destination: &mut PathBuf = &mut destination.to_path_buf();
                         destination.push(source.file_name().expect("Can't create a file or directory."));
-                        Node::new(source, destination, false, boot_sector, clusters, upcase_table, has_volume_guid, rand_generator)
+                        Node::new(source, destination, false, boot_sector, clusters, upcase_table, has_volume_guid, cluster_count, rand_generator)
                     })
                     .collect(),
                 _ => vec![],
@@ -124,16 +125,7 @@ impl Content {
                 None => (),
             }
             let allocation_bitmaps: Vec<directory_entry::DirectoryEntry> = if is_root {
-                let volume_size_lower_limit: usize  = 1 << 20;
-                let cluster_heap_offset: usize = (boot_sector.fat_offset() as usize + boot_sector.num_of_fats() as usize) * boot_sector.bytes_per_sector();
-                let cluster_heap_offset: usize = (cluster_heap_offset + boot_sector.cluster_size() - 1) / boot_sector.cluster_size();
-                let cluster_heap_offset: usize = cluster_heap_offset * boot_sector.cluster_size();
-                let reserved_clusters: usize = 1/* root directory */ + boot_sector.num_of_fats()/* allocation bitmap */;
-                let reserved_clusters_size: usize = reserved_clusters * boot_sector.cluster_size();
-                let reserved_size: usize = cluster_heap_offset + reserved_clusters_size;
-                let fixed_clusters_size: usize = volume_size_lower_limit - reserved_size;
-                clusters.fix_size(fixed_clusters_size);
-                directory_entry::DirectoryEntry::allocation_bitmaps(clusters, &directory_entries, boot_sector.num_of_fats())
+                directory_entry::DirectoryEntry::allocation_bitmaps(clusters, &directory_entries, boot_sector.num_of_fats(), cluster_count)
             } else {
                 vec![]
             };
@@ -365,11 +357,12 @@ impl Node {
         clusters: &mut cluster::Clusters,
         upcase_table: &upcase_table::UpcaseTable,
         has_volume_guid: bool,
+        cluster_count: usize,
         rand_generator: &mut rand::Generator,
     ) -> Rc<Self> {
         let destination = &PathBuf::from("/");
         let is_root: bool = true;
-        Self::new(source, destination, is_root, boot_sector, clusters, upcase_table, has_volume_guid, rand_generator)
+        Self::new(source, destination, is_root, boot_sector, clusters, upcase_table, has_volume_guid, cluster_count, rand_generator)
     }
 
     pub fn upcase_table(&self) -> upcase_table::UpcaseTable {
@@ -395,9 +388,10 @@ impl Node {
         clusters: &mut cluster::Clusters,
         upcase_table: &upcase_table::UpcaseTable,
         has_volume_guid: bool,
+        cluster_count: usize,
         rand_generator: &mut rand::Generator,
     ) -> Rc<Self> {
-        let (content, first_cluster, length) = Content::new(&source, &destination, is_root, boot_sector, clusters, upcase_table, has_volume_guid, rand_generator);
+        let (content, first_cluster, length) = Content::new(&source, &destination, is_root, boot_sector, clusters, upcase_table, has_volume_guid, cluster_count, rand_generator);
         let destination: PathBuf = destination.to_path_buf();
         let directory_entry = if is_root {
             None
@@ -488,8 +482,17 @@ impl fmt::Display for Node {
         let path: String = self
             .upcase_table()
             .capitalize_str(&path);
+        let times: String = match &self.directory_entry {
+            Some(directory_entry::DirectoryEntry::File {
+                file_attributes: _,
+                create_time,
+                modified_time,
+                accessed_time,
+                stream_extension: _,
+            }) => format!("create_time: {}\nmodified_time: {}\naccessed_time: {}\n", create_time, modified_time, accessed_time),
+            _ => String::new(),
+        };
         let content: String = format!("{}", self.content);
-        write!(f, "{}\n{}", path, content)
+        write!(f, "{}\n{}{}", path, times, content)
     }
 }
-
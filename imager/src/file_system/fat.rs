@@ -4,23 +4,33 @@ mod directory_entry;
 mod fat;
 mod node;
 
-use std::{
-    fmt,
-    fs,
-    path::PathBuf,
+use {
+    std::{
+        collections::BTreeSet,
+        fmt,
+        fs,
+        path::PathBuf,
+    },
+    super::super::{
+        fault,
+        output,
+    },
 };
 
+const ORPHAN_CLUSTER: &[u8] = b"THEOS orphan cluster";
+
 #[derive(Debug)]
 pub struct Fat {
     boot_sector: boot_sector::BootSector,
     fat: fat::Fat,
     clusters: cluster::Clusters,
+    faults: BTreeSet<fault::Fault>,
     root_directory: node::Content,
     volume_label: String,
 }
 
 impl Fat {
-    pub fn new(boot_sector_candidates: Vec<PathBuf>, root: &PathBuf) -> Self {
+    pub fn new(boot_sector_candidates: Vec<PathBuf>, root: &PathBuf, bad_clusters: &BTreeSet<u32>, faults: &BTreeSet<fault::Fault>) -> Result<Self, String> {
         let boot_sector_candidates: Vec<boot_sector::BootSector> = boot_sector_candidates
             .into_iter()
             .map(|boot_sector| {
@@ -54,17 +64,53 @@ impl Fat {
             })
             .0
             .expect("Boot sector candidates are not unanimous about volume label.");
-        let (root_directory, clusters): (node::Content, cluster::Clusters) = node::Content::root(&root, cluster_size);
+        let (root_directory, mut clusters): (node::Content, cluster::Clusters) = node::Content::root(&root, cluster_size, bad_clusters);
+        if faults.contains(&fault::Fault::OrphanCluster) {
+            clusters.append(&ORPHAN_CLUSTER.to_vec(), 0);
+        }
+        if faults.contains(&fault::Fault::BadChecksum) {
+            return Err("A FAT file system has no boot checksum.".to_string());
+        }
         let boot_sector = boot_sector::BootSector::select(boot_sector_candidates, &clusters);
+        if faults.contains(&fault::Fault::BrokenFatMirror) && boot_sector.fats() < 2 {
+            return Err("Can't break the FAT mirror of a FAT file system with only one FAT.".to_string());
+        }
         let fat = fat::Fat::new(&clusters, &boot_sector);
         let boot_sector = boot_sector.fix(&fat, &clusters);
-        Self {
+        let faults: BTreeSet<fault::Fault> = faults.clone();
+        Ok(Self {
             boot_sector,
             fat,
             clusters,
+            faults,
             root_directory,
             volume_label,
-        }
+        })
+    }
+
+    pub fn write(&self, output: &mut output::Output) {
+        let mut boot_sector: Vec<u8> = (&self.boot_sector).into();
+        let sector_size: usize = self.boot_sector.sector_size();
+        let reserved_sectors: usize = self.boot_sector.reserved_sectors();
+        let reserved_size: usize = reserved_sectors * sector_size;
+        boot_sector.resize(reserved_size, 0x00);
+        output.write(&boot_sector);
+        let fat: Vec<u8> = (&self.fat).into();
+        let fat_mirror: Vec<u8> = if self.faults.contains(&fault::Fault::BrokenFatMirror) {
+            self.fat.broken_mirror()
+        } else {
+            fat.clone()
+        };
+        (0..self.boot_sector.fats()).for_each(|i| output.write(match i {
+            0 => &fat,
+            _ => &fat_mirror,
+        }));
+        let root_directory_entries: usize = self.boot_sector
+            .root_directory_entries()
+            .expect("Can't write a FAT file system.");
+        let root_directory: Vec<u8> = self.root_directory.root_into_bytes(&self.volume_label, root_directory_entries);
+        output.write(&root_directory);
+        self.clusters.write(output);
     }
 }
 
@@ -76,13 +122,23 @@ impl fmt::Display for Fat {
             .collect::<Vec<String>>()
             .join("\n");
         let fat: String = format!("{}", self.fat);
+        let faults: String = self.faults
+            .iter()
+            .map(|fault| format!("fault: {}", fault))
+            .collect::<Vec<String>>()
+            .join("\n");
         let root_directory: String = format!("{}", self.root_directory);
         let fat: Vec<String> = vec![
             boot_sector,
             fat,
+            faults,
             root_directory,
         ];
-        let fat: String = fat.join("\n");
+        let fat: String = fat
+            .into_iter()
+            .filter(|element| !element.is_empty())
+            .collect::<Vec<String>>()
+            .join("\n");
         write!(f, "{}", fat)
     }
 }
@@ -103,36 +159,14 @@ impl From<&Vec<u8>> for Fat {
         let clusters: Vec<u8> = bytes[clusters_offset..].to_vec();
         let clusters = cluster::Clusters::read(clusters, &fat, cluster_size);
         let (root_directory, volume_label): (node::Content, String) = node::Content::read_root(&root_directory, &clusters);
+        let faults: BTreeSet<fault::Fault> = BTreeSet::new();
         Self {
             boot_sector,
             fat,
             clusters,
+            faults,
             root_directory,
             volume_label,
         }
     }
 }
-
-impl Into<Vec<u8>> for &Fat {
-    fn into(self) -> Vec<u8> {
-        let mut boot_sector: Vec<u8> = (&self.boot_sector).into();
-        let sector_size: usize = self.boot_sector.sector_size();
-        let reserved_sectors: usize = self.boot_sector.reserved_sectors();
-        let reserved_size: usize = reserved_sectors * sector_size;
-        boot_sector.resize(reserved_size, 0x00);
-        let fat: Vec<u8> = (&self.fat).into();
-        let fat: Vec<u8> = fat.repeat(self.boot_sector.fats());
-        let root_directory_entries: usize = self.boot_sector
-            .root_directory_entries()
-            .expect("Can't convert a FAT file system into bytes.");
-        let root_directory: Vec<u8> = self.root_directory.root_into_bytes(&self.volume_label, root_directory_entries);
-        let clusters: Vec<u8> = (&self.clusters).into();
-        vec![
-            boot_sector,
-            fat,
-            root_directory,
-            clusters,
-        ].concat().to_vec()
-    }
-}
-
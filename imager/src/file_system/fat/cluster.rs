@@ -1,15 +1,20 @@
 use {
     std::collections::{
+        BTreeSet,
         HashMap,
         VecDeque,
     },
-    super::fat,
+    super::{
+        fat,
+        super::super::output,
+    },
 };
 
 pub const FIRST_CLUSTER_NUMBER: u32 = 2;
 
 #[derive(Debug)]
 pub struct Clusters {
+    bad_clusters: BTreeSet<u32>,
     cluster_size: usize,
     clusters: Vec<Cluster>,
     next_cluster_number: u32,
@@ -31,9 +36,32 @@ impl Clusters {
         self.clusters.push(cluster);
     }
 
+    // Fill the volume with available clusters until it contains all bad clusters.
+    pub fn append_bad_clusters(&mut self) {
+        while let Some(bad_cluster) = self.bad_clusters
+            .range(self.next_cluster_number..)
+            .next()
+            .copied() {
+            if bad_cluster == self.next_cluster_number {
+                self.append_bad_cluster();
+            } else {
+                self.append_available_cluster();
+            }
+        }
+    }
+
+    pub fn bad_clusters(&self) -> BTreeSet<u32> {
+        self.clusters
+            .iter()
+            .filter(|cluster| cluster.bad)
+            .map(|cluster| cluster.cluster_number)
+            .collect()
+    }
+
     pub fn cluster_chain(&self) -> HashMap<u32, Option<u32>> {
         self.clusters
             .iter()
+            .filter(|cluster| !cluster.bad && cluster.used != Some(false))
             .map(|cluster| cluster.cluster_chain())
             .fold(HashMap::<u32, Option<u32>>::new(), |cluster_chains, cluster_chain| {
                 let mut cluster_chains: HashMap<u32, Option<u32>> = cluster_chains;
@@ -56,19 +84,22 @@ impl Clusters {
     }
 
     pub fn fix_size(&mut self, size: usize) {
-        while self.number_of_clusters() * self.cluster_size < size {
-            self.append_available_cluster();
-        }
+        let number_of_clusters: usize = self.number_of_clusters();
+        let fixed_number_of_clusters: usize = (size + self.cluster_size - 1) / self.cluster_size;
+        (number_of_clusters..fixed_number_of_clusters).for_each(|_| self.append_available_cluster());
+        self.append_bad_clusters();
     }
 
     pub fn len(&self) -> usize {
         self.clusters.len()
     }
 
-    pub fn new(cluster_size: usize) -> Self {
+    pub fn new(cluster_size: usize, bad_clusters: &BTreeSet<u32>) -> Self {
+        let bad_clusters: BTreeSet<u32> = bad_clusters.clone();
         let clusters: Vec<Cluster> = vec![];
         let next_cluster_number: u32 = FIRST_CLUSTER_NUMBER;
         Self {
+            bad_clusters,
             cluster_size,
             clusters,
             next_cluster_number,
@@ -76,7 +107,9 @@ impl Clusters {
     }
 
     pub fn next_cluster_number(&self) -> u32 {
-        self.next_cluster_number
+        (self.next_cluster_number..)
+            .find(|cluster_number| !self.bad_clusters.contains(cluster_number))
+            .expect("Can't get next cluster number.")
     }
 
     pub fn number_of_clusters(&self) -> usize {
@@ -99,6 +132,7 @@ impl Clusters {
             .enumerate()
             .map(|(cluster, bytes)| ((cluster as u32) + FIRST_CLUSTER_NUMBER, bytes.to_vec()))
             .collect();
+        let bad_clusters: BTreeSet<u32> = fat.bad_clusters().clone();
         let fat: HashMap<u32, Vec<u32>> = fat.to_chains();
         let clusters: Vec<Vec<(u32, Vec<u8>)>> = fat
             .into_iter()
@@ -122,6 +156,7 @@ impl Clusters {
             .max()
             .expect("Can't read clusters.") + 1;
         Self {
+            bad_clusters,
             cluster_size,
             clusters,
             next_cluster_number,
@@ -138,10 +173,31 @@ impl Clusters {
             .collect()
     }
 
-    fn cluster_bytes(&self, cluster_number: u32) -> Option<Vec<u8>> {
-        self.clusters
+    pub fn write(&self, output: &mut output::Output) {
+        let cluster_bytes: HashMap<u32, &Vec<u8>> = self.clusters
             .iter()
-            .find_map(|cluster| cluster.bytes(cluster_number))
+            .map(|cluster| cluster.bytes_chain())
+            .flatten()
+            .collect();
+        (FIRST_CLUSTER_NUMBER..=self.max_cluster_number())
+            .for_each(|cluster_number| match cluster_bytes.get(&cluster_number) {
+                Some(bytes) if bytes.len() == self.cluster_size => output.write(bytes),
+                _ => output.write_zeros(self.cluster_size),
+            });
+    }
+
+    fn allocate_cluster_number(&mut self) -> u32 {
+        while self.bad_clusters.contains(&self.next_cluster_number) {
+            self.append_bad_cluster();
+        }
+        let cluster_number: u32 = self.next_cluster_number;
+        self.next_cluster_number += 1;
+        cluster_number
+    }
+
+    fn append_bad_cluster(&mut self) {
+        let cluster = Cluster::bad_cluster(self);
+        self.clusters.push(cluster);
     }
 
     fn max_cluster_number(&self) -> u32 {
@@ -153,37 +209,47 @@ impl Clusters {
     }
 }
 
-impl Into<Vec<u8>> for &Clusters {
-    fn into(self) -> Vec<u8> {
-        (FIRST_CLUSTER_NUMBER..=self.max_cluster_number())
-            .map(|cluster_number| match self.cluster_bytes(cluster_number) {
-                Some(bytes) => bytes,
-                None => (0..self.cluster_size)
-                    .map(|_| 0u8)
-                    .collect::<Vec<u8>>(),
-            })
-            .flatten()
-            .collect()
-    }
-}
-
 #[derive(Debug)]
 struct Cluster {
     cluster_number: u32,
     bytes: Vec<u8>,
     next_cluster: Option<Box<Self>>,
-    used: Option<bool>
+    used: Option<bool>,
+    bad: bool,
 }
 
 impl Cluster {
     fn available_cluster(clusters: &mut Clusters) -> Self {
-        let blank: u8 = 0x00;
-        let cluster: Vec<u8> = (0..clusters.cluster_size)
-            .map(|_| blank)
-            .collect();
-        let mut cluster: Self = Self::new(clusters, &cluster, blank).expect("Can't create an available cluster.");
-        cluster.used = Some(false);
-        cluster
+        let cluster_number: u32 = clusters.allocate_cluster_number();
+        // An available cluster is filled with zeros when the image is written.
+        let bytes: Vec<u8> = vec![];
+        let next_cluster: Option<Box<Self>> = None;
+        let used: Option<bool> = Some(false);
+        let bad: bool = false;
+        Self {
+            cluster_number,
+            bytes,
+            next_cluster,
+            used,
+            bad,
+        }
+    }
+
+    fn bad_cluster(clusters: &mut Clusters) -> Self {
+        let cluster_number: u32 = clusters.next_cluster_number;
+        clusters.next_cluster_number += 1;
+        // A bad cluster is never allocated to a file or a directory, so it is marked as in use in the allocation bitmap.
+        let bytes: Vec<u8> = vec![];
+        let next_cluster: Option<Box<Self>> = None;
+        let used: Option<bool> = Some(true);
+        let bad: bool = true;
+        Self {
+            cluster_number,
+            bytes,
+            next_cluster,
+            used,
+            bad,
+        }
     }
 
     fn cluster_chain(&self) -> HashMap<u32, Option<u32>> {
@@ -207,15 +273,13 @@ impl Cluster {
         bytes
     }
 
-    fn bytes(&self, cluster_number: u32) -> Option<Vec<u8>> {
-        if cluster_number == self.cluster_number {
-            Some(self.bytes.clone())
-        } else {
-            match &self.next_cluster {
-                Some(next_cluster) => next_cluster.bytes(cluster_number),
-                None => None,
-            }
-        }
+    fn bytes_chain(&self) -> Vec<(u32, &Vec<u8>)> {
+        let mut bytes_chain: Vec<(u32, &Vec<u8>)> = match &self.next_cluster {
+            Some(next_cluster) => next_cluster.bytes_chain(),
+            None => vec![],
+        };
+        bytes_chain.push((self.cluster_number, &self.bytes));
+        bytes_chain
     }
 
     fn max_cluster_number(&self) -> u32 {
@@ -237,8 +301,7 @@ impl Cluster {
         if bytes.len() == 0 {
             return None;
         }
-        let cluster_number: u32 = clusters.next_cluster_number;
-        clusters.next_cluster_number += 1;
+        let cluster_number: u32 = clusters.allocate_cluster_number();
         let remaining_bytes: Vec<u8> = if clusters.cluster_size < bytes.len() {
             bytes.split_off(clusters.cluster_size)
         } else {
@@ -250,11 +313,13 @@ impl Cluster {
             None => None,
         };
         let used: Option<bool> = Some(true);
+        let bad: bool = false;
         Some(Self {
             cluster_number,
             bytes,
             next_cluster,
             used,
+            bad,
         })
     }
 
@@ -284,11 +349,13 @@ impl Cluster {
             _ => Some(Box::new(Self::read(clusters))),
         };
         let used: Option<bool> = None;
+        let bad: bool = false;
         Self {
             cluster_number,
             bytes,
             next_cluster,
-            used
+            used,
+            bad,
         }
     }
 }
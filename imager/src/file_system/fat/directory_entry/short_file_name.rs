@@ -96,13 +96,13 @@ impl From<&DirectoryEntry> for ShortFileName {
             let name_flags: u8 = name_flags.into();
             let created_time_centi_second: u8 = created_time.fat_centi_second();
             let created_time: u32 = created_time.fat_timestamp();
-            let accessed_date: u16 = (accessed_time.fat_timestamp() >> 16) as u16;
+            let accessed_date: u16 = (accessed_time.rounded_fat_timestamp() >> 16) as u16;
             let cluster: u32 = match *cluster.borrow() {
                 Some(cluster) => cluster,
                 None => 0,
             };
             let cluster_high: u16 = (cluster >> 16) as u16;
-            let written_time: u32 = written_time.fat_timestamp();
+            let written_time: u32 = written_time.rounded_fat_timestamp();
             let cluster_low: u16 = cluster as u16;
             let size: u32 = *size as u32;
             Self {
@@ -1,6 +1,9 @@
 use {
     std::{
-        collections::HashMap,
+        collections::{
+            BTreeSet,
+            HashMap,
+        },
         fmt,
     },
     super::{
@@ -9,8 +12,11 @@ use {
     },
 };
 
+const BAD_CLUSTER: u32 = 0x0ffffff7;
+
 #[derive(Debug)]
 pub struct Fat {
+    bad_clusters: BTreeSet<u32>,
     bit: Bit,
     cluster_chain: HashMap<u32, Option<u32>>,
     media: u8,
@@ -18,12 +24,32 @@ pub struct Fat {
 }
 
 impl Fat {
+    pub fn bad_clusters(&self) -> &BTreeSet<u32> {
+        &self.bad_clusters
+    }
+
+    // The broken mirror has lost all cluster chains recorded in the FAT.
+    pub fn broken_mirror(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.into();
+        let reserved_entries_size: usize = match self.bit {
+            Bit::Fat12 => 3,
+            Bit::Fat16 => 4,
+            Bit::Fat32 => 8,
+        };
+        bytes[reserved_entries_size..]
+            .iter_mut()
+            .for_each(|byte| *byte = 0x00);
+        bytes
+    }
+
     pub fn new(clusters: &cluster::Clusters, boot_sector: &boot_sector::BootSector) -> Self {
+        let bad_clusters: BTreeSet<u32> = clusters.bad_clusters();
         let bit: Bit = boot_sector.into();
         let cluster_chain: HashMap<u32, Option<u32>> = clusters.cluster_chain();
         let media: u8 = boot_sector.media();
         let sector_size: usize = boot_sector.sector_size();
         Self {
+            bad_clusters,
             bit,
             cluster_chain,
             media,
@@ -59,6 +85,17 @@ impl Fat {
                     .fold(0x00000000u32, |cluster, byte| (cluster << 8) + (*byte as u32)))
                 .collect(),
         };
+        let bad_cluster: u32 = match bit {
+            Bit::Fat12 => BAD_CLUSTER & 0x00000fff,
+            Bit::Fat16 => BAD_CLUSTER & 0x0000ffff,
+            Bit::Fat32 => BAD_CLUSTER,
+        };
+        let bad_clusters: BTreeSet<u32> = cluster_chain
+            .iter()
+            .enumerate()
+            .filter(|(_, next_cluster)| **next_cluster == bad_cluster)
+            .map(|(cluster, _)| cluster as u32)
+            .collect();
         let cluster_chain: HashMap<u32, Option<u32>> = cluster_chain
             .into_iter()
             .enumerate()
@@ -94,6 +131,7 @@ impl Fat {
             })
             .collect();
         Self {
+            bad_clusters,
             bit,
             cluster_chain,
             media,
@@ -158,12 +196,19 @@ impl fmt::Display for Fat {
             })
             .collect::<Vec<String>>()
             .join("\n");
+        let bad_clusters: String = self.bad_clusters
+            .iter()
+            .map(|cluster| format!("bad_cluster: {:0digits$x}", cluster))
+            .collect::<Vec<String>>()
+            .join("\n");
         let fat: Vec<String> = vec![
             media,
             chains,
+            bad_clusters,
         ];
         let fat: String = fat
             .into_iter()
+            .filter(|element| !element.is_empty())
             .map(|element| element
                 .lines()
                 .map(|line| format!("fat.{}", line))
@@ -181,6 +226,7 @@ impl Into<Vec<u8>> for &Fat {
         let fat1: u32 = 0xffffffff;
         let max_cluster_number: u32 = *self.cluster_chain
             .keys()
+            .chain(self.bad_clusters.iter())
             .max()
             .expect("Can't find max cluster number.");
         let mut cluster_chain: Vec<u32> = (0..=max_cluster_number)
@@ -192,7 +238,11 @@ impl Into<Vec<u8>> for &Fat {
                         Some(next_cluster_number) => *next_cluster_number,
                         None => 0xffffffff,
                     },
-                    None => 0xffffffff,
+                    None => if self.bad_clusters.contains(&cluster_number) {
+                        BAD_CLUSTER
+                    } else {
+                        0xffffffff
+                    },
                 },
             })
             .collect();
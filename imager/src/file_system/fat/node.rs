@@ -4,6 +4,7 @@ use {
             Ref,
             RefCell,
         },
+        collections::BTreeSet,
         fmt,
         fs,
         path::PathBuf,
@@ -143,7 +144,7 @@ impl Content {
         (root, volume_label)
     }
 
-    pub fn root(source: &PathBuf, cluster_size: usize) -> (Self, cluster::Clusters) {
+    pub fn root(source: &PathBuf, cluster_size: usize, bad_clusters: &BTreeSet<u32>) -> (Self, cluster::Clusters) {
         if let Self::Directory {
             children,
             node: _,
@@ -158,11 +159,12 @@ impl Content {
                 node,
             };
             // Temporary clusters to determine cluster number of each node.
-            let mut clusters = cluster::Clusters::new(cluster_size);
+            let mut clusters = cluster::Clusters::new(cluster_size, bad_clusters);
             root.write_root(&mut clusters);
             // Correct clusters.
-            let mut clusters = cluster::Clusters::new(cluster_size);
+            let mut clusters = cluster::Clusters::new(cluster_size, bad_clusters);
             root.write_root(&mut clusters);
+            clusters.append_bad_clusters();
             (root, clusters)
         } else {
             panic!("Can't generate a root directory.");
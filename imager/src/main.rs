@@ -1,15 +1,17 @@
 mod args;
+mod fault;
 mod file_system;
 mod guid;
 mod mac_address;
+mod output;
 mod rand;
 mod time;
+mod time_zone;
 
 use std::{
     env,
     fs,
-    io,
-    io::Write,
+    process,
 };
 
 fn main() {
@@ -17,7 +19,9 @@ fn main() {
     match args::Args::new(env::args()) {
         args::Args::Read {
             image,
+            time_zone,
         } => {
+            time::configure(time_zone, time::Rounding::Down);
             let image: Vec<u8> = fs::read(&image).expect(&format!("Can't read {}!", image.display()));
             let file_system = file_system::FileSystem::from(&image);
             println!("{}", file_system);
@@ -26,11 +30,25 @@ fn main() {
             boot_sector,
             root_directory,
             has_volume_guid,
+            volume_size,
+            output,
+            bad_clusters,
+            faults,
+            time_zone,
+            rounding,
         } => {
-            let file_system = file_system::FileSystem::new(boot_sector, root_directory, has_volume_guid, &mut rand_generator);
+            time::configure(time_zone, rounding);
+            let file_system = match file_system::FileSystem::new(boot_sector, root_directory, has_volume_guid, volume_size, &bad_clusters, &faults, &mut rand_generator) {
+                Ok(file_system) => file_system,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                },
+            };
             eprintln!("{}", file_system);
-            let file_system: Vec<u8> = (&file_system).into();
-            io::stdout().write_all(&file_system).expect("Can't write image to stdout.");
+            let mut output = output::Output::new(output.as_ref());
+            file_system.write(&mut output);
+            output.finish();
         },
     }
 }
@@ -0,0 +1,116 @@
+use std::{
+    fs,
+    io,
+    io::{
+        Seek,
+        SeekFrom,
+        Write,
+    },
+    path::PathBuf,
+};
+
+const BLOCK_SIZE: usize = 0x1000;
+
+pub enum Output {
+    Sparse {
+        file: fs::File,
+        offset: u64,
+    },
+    Stream {
+        writer: Box<dyn Write>,
+    },
+}
+
+impl Output {
+    pub fn finish(self) {
+        match self {
+            Self::Sparse {
+                file,
+                offset,
+            } => file
+                .set_len(offset)
+                .expect("Can't fix the length of the image."),
+            Self::Stream {
+                mut writer,
+            } => writer
+                .flush()
+                .expect("Can't flush the image."),
+        }
+    }
+
+    pub fn new(path: Option<&PathBuf>) -> Self {
+        match path {
+            Some(path) => {
+                let is_regular_file: bool = match fs::metadata(path) {
+                    Ok(metadata) => metadata.file_type().is_file(),
+                    Err(_) => true,
+                };
+                if is_regular_file {
+                    let file = fs::File::create(path).expect(&format!("Can't create {}!", path.display()));
+                    let offset: u64 = 0;
+                    Self::Sparse {
+                        file,
+                        offset,
+                    }
+                } else {
+                    let file = fs::OpenOptions::new()
+                        .write(true)
+                        .open(path)
+                        .expect(&format!("Can't open {}!", path.display()));
+                    let writer: Box<dyn Write> = Box::new(io::BufWriter::new(file));
+                    Self::Stream {
+                        writer,
+                    }
+                }
+            },
+            None => {
+                let writer: Box<dyn Write> = Box::new(io::BufWriter::new(io::stdout()));
+                Self::Stream {
+                    writer,
+                }
+            },
+        }
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Sparse {
+                file,
+                offset,
+            } => bytes
+                .chunks(BLOCK_SIZE)
+                .for_each(|block| {
+                    if block.iter().any(|byte| *byte != 0x00) {
+                        file.seek(SeekFrom::Start(*offset)).expect("Can't seek the image.");
+                        file.write_all(block).expect("Can't write the image.");
+                    }
+                    *offset += block.len() as u64;
+                }),
+            Self::Stream {
+                writer,
+            } => writer
+                .write_all(bytes)
+                .expect("Can't write the image."),
+        }
+    }
+
+    pub fn write_zeros(&mut self, length: usize) {
+        match self {
+            Self::Sparse {
+                file: _,
+                offset,
+            } => *offset += length as u64,
+            Self::Stream {
+                writer: _,
+            } => {
+                let zeros: Vec<u8> = vec![0x00; BLOCK_SIZE];
+                let mut remaining: usize = length;
+                while 0 < remaining {
+                    let length: usize = remaining.min(BLOCK_SIZE);
+                    self.write(&zeros[..length]);
+                    remaining -= length;
+                }
+            },
+        }
+    }
+}
@@ -1,8 +1,12 @@
-use std::{
-    fmt,
-    fs,
-    path::PathBuf,
-    time,
+use {
+    std::{
+        fmt,
+        fs,
+        path::PathBuf,
+        sync::OnceLock,
+        time,
+    },
+    super::time_zone,
 };
 
 const FAT_YEAR: i128 = 1980;
@@ -16,6 +20,46 @@ const MINUTES_PER_HOUR: u8 = 60;
 const SECONDS_PER_MINUTE: u8 = 60;
 const UNIX_YEAR: i128 = 1970;
 
+static ROUNDING: OnceLock<Rounding> = OnceLock::new();
+static TIME_ZONE: OnceLock<time_zone::TimeZone> = OnceLock::new();
+
+// Set how times are written into and read from FAT and exFAT timestamps.
+pub fn configure(time_zone: time_zone::TimeZone, rounding: Rounding) {
+    TIME_ZONE
+        .set(time_zone)
+        .expect("Can't configure time zone.");
+    ROUNDING
+        .set(rounding)
+        .expect("Can't configure rounding.");
+}
+
+fn rounding() -> Rounding {
+    *ROUNDING.get_or_init(|| Rounding::Down)
+}
+
+fn time_zone() -> &'static time_zone::TimeZone {
+    TIME_ZONE.get_or_init(time_zone::TimeZone::utc)
+}
+
+// How a time is rounded into a FAT timestamp with 2 seconds granularity.
+#[derive(Clone, Copy, Debug)]
+pub enum Rounding {
+    Down,
+    Nearest,
+    Up,
+}
+
+impl From<&str> for Rounding {
+    fn from(rounding: &str) -> Self {
+        match rounding {
+            "down" => Self::Down,
+            "nearest" => Self::Nearest,
+            "up" => Self::Up,
+            _ => panic!("Can't interpret a rounding \"{}\".", rounding),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct Time {
     year: i128,
@@ -33,17 +77,14 @@ impl Time {
     }
 
     pub fn fat_centi_second(&self) -> u8 {
-        (self.sec % 2) * 100 + ((self.nsec / 10000000) as u8)
+        let (local, _): (Self, i32) = self.local();
+        (local.sec % 2) * 100 + ((local.nsec / 10000000) as u8)
     }
 
+    // The timestamp is truncated into 2 seconds granularity to be combined with a 10ms increment.
     pub fn fat_timestamp(&self) -> u32 {
-        let double_seconds: u32 = (self.sec as u32) / 2;
-        let minute: u32 = (self.min as u32) << 5;
-        let hour: u32 = (self.hour as u32) << 11;
-        let day: u32 = (self.day as u32) << 16;
-        let month: u32 = (self.month as u32) << 21;
-        let year: u32 = (self.year - FAT_YEAR << 25) as u32;
-        year + month + day + hour + minute + double_seconds
+        let (local, _): (Self, i32) = self.local();
+        local.local_fat_timestamp()
     }
 
     pub fn from_fat_timestamp(timestamp: u32, t_10ms_increment: u8, utc_offset: i8) -> Self {
@@ -54,9 +95,7 @@ impl Time {
         let day: u8 = ((timestamp >> 16) as u8) & 0x1f;
         let month: u8 = ((timestamp >> 21) as u8) & 0x0f;
         let year: i128 = ((timestamp >> 25) as i128) + FAT_YEAR;
-        let utc_offset_min: i128 = 15 * (utc_offset as i128);
-        let utc_offset_sec: i128 = 60 * utc_offset_min;
-        Self {
+        let local = Self {
             year,
             month,
             day,
@@ -64,7 +103,48 @@ impl Time {
             min,
             sec,
             nsec,
-        }.add_sec(utc_offset_sec)
+        };
+        // The offset is valid if its highest bit is set, and it is a signed 7 bits number of 15 minutes.
+        let utc_offset_sec: i128 = if utc_offset as u8 & 0x80 != 0 {
+            let utc_offset: i128 = (((utc_offset as u8) << 1) as i8 >> 1) as i128;
+            15 * 60 * utc_offset
+        } else {
+            let unix_timestamp: i64 = local.add_sec(0).unix_timestamp() as i64;
+            time_zone().utc_offset(unix_timestamp) as i128
+        };
+        local.add_sec(-utc_offset_sec)
+    }
+
+    // The timestamp is rounded into 2 seconds granularity as configured.
+    pub fn rounded_fat_timestamp(&self) -> u32 {
+        let (local, _): (Self, i32) = self.local();
+        let sec: i128 = local.to_sec();
+        let truncated_sec: i128 = sec - sec.rem_euclid(2);
+        let remainder_nsec: u64 = (sec.rem_euclid(2) as u64) * 1000000000 + local.nsec as u64;
+        let rounded_sec: i128 = match rounding() {
+            Rounding::Down => truncated_sec,
+            Rounding::Nearest => if 1000000000 <= remainder_nsec {
+                truncated_sec + 2
+            } else {
+                truncated_sec
+            },
+            Rounding::Up => if 0 < remainder_nsec {
+                truncated_sec + 2
+            } else {
+                truncated_sec
+            },
+        };
+        Self::from_sec(rounded_sec).local_fat_timestamp()
+    }
+
+    fn local_fat_timestamp(&self) -> u32 {
+        let double_seconds: u32 = (self.sec as u32) / 2;
+        let minute: u32 = (self.min as u32) << 5;
+        let hour: u32 = (self.hour as u32) << 11;
+        let day: u32 = (self.day as u32) << 16;
+        let month: u32 = (self.month as u32) << 21;
+        let year: u32 = (self.year - FAT_YEAR << 25) as u32;
+        year + month + day + hour + minute + double_seconds
     }
 
     pub fn from_guid_timestamp(timestamp: u64) -> Self {
@@ -105,8 +185,9 @@ impl Time {
     }
 
     pub fn get_10ms_increment(&self) -> u8 {
-        let sec: u8 = 100 * (self.sec % 2);
-        let msec: u8 = (self.nsec / 10000000) as u8;
+        let (local, _): (Self, i32) = self.local();
+        let sec: u8 = 100 * (local.sec % 2);
+        let msec: u8 = (local.nsec / 10000000) as u8;
         sec + msec
     }
 
@@ -214,12 +295,31 @@ impl Time {
         seconds
     }
 
+    // The exFAT UtcOffset field, which is valid only if the offset is a multiple of 15 minutes.
     pub fn utc_offset(&self) -> i8 {
-        0
+        let (_, utc_offset): (Self, i32) = self.local();
+        let quarter: i32 = 15 * 60;
+        if utc_offset % quarter == 0 {
+            let utc_offset: u8 = ((utc_offset / quarter) as u8) & 0x7f;
+            (0x80 | utc_offset) as i8
+        } else {
+            0
+        }
     }
 
     fn add_sec(self, sec: i128) -> Self {
-        Self::from_sec(self.to_sec() + sec)
+        let nsec: u32 = self.nsec;
+        let time: Self = Self::from_sec(self.to_sec() + sec);
+        Self {
+            nsec,
+            ..time
+        }
+    }
+
+    // Convert a UTC time into the configured time zone and get the UTC offset in seconds.
+    fn local(&self) -> (Self, i32) {
+        let utc_offset: i32 = time_zone().utc_offset(self.unix_timestamp() as i64);
+        (self.add_sec(utc_offset as i128), utc_offset)
     }
 
     fn from_system_time(time: time::SystemTime) -> Self {
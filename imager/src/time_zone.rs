@@ -0,0 +1,158 @@
+use std::{
+    env,
+    fs,
+    path::PathBuf,
+};
+
+const LOCAL_TIME: &str = "/etc/localtime";
+const ZONE_INFO: &str = "/usr/share/zoneinfo";
+
+#[derive(Debug)]
+pub enum TimeZone {
+    Fixed {
+        utc_offset: i32,
+    },
+    Local {
+        // Pairs of a unix timestamp when an offset starts and the offset in seconds.
+        transitions: Vec<(i64, i32)>,
+        initial_utc_offset: i32,
+    },
+}
+
+impl TimeZone {
+    // Times after the last transition are assumed to keep its offset.
+    pub fn local() -> Self {
+        let path: PathBuf = match env::var("TZ") {
+            Ok(time_zone) => {
+                let time_zone: &str = time_zone
+                    .strip_prefix(':')
+                    .unwrap_or(&time_zone);
+                let time_zone = PathBuf::from(time_zone);
+                if time_zone.is_absolute() {
+                    time_zone
+                } else {
+                    PathBuf::from(ZONE_INFO).join(time_zone)
+                }
+            },
+            Err(_) => PathBuf::from(LOCAL_TIME),
+        };
+        match fs::read(&path) {
+            Ok(bytes) => Self::read_tzif(&bytes),
+            Err(_) => Self::utc(),
+        }
+    }
+
+    pub fn new(time_zone: &str) -> Self {
+        match time_zone {
+            "utc" | "UTC" => Self::utc(),
+            "local" => Self::local(),
+            utc_offset => {
+                let (sign, utc_offset): (i32, &str) = match utc_offset.split_at(1) {
+                    ("+", utc_offset) => (1, utc_offset),
+                    ("-", utc_offset) => (-1, utc_offset),
+                    _ => panic!("Can't interpret a time zone \"{}\".", time_zone),
+                };
+                let (hour, min): (&str, &str) = utc_offset
+                    .split_once(':')
+                    .unwrap_or((utc_offset, "0"));
+                let hour: i32 = hour
+                    .parse()
+                    .expect("Can't interpret a time zone.");
+                let min: i32 = min
+                    .parse()
+                    .expect("Can't interpret a time zone.");
+                let utc_offset: i32 = sign * 60 * (60 * hour + min);
+                Self::Fixed {
+                    utc_offset,
+                }
+            },
+        }
+    }
+
+    pub fn utc() -> Self {
+        let utc_offset: i32 = 0;
+        Self::Fixed {
+            utc_offset,
+        }
+    }
+
+    // UTC offset in seconds at a unix timestamp.
+    pub fn utc_offset(&self, unix_timestamp: i64) -> i32 {
+        match self {
+            Self::Fixed {
+                utc_offset,
+            } => *utc_offset,
+            Self::Local {
+                transitions,
+                initial_utc_offset,
+            } => transitions
+                .iter()
+                .take_while(|(transition_time, _)| *transition_time <= unix_timestamp)
+                .last()
+                .map(|(_, utc_offset)| *utc_offset)
+                .unwrap_or(*initial_utc_offset),
+        }
+    }
+
+    // Read a TZif file described in RFC 8536.
+    fn read_tzif(bytes: &[u8]) -> Self {
+        assert!(&bytes[0..4] == b"TZif", "Can't read a time zone.");
+        let version: u8 = bytes[4];
+        let header_size: usize = 0x2c;
+        let counts = |header: &[u8]| -> [usize; 6] {
+            let mut counts: [usize; 6] = [0; 6];
+            counts
+                .iter_mut()
+                .enumerate()
+                .for_each(|(i, count)| {
+                    let offset: usize = 0x14 + 4 * i;
+                    *count = u32::from_be_bytes(header[offset..offset + 4]
+                        .try_into()
+                        .expect("Can't read a time zone.")) as usize;
+                });
+            counts
+        };
+        let data_size = |counts: &[usize; 6], time_size: usize| -> usize {
+            let [isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt] = *counts;
+            timecnt * time_size + timecnt + typecnt * 6 + charcnt + leapcnt * (time_size + 4) + isstdcnt + isutcnt
+        };
+        let (data, counts, time_size): (&[u8], [usize; 6], usize) = if b'2' <= version {
+            let v1_counts: [usize; 6] = counts(bytes);
+            let v2_header: &[u8] = &bytes[header_size + data_size(&v1_counts, 4)..];
+            (&v2_header[header_size..], counts(v2_header), 8)
+        } else {
+            (&bytes[header_size..], counts(bytes), 4)
+        };
+        let [_, _, _, timecnt, typecnt, _] = counts;
+        let transition_times: Vec<i64> = data[..timecnt * time_size]
+            .chunks(time_size)
+            .map(|transition_time| match time_size {
+                4 => i32::from_be_bytes(transition_time
+                    .try_into()
+                    .expect("Can't read a time zone.")) as i64,
+                _ => i64::from_be_bytes(transition_time
+                    .try_into()
+                    .expect("Can't read a time zone.")),
+            })
+            .collect();
+        let transition_types: &[u8] = &data[timecnt * time_size..timecnt * time_size + timecnt];
+        let utc_offsets: Vec<i32> = data[timecnt * time_size + timecnt..timecnt * time_size + timecnt + typecnt * 6]
+            .chunks(6)
+            .map(|local_time_type| i32::from_be_bytes(local_time_type[0..4]
+                .try_into()
+                .expect("Can't read a time zone.")))
+            .collect();
+        let transitions: Vec<(i64, i32)> = transition_times
+            .into_iter()
+            .zip(transition_types.iter())
+            .map(|(transition_time, transition_type)| (transition_time, utc_offsets[*transition_type as usize]))
+            .collect();
+        let initial_utc_offset: i32 = *utc_offsets
+            .first()
+            .expect("Can't read a time zone.");
+        Self::Local {
+            transitions,
+            initial_utc_offset,
+        }
+    }
+}
@@ -10,6 +10,8 @@ use core::arch::asm;
 
 pub type Port = u16;
 
+const RANDOM_NUMBER_RETRIES: usize = 0x10;
+
 fn get_rflags() -> u64 {
     let mut rflags: u64;
     unsafe {
@@ -60,3 +62,58 @@ pub fn outb(port: Port, value: u8) {
     }
 }
 
+
+pub fn rdtsc() -> u64 {
+    let mut low: u32;
+    let mut high: u32;
+    unsafe {
+        asm!(
+            "rdtsc",
+            out("eax") low,
+            out("edx") high,
+        );
+    }
+    (high as u64) << 32 | low as u64
+}
+
+// Return None if no random number is available after retries.
+pub fn rdrand() -> Option<u64> {
+    (0..RANDOM_NUMBER_RETRIES).find_map(|_| {
+        let mut value: u64;
+        let mut success: u8;
+        unsafe {
+            asm!(
+                "rdrand rax",
+                "setc cl",
+                out("rax") value,
+                out("cl") success,
+            );
+        }
+        if success != 0 {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
+
+// Return None if no random number is available after retries.
+pub fn rdseed() -> Option<u64> {
+    (0..RANDOM_NUMBER_RETRIES).find_map(|_| {
+        let mut value: u64;
+        let mut success: u8;
+        unsafe {
+            asm!(
+                "rdseed rax",
+                "setc cl",
+                out("rax") value,
+                out("cl") success,
+            );
+        }
+        if success != 0 {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
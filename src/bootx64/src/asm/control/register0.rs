@@ -78,6 +78,20 @@ impl Cr0 {
         }
     }
 
+    // Writes from the supervisor mode to read only pages cause page faults.
+    pub fn enable_write_protect() -> Self {
+        unsafe {
+            asm!(
+                "mov rax, cr0",
+                "or rax, rcx",
+                "mov cr0, rax",
+                in("rcx") Self::WP_MASK,
+                out("rax") _,
+            );
+        }
+        Self::get()
+    }
+
     pub fn pg(&self) -> bool {
         self.pg
     }
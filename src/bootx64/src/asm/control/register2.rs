@@ -10,6 +10,7 @@ pub struct Cr2 {
 }
 
 impl Cr2 {
+    #[allow(dead_code)]
     pub fn get() -> Self {
         let mut cr2: u64;
         unsafe {
@@ -23,5 +24,9 @@ impl Cr2 {
             page_fault_linear_address,
         }
     }
+
+    pub fn page_fault_linear_address(&self) -> u64 {
+        self.page_fault_linear_address
+    }
 }
 
@@ -1,7 +1,14 @@
 // References
 // Intel 64 an IA-32 Architectures Software Developer's Manual, Volume 3, Chapter 2.5 Control Registers
 
-use core::arch::asm;
+use core::{
+    arch::asm,
+    ptr,
+};
+
+// LONG_MODE_ENTRY[0] is a far pointer to return to 64-bit mode from compatibility mode.
+// LONG_MODE_ENTRY[1] is RSP saved before leaving 64-bit mode.
+static mut LONG_MODE_ENTRY: [u64; 2] = [0; 2];
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -143,6 +150,82 @@ impl Cr4 {
         }
     }
 
+    // 5-level paging can be enabled only while paging is disabled.
+    // So the bootloader goes through compatibility mode and legacy protected mode.
+    // CR3 has to be the address of a PML5 table below 4GiB.
+    pub fn enable_la57(cr3: u64, compatibility_mode_cs: u16, long_mode_cs: u16) -> Self {
+        let long_mode_entry: *mut [u64; 2] = ptr::addr_of_mut!(LONG_MODE_ENTRY);
+        let long_mode_entry: u64 = long_mode_entry as u64;
+        assert!(cr3 <= u32::MAX as u64 && rsp() <= u32::MAX as u64, "Can't enable 5-level paging!");
+        unsafe {
+            asm!(
+                // Upper halves of registers are not preserved through 32-bit modes.
+                "push rbx",
+                "push rbp",
+                "push r12",
+                "push r13",
+                "push r14",
+                "push r15",
+                "pushfq",
+                "cli",
+                "lea rax, [rip + 3f]",
+                "mov dword ptr [rdx], eax",
+                "mov dword ptr [rdx + 4], esi",
+                "mov qword ptr [rdx + 8], rsp",
+                // Enter compatibility mode.
+                "push rdi",
+                "lea rax, [rip + 2f]",
+                "push rax",
+                "retfq",
+                ".code32",
+                "2:",
+                // Disable paging.
+                "mov eax, cr0",
+                "and eax, 0x7fffffff",
+                "mov cr0, eax",
+                // Set CR4.LA57.
+                "mov eax, cr4",
+                "or eax, 0x00001000",
+                "mov cr4, eax",
+                "mov cr3, ecx",
+                // Enable paging.
+                "mov eax, cr0",
+                "or eax, 0x80000000",
+                "mov cr0, eax",
+                // Return to 64-bit mode.
+                "jmp fword ptr [edx]",
+                ".code64",
+                "3:",
+                "mov edx, edx",
+                "mov rsp, qword ptr [rdx + 8]",
+                "popfq",
+                "pop r15",
+                "pop r14",
+                "pop r13",
+                "pop r12",
+                "pop rbp",
+                "pop rbx",
+                in("rcx") cr3,
+                in("rdx") long_mode_entry,
+                in("rsi") long_mode_cs as u64,
+                in("rdi") compatibility_mode_cs as u64,
+                clobber_abi("C"),
+            );
+        }
+        Self::get()
+    }
+
+    // Instructions running in compatibility mode, LONG_MODE_ENTRY and the stack have to be below 4GiB.
+    // Paging can't be disabled while CR4.PCIDE is set.
+    pub fn la57_enableable(&self) -> bool {
+        let enable_la57: fn(u64, u16, u16) -> Self = Self::enable_la57;
+        let enable_la57: usize = enable_la57 as usize;
+        let long_mode_entry: *const [u64; 2] = ptr::addr_of!(LONG_MODE_ENTRY);
+        let long_mode_entry: usize = long_mode_entry as usize;
+        let rsp: usize = rsp() as usize;
+        !self.pcide && enable_la57 <= u32::MAX as usize && long_mode_entry <= u32::MAX as usize && rsp <= u32::MAX as usize
+    }
+
     pub fn la57(&self) -> bool {
         self.la57
     }
@@ -152,3 +235,14 @@ impl Cr4 {
     }
 }
 
+// The compatibility mode uses the lower 32 bits of RSP.
+fn rsp() -> u64 {
+    let rsp: u64;
+    unsafe {
+        asm!(
+            "mov {}, rsp",
+            out(reg) rsp,
+        );
+    }
+    rsp
+}
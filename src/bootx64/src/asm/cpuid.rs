@@ -87,6 +87,13 @@ impl Cpuid {
         }
     }
 
+    pub fn supports_1_gib_pages(&self) -> bool {
+        match &self.eax0x80000001 {
+            Some(eax0x80000001) => eax0x80000001.supports_1_gib_pages(),
+            None => false,
+        }
+    }
+
     pub fn supports_5_level_paging(&self) -> bool {
         match &self.eax0x00000007 {
             Some(eax0x00000007) => eax0x00000007.supports_5_level_paging(),
@@ -100,6 +107,20 @@ impl Cpuid {
             None => false,
         }
     }
+
+    pub fn supports_rdrand(&self) -> bool {
+        match &self.eax0x00000001 {
+            Some(eax0x00000001) => eax0x00000001.supports_rdrand(),
+            None => false,
+        }
+    }
+
+    pub fn supports_rdseed(&self) -> bool {
+        match &self.eax0x00000007 {
+            Some(eax0x00000007) => eax0x00000007.supports_rdseed(),
+            None => false,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -37,6 +37,10 @@ impl Eax0x00000001 {
             None
         }
     }
+
+    pub fn supports_rdrand(&self) -> bool {
+        self.ecx.supports_rdrand()
+    }
 }
 
 #[allow(dead_code)]
@@ -404,6 +408,10 @@ impl Ecx {
     const AVX_MASK: u32 = (1 << Self::AVX_SHIFT) as u32;
     const F16C_MASK: u32 = (1 << Self::F16C_SHIFT) as u32;
     const RDRAND_MASK: u32 = (1 << Self::RDRAND_SHIFT) as u32;
+
+    pub fn supports_rdrand(&self) -> bool {
+        self.rdrand
+    }
 }
 
 impl From<u32> for Ecx {
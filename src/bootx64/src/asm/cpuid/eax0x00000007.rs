@@ -28,5 +28,9 @@ impl Eax0x00000007 {
     pub fn supports_5_level_paging(&self) -> bool {
         self.ecx0x00000000.supports_5_level_paging()
     }
+
+    pub fn supports_rdseed(&self) -> bool {
+        self.ecx0x00000000.supports_rdseed()
+    }
 }
 
@@ -34,6 +34,10 @@ impl Ecx0x00000000 {
     pub fn supports_5_level_paging(&self) -> bool {
         self.ecx.supports_5_level_paging()
     }
+
+    pub fn supports_rdseed(&self) -> bool {
+        self.ebx.supports_rdseed()
+    }
 }
 
 #[allow(dead_code)]
@@ -151,6 +155,10 @@ impl Ebx {
     const SHA_MASK: u32 = (1 << Self::SHA_SHIFT) as u32;
     const AVX512BW_MASK: u32 = (1 << Self::AVX512BW_SHIFT) as u32;
     const AVX512VL_MASK: u32 = (1 << Self::AVX512VL_SHIFT) as u32;
+
+    pub fn supports_rdseed(&self) -> bool {
+        self.rdseed
+    }
 }
 
 impl From<u32> for Ebx {
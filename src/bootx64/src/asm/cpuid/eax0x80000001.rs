@@ -35,6 +35,10 @@ impl Eax0x80000001 {
         }
     }
 
+    pub fn supports_1_gib_pages(&self) -> bool {
+        self.edx.supports_1_gib_pages()
+    }
+
     pub fn supports_ia32_efer(&self) -> bool {
         self.edx.supports_ia32_efer()
     }
@@ -78,6 +82,10 @@ impl Edx {
     const RDTSCP_AND_IA32_TSC_AUX_MASK: u32 = (1 << Self::RDTSCP_AND_IA32_TSC_AUX_SHIFT) as u32;
     const INTEL_64_ARCHITECTURE_MASK: u32 = (1 << Self::INTEL_64_ARCHITECTURE_SHIFT) as u32;
 
+    pub fn supports_1_gib_pages(&self) -> bool {
+        self.gbyte_pages
+    }
+
     pub fn supports_ia32_efer(&self) -> bool {
         self.execute_disable_bit || self.intel_64_architecture
     }
@@ -0,0 +1,368 @@
+// The boot information is the only interface between a bootloader and the kernel.
+// It consists of plain data in a fixed layout so that the bootloader and the kernel evolve independently and other bootloaders can boot THEOS.
+// The kernel checks the magic number, the version and the size before reading the other fields.
+// A new version may only append fields.
+
+use {
+    alloc::vec::Vec,
+    core::mem,
+    super::{
+        firmware_tables,
+        module,
+        serial,
+        uefi::{
+            protocols::{
+                console_support::graphics_output,
+                human_interface_infrastructure::font,
+            },
+            services::boot::memory_allocation,
+            tables::system,
+            types::handle,
+        },
+    },
+};
+
+// "THEOSBI\0"
+pub const MAGIC: u64 = u64::from_le_bytes(*b"THEOSBI\0");
+pub const VERSION: u32 = 4;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct BootInfo {
+    magic: u64,
+    version: u32,
+    size: u32,
+    // The UEFI system table and the image handle remain available for runtime services, or 0 if the kernel isn't booted by UEFI.
+    system: u64,
+    image: u64,
+    memory_size: u64,
+    highest_parallel_offset: u64,
+    memory_map: MemoryMap,
+    physical_page_present_bit_map: Array,
+    stack_floor: u64,
+    frame_buffer: FrameBuffer,
+    // Physical addresses of the ACPI 2.0 RSDP and the SMBIOS 3 entry point, or 0 if absent.
+    rsdp: u64,
+    smbios: u64,
+    com1: SerialPort,
+    com2: SerialPort,
+    command_line: Array,
+    modules: Array,
+    debug_sections: Array,
+    font: Font,
+    // Version 2
+    // Physical addresses of the flattened device tree and the UEFI memory attributes table, or 0 if absent.
+    device_tree: u64,
+    memory_attributes: u64,
+    // Version 3
+    // Loader phases in the order they ended and messages printed by the loader from the oldest one.
+    phases: Array,
+    boot_log: Array,
+    // Version 4
+    // The width of a character cell. A wide glyph occupies two cells.
+    narrow_width: u16,
+}
+
+impl BootInfo {
+    pub fn new(
+        image: handle::Handle<'static>,
+        system: &system::System,
+        memory_size: usize,
+        highest_parallel_offset: usize,
+        memory_map: MemoryMap,
+        physical_page_present_bit_map: &[u8],
+        stack_floor: usize,
+        frame_buffer: FrameBuffer,
+        com1: &serial::Serial,
+        com2: &serial::Serial,
+        command_line: &str,
+        modules: &[module::Module],
+        debug_sections: &[module::Module],
+        font: Font,
+        firmware_tables: &firmware_tables::FirmwareTables,
+        phases: &[Phase],
+        boot_log: &[u8],
+        narrow_width: u16,
+    ) -> Self {
+        let magic: u64 = MAGIC;
+        let version: u32 = VERSION;
+        let size: u32 = mem::size_of::<Self>() as u32;
+        let rsdp: u64 = firmware_tables.rsdp().unwrap_or(0) as u64;
+        let smbios: u64 = firmware_tables.smbios().unwrap_or(0) as u64;
+        let device_tree: u64 = firmware_tables.device_tree().unwrap_or(0) as u64;
+        let memory_attributes: u64 = firmware_tables.memory_attributes().unwrap_or(0) as u64;
+        let image: usize = image.into();
+        let image: u64 = image as u64;
+        let system: *const system::System = system as *const system::System;
+        let system: u64 = system as u64;
+        let memory_size: u64 = memory_size as u64;
+        let highest_parallel_offset: u64 = highest_parallel_offset as u64;
+        let physical_page_present_bit_map: Array = physical_page_present_bit_map.into();
+        let stack_floor: u64 = stack_floor as u64;
+        let com1: SerialPort = com1.into();
+        let com2: SerialPort = com2.into();
+        let command_line: Array = command_line.as_bytes().into();
+        let modules: Array = modules.into();
+        let debug_sections: Array = debug_sections.into();
+        let phases: Array = phases.into();
+        let boot_log: Array = boot_log.into();
+        Self {
+            magic,
+            version,
+            size,
+            system,
+            image,
+            memory_size,
+            highest_parallel_offset,
+            memory_map,
+            physical_page_present_bit_map,
+            stack_floor,
+            frame_buffer,
+            rsdp,
+            smbios,
+            com1,
+            com2,
+            command_line,
+            modules,
+            debug_sections,
+            font,
+            device_tree,
+            memory_attributes,
+            phases,
+            boot_log,
+            narrow_width,
+        }
+    }
+
+    pub fn stack_floor(&self) -> usize {
+        self.stack_floor as usize
+    }
+}
+
+impl Into<usize> for &BootInfo {
+    fn into(self) -> usize {
+        let boot_info: *const BootInfo = self as *const BootInfo;
+        boot_info as usize
+    }
+}
+
+// An address and a number of elements.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Array {
+    address: u64,
+    length: u64,
+}
+
+impl<T> From<&[T]> for Array {
+    fn from(array: &[T]) -> Self {
+        let address: *const T = array.as_ptr();
+        let address: u64 = address as u64;
+        let length: u64 = array.len() as u64;
+        Self {
+            address,
+            length,
+        }
+    }
+}
+
+// A loader phase measured by TSC ticks.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Phase {
+    name: Array,
+    begin: u64,
+    end: u64,
+}
+
+impl Phase {
+    pub const NONE: Self = Self {
+        name: Array {
+            address: 0,
+            length: 0,
+        },
+        begin: 0,
+        end: 0,
+    };
+
+    pub fn new(name: &'static str, begin: u64, end: u64) -> Self {
+        let name: Array = name.as_bytes().into();
+        Self {
+            name,
+            begin,
+            end,
+        }
+    }
+}
+
+// UEFI memory descriptors as returned by GetMemoryMap.
+#[derive(Debug)]
+#[repr(C)]
+pub struct MemoryMap {
+    buffer: Array,
+    descriptors: u64,
+    descriptor_size: u64,
+    descriptor_version: u32,
+}
+
+impl From<&memory_allocation::Map<'_>> for MemoryMap {
+    fn from(map: &memory_allocation::Map) -> Self {
+        let buffer: Array = map.buffer().into();
+        let descriptors: u64 = map.descriptors() as u64;
+        let descriptor_size: u64 = map.descriptor_size() as u64;
+        let descriptor_version: u32 = map.descriptor_version();
+        Self {
+            buffer,
+            descriptors,
+            descriptor_size,
+            descriptor_version,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct FrameBuffer {
+    base: u64,
+    size: u64,
+    horizontal_resolution: u32,
+    vertical_resolution: u32,
+    pixels_per_scan_line: u32,
+    // EFI_GRAPHICS_PIXEL_FORMAT
+    pixel_format: u32,
+    red_mask: u32,
+    green_mask: u32,
+    blue_mask: u32,
+    reserved_mask: u32,
+}
+
+impl From<&graphics_output::GraphicsOutput<'_>> for FrameBuffer {
+    fn from(graphics_output: &graphics_output::GraphicsOutput) -> Self {
+        let information: &graphics_output::ModeInformation = graphics_output.information();
+        let pixel_bit_mask: &graphics_output::PixelBitMask = information.pixel_bit_mask();
+        let base: u64 = graphics_output.frame_buffer_base();
+        let size: u64 = graphics_output.frame_buffer_size() as u64;
+        let horizontal_resolution: u32 = information.horizontal_resolution();
+        let vertical_resolution: u32 = information.vertical_resolution();
+        let pixels_per_scan_line: u32 = information.pixels_per_scan_line();
+        let pixel_format: u32 = information.pixel_format();
+        let red_mask: u32 = pixel_bit_mask.red_mask();
+        let green_mask: u32 = pixel_bit_mask.green_mask();
+        let blue_mask: u32 = pixel_bit_mask.blue_mask();
+        let reserved_mask: u32 = pixel_bit_mask.reserved_mask();
+        Self {
+            base,
+            size,
+            horizontal_resolution,
+            vertical_resolution,
+            pixels_per_scan_line,
+            pixel_format,
+            red_mask,
+            green_mask,
+            blue_mask,
+            reserved_mask,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SerialPort {
+    port: u16,
+    baud: u32,
+}
+
+impl Into<SerialPort> for &serial::Serial {
+    fn into(self) -> SerialPort {
+        let port: u16 = self.port();
+        let baud: u32 = self.baud();
+        SerialPort {
+            port,
+            baud,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Font {
+    // Glyphs sorted by their characters.
+    glyphs: Array,
+    bitmap: Array,
+    max_width: u16,
+    max_height: u16,
+}
+
+// Each row of a glyph begins at a byte boundary and the most significant bit is the leftmost pixel.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Glyph {
+    character: u32,
+    width: u16,
+    height: u16,
+    // Offset of the first row in the bitmap.
+    offset: u64,
+}
+
+// Glyphs and bitmaps referred by Font.
+#[derive(Debug)]
+pub struct FontBitmaps {
+    glyphs: Vec<Glyph>,
+    bitmap: Vec<u8>,
+    max_width: u16,
+    max_height: u16,
+    narrow_width: u16,
+}
+
+impl FontBitmaps {
+    pub fn narrow_width(&self) -> u16 {
+        self.narrow_width
+    }
+}
+
+impl From<&font::Font> for FontBitmaps {
+    fn from(font: &font::Font) -> Self {
+        let mut bitmap: Vec<u8> = Vec::new();
+        let glyphs: Vec<Glyph> = font
+            .glyphs()
+            .map(|(character, glyph)| {
+                let character: u32 = *character as u32;
+                let width: u16 = glyph.width();
+                let height: u16 = glyph.height();
+                let offset: u64 = bitmap.len() as u64;
+                bitmap.extend(glyph.rows());
+                Glyph {
+                    character,
+                    width,
+                    height,
+                    offset,
+                }
+            })
+            .collect();
+        let max_width: u16 = font.max_width();
+        let max_height: u16 = font.max_height();
+        let narrow_width: u16 = font.narrow_width();
+        Self {
+            glyphs,
+            bitmap,
+            max_width,
+            max_height,
+            narrow_width,
+        }
+    }
+}
+
+impl Into<Font> for &FontBitmaps {
+    fn into(self) -> Font {
+        let glyphs: Array = self.glyphs[..].into();
+        let bitmap: Array = self.bitmap[..].into();
+        let max_width: u16 = self.max_width;
+        let max_height: u16 = self.max_height;
+        Font {
+            glyphs,
+            bitmap,
+            max_width,
+            max_height,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// The loader keeps boot settings across reboots in UEFI variables.
+// The loader counts boots and the kernel deletes the counter after booting successfully.
+// If the counter reaches the limit, the kernel of the selected entry keeps crashing and the last known good entry is booted instead.
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::string::String,
+    super::{
+        config,
+        uefi::{
+            services::{
+                boot::protocol_handler,
+                runtime::variable,
+            },
+            types::status,
+        },
+    },
+};
+
+// The vendor GUID of THEOS variables shared with the kernel.
+pub const VENDOR: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x5c0f5e6a,
+    0x6f1d,
+    0x4b8e,
+    [
+        0x9d,
+        0x3a,
+        0x7e,
+        0x4f,
+        0x2b,
+        0x1c,
+        0x8a,
+        0x90
+    ],
+);
+const ATTRIBUTES: u32 = variable::NON_VOLATILE | variable::BOOTSERVICE_ACCESS | variable::RUNTIME_ACCESS;
+const LAST_ENTRY: variable::Variable<String> = variable::Variable::new("TheosLastEntry", &VENDOR, ATTRIBUTES);
+// Chain-loaded applications never report success, so only kernel entries can be known good.
+const LAST_KERNEL_ENTRY: variable::Variable<String> = variable::Variable::new("TheosLastKernelEntry", &VENDOR, ATTRIBUTES);
+const KNOWN_GOOD_ENTRY: variable::Variable<String> = variable::Variable::new("TheosKnownGoodEntry", &VENDOR, ATTRIBUTES);
+const BOOT_ATTEMPTS: variable::Variable<u32> = variable::Variable::new("TheosBootAttempts", &VENDOR, ATTRIBUTES);
+
+// Select the entry to boot according to the previous boots.
+pub fn restore(config: &mut config::Config) {
+    let last_entry: Option<String> = LAST_ENTRY.get();
+    let boot_attempts: u32 = BOOT_ATTEMPTS
+        .get()
+        .unwrap_or(0);
+    serial_println!("last_entry = {:?}", last_entry);
+    serial_println!("boot_attempts = {:?}", boot_attempts);
+    // The kernel deleted the counter, so the last kernel boot succeeded.
+    if boot_attempts == 0 {
+        if let Some(last_kernel_entry) = LAST_KERNEL_ENTRY.get() {
+            if let Err(status) = KNOWN_GOOD_ENTRY.set(&last_kernel_entry) {
+                serial_println!("Can't save the known good entry. status = {:#x?}", status);
+            }
+        }
+    }
+    if config.saved() {
+        if let Some(selected) = last_entry
+            .as_ref()
+            .and_then(|last_entry| config.entry(last_entry)) {
+            config.select(selected);
+        }
+    }
+    if config.boot_attempts() <= boot_attempts {
+        let selected: usize = config.selected();
+        match KNOWN_GOOD_ENTRY
+            .get()
+            .and_then(|known_good_entry| config.entry(&known_good_entry))
+            .filter(|known_good_entry| *known_good_entry != selected && !config.entries()[*known_good_entry].chain_load()) {
+            Some(known_good_entry) => {
+                serial_println!("{} failed to boot {} times. Fall back to {}.", config.entries()[selected].name(), boot_attempts, config.entries()[known_good_entry].name());
+                config.select(known_good_entry);
+                if let Err(status) = BOOT_ATTEMPTS.delete() {
+                    serial_println!("Can't reset boot attempts. status = {:#x?}", status);
+                }
+            },
+            None => serial_println!("{} failed to boot {} times and no other entry is known good.", config.entries()[selected].name(), boot_attempts),
+        }
+    }
+}
+
+// Count a boot of the selected entry.
+// Chain-loaded applications never report success, so they are remembered but not counted.
+pub fn save(config: &config::Config) {
+    let name: String = config.entries()[config.selected()]
+        .name()
+        .into();
+    let saved: Result<(), status::Status> = if config.chain_load() {
+        LAST_ENTRY.set(&name)
+    } else {
+        let boot_attempts: u32 = BOOT_ATTEMPTS
+            .get()
+            .unwrap_or(0)
+            .saturating_add(1);
+        LAST_ENTRY
+            .set(&name)
+            .and_then(|()| LAST_KERNEL_ENTRY.set(&name))
+            .and_then(|()| BOOT_ATTEMPTS.set(&boot_attempts))
+    };
+    if let Err(status) = saved {
+        serial_println!("Can't save the boot state. status = {:#x?}", status);
+    }
+}
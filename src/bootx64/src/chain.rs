@@ -0,0 +1,78 @@
+// The loader chain-loads other EFI applications such as the UEFI shell, memtest86 or another OS loader.
+// The application is read by the same file systems as kernels and handed to the firmware by LoadImage and StartImage.
+// The firmware verifies the application if Secure Boot is enabled.
+// The loader also verifies the application by its signature as it verifies kernels. See signature.rs.
+// The loader regains control when the application exits.
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::vec::Vec,
+    core::iter,
+    super::{
+        config,
+        file_system,
+        signature,
+        uefi::{
+            protocols::{
+                device_path,
+                efi_loaded_image::efi_loaded_image,
+                media_access::simple_file_system,
+            },
+            tables::system,
+            types::{
+                handle,
+                status,
+            },
+        },
+    },
+};
+
+// Seconds
+const WATCHDOG_TIMEOUT: usize = 5 * 60;
+
+// Run the EFI application of the selected entry until it exits.
+pub fn run(config: &config::Config) -> Result<(), status::Status> {
+    let file_system: file_system::FileSystem = file_system::FileSystem::select(config.volume(), config.server()).ok_or(status::NOT_FOUND)?;
+    let application: Vec<u8> = file_system
+        .try_read_file(config.kernel())
+        .ok_or(status::NOT_FOUND)?;
+    signature::Verifier::new(config.verify_signatures()).enforce(&file_system, config.kernel(), &application);
+    // Applications such as the UEFI shell find the volume they came from by the device path.
+    // The device path is unknown if the application is read by a built-in driver or from a TFTP server.
+    let device_path: Option<Vec<u8>> = match config.server() {
+        Some(_) => None,
+        None => simple_file_system::SimpleFileSystem::select_handle(config.volume())
+            .and_then(device_path::DevicePathProtocol::new)
+            .and_then(|device_path| device_path.append_file_path(config.kernel())),
+    };
+    let device_path: Option<&device_path::DevicePathProtocol> = device_path
+        .as_ref()
+        .map(|device_path| device_path::DevicePathProtocol::from_bytes(device_path));
+    let boot_services = &system::system().boot_services;
+    let image: handle::Handle = boot_services.load_image(system::image(), device_path, &application)?;
+    let load_options: Vec<u16> = config
+        .command_line()
+        .encode_utf16()
+        .chain(iter::once(0))
+        .collect();
+    if 1 < load_options.len() {
+        match efi_loaded_image::EfiLoadedImage::of(image) {
+            Some(loaded_image) => loaded_image.set_load_options(&load_options),
+            None => serial_println!("Can't pass the command line to {}.", config.kernel()),
+        }
+    }
+    // Arm the watchdog timer as the boot manager of the firmware does before starting an application.
+    if let Err(status) = boot_services.set_watchdog_timer(WATCHDOG_TIMEOUT) {
+        serial_println!("Can't set the watchdog timer. status = {:#x?}", status);
+    }
+    serial_println!("Start {}.", config.kernel());
+    let result: Result<(), status::Status> = boot_services.start_image(image);
+    // The menu waits for the user without a timeout after the application returns.
+    if let Err(status) = boot_services.set_watchdog_timer(0) {
+        serial_println!("Can't disable the watchdog timer. status = {:#x?}", status);
+    }
+    result
+}
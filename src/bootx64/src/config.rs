@@ -0,0 +1,496 @@
+// The boot configuration file consists of lines "key = value".
+// Empty lines and lines beginning with '#' are ignored.
+// "entry" begins a new boot entry and "kernel", "command_line" and "module" set the last boot entry.
+// "module" can be repeated to load files alongside the kernel.
+// "kernel", "command_line", "module", "efi", "volume" and "server" before the first "entry" are defaults of every boot entry.
+// "efi" makes the last boot entry chain-load another EFI application such as the UEFI shell or another OS loader instead of a kernel.
+// The command line of the entry is passed to the EFI application as its load options.
+// "volume" names the volume the kernel and the modules are read from by "PARTUUID=<partition GUID>" or "LABEL=<volume label>".
+// "server" names a TFTP server directory the kernel and the modules are read from by "tftp://<IPv4 address>/<directory>" instead of a volume.
+// QEMU serves a host directory by "-netdev user,id=net0,tftp=<directory>" at "tftp://10.0.2.2/".
+// The boot configuration file is always read from the volume the loader came from.
+// ELF files in the root directory of the volume which no entry boots are added as entries named after the files.
+// "default" and "fallback" refer to boot entries by their names.
+// "default = saved" boots the entry booted last time.
+// "verify_signatures = true" refuses kernels and modules without valid signatures. A provisioned public key is enforced even if it is false. See signature.rs for keys and signatures.
+// "font_ranges" lists Unicode ranges of the glyphs passed to the kernel. All glyphs of the basic multilingual plane are passed by default.
+// "boot_attempts" is the number of boots without the kernel reporting success before the last known good entry is booted instead.
+// "level5_paging = true" switches to 5-level paging if the processor supports it. Otherwise the kernel runs with the paging mode of the firmware.
+//
+// timeout = 5
+// default = THEOS
+// boot_attempts = 3
+// verify_signatures = false
+// level5_paging = false
+// fallback = THEOS (safe)
+// entry = THEOS
+// kernel = /kernel.elf
+// command_line = console=com1
+// module = /initrd.img
+// entry = THEOS (safe)
+// volume = LABEL=THEOS
+// kernel = /kernel.elf
+// command_line = console=com1 safe
+// entry = THEOS (network)
+// server = tftp://10.0.2.2/
+// kernel = /kernel.elf
+// entry = UEFI shell
+// volume = LABEL=TOOLS
+// efi = /EFI/BOOT/shellx64.efi
+// video_mode = 1024x768
+// font_ranges = 0x20-0x7e, 0x3000-0x30ff, 0x4e00-0x9fff
+// stack_size = 0x10000
+// baud = 9600
+// verbosity = normal
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::{
+        format,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    core::{
+        ops::RangeInclusive,
+        str,
+    },
+    super::{
+        file_system::tftp,
+        serial,
+        uefi::{
+            protocols::media_access::simple_file_system,
+            services::boot::memory_allocation,
+        },
+    },
+};
+
+const PATH: &str = "/theos.cfg";
+const DEFAULT_ENTRY: &str = "default";
+const DEFAULT_KERNEL: &str = "/kernel.elf";
+const KERNEL_EXTENSION: &str = ".elf";
+const DEFAULT_STACK_SIZE: usize = 0x10 * memory_allocation::PAGE_SIZE;
+const DEFAULT_TIMEOUT: usize = 3;
+const DEFAULT_BOOT_ATTEMPTS: u32 = 3;
+const SAVED_ENTRY: &str = "saved";
+// The basic multilingual plane except control characters.
+const DEFAULT_FONT_RANGES: [RangeInclusive<char>; 2] = [' '..='~', '\u{a0}'..='\u{fffd}'];
+
+#[derive(Debug)]
+pub struct Config {
+    entries: Vec<Entry>,
+    selected: usize,
+    fallback: Option<usize>,
+    // Select the entry booted last time.
+    saved: bool,
+    boot_attempts: u32,
+    verify_signatures: bool,
+    level5_paging: bool,
+    // Seconds until the selected entry is booted.
+    timeout: usize,
+    // Horizontal and vertical resolutions.
+    video_mode: Option<(u32, u32)>,
+    font_ranges: Vec<RangeInclusive<char>>,
+    stack_size: usize,
+    baud: u32,
+    verbosity: serial::Verbosity,
+}
+
+impl Config {
+    pub fn add_kernels(&mut self, simple_file_system: &simple_file_system::SimpleFileSystem) {
+        let kernels: Vec<String> = simple_file_system
+            .directory("/")
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|information| !information.is_directory())
+            .map(|information| information.file_name())
+            .filter(|file_name| file_name
+                .to_ascii_lowercase()
+                .ends_with(KERNEL_EXTENSION))
+            .collect();
+        kernels
+            .into_iter()
+            .for_each(|file_name| {
+                let kernel: String = format!("/{}", file_name);
+                // FAT file names are case insensitive.
+                if !self.entries
+                    .iter()
+                    .any(|entry| entry.volume.is_none() && entry.server.is_none() && entry.kernel.eq_ignore_ascii_case(&kernel)) {
+                    let mut entry = Entry::new(&file_name);
+                    entry.kernel = kernel;
+                    self.entries.push(entry);
+                }
+            });
+    }
+
+    pub fn baud(&self) -> u32 {
+        self.baud
+    }
+
+    pub fn boot_attempts(&self) -> u32 {
+        self.boot_attempts
+    }
+
+    pub fn chain_load(&self) -> bool {
+        self.entries[self.selected].chain_load()
+    }
+
+    pub fn command_line(&self) -> &str {
+        self.entries[self.selected].command_line()
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn entry(&self, name: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.name == name)
+    }
+
+    pub fn fallback(&self) -> Option<&Entry> {
+        self.fallback.map(|fallback| &self.entries[fallback])
+    }
+
+    pub fn font_ranges(&self) -> &[RangeInclusive<char>] {
+        &self.font_ranges
+    }
+
+    pub fn kernel(&self) -> &str {
+        self.entries[self.selected].kernel()
+    }
+
+    pub fn level5_paging(&self) -> bool {
+        self.level5_paging
+    }
+
+    pub fn modules(&self) -> &[String] {
+        self.entries[self.selected].modules()
+    }
+
+    pub fn read(simple_file_system: &simple_file_system::SimpleFileSystem) -> Self {
+        match simple_file_system.try_read_file(PATH) {
+            Some(config) => match str::from_utf8(&config) {
+                Ok(config) => config.into(),
+                Err(_) => {
+                    serial_println!("{} is not UTF-8.", PATH);
+                    Self::default()
+                },
+            },
+            None => Self::default(),
+        }
+    }
+
+    pub fn saved(&self) -> bool {
+        self.saved
+    }
+
+    pub fn select(&mut self, selected: usize) {
+        if selected < self.entries.len() {
+            self.selected = selected;
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn set_command_line(&mut self, command_line: String) {
+        self.entries[self.selected].command_line = command_line;
+    }
+
+    pub fn set_fallback(&mut self, fallback: Option<usize>) {
+        self.fallback = fallback.filter(|fallback| *fallback < self.entries.len());
+    }
+
+    pub fn stack_pages(&self) -> usize {
+        (self.stack_size + memory_allocation::PAGE_SIZE - 1) / memory_allocation::PAGE_SIZE
+    }
+
+    pub fn timeout(&self) -> usize {
+        self.timeout
+    }
+
+    pub fn server(&self) -> Option<&tftp::Server> {
+        self.entries[self.selected].server()
+    }
+
+    pub fn verbosity(&self) -> serial::Verbosity {
+        self.verbosity
+    }
+
+    pub fn verify_signatures(&self) -> bool {
+        self.verify_signatures
+    }
+
+    pub fn video_mode(&self) -> Option<(u32, u32)> {
+        self.video_mode
+    }
+
+    pub fn volume(&self) -> Option<&simple_file_system::Volume> {
+        self.entries[self.selected].volume()
+    }
+
+    fn last_entry(&mut self) -> &mut Entry {
+        if self.entries.is_empty() {
+            self.entries.push(Entry::new(DEFAULT_ENTRY));
+        }
+        self.entries
+            .last_mut()
+            .expect("Can't get a boot entry!")
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let entries: Vec<Entry> = Vec::new();
+        let selected: usize = 0;
+        let fallback: Option<usize> = None;
+        let saved: bool = false;
+        let boot_attempts: u32 = DEFAULT_BOOT_ATTEMPTS;
+        let verify_signatures: bool = false;
+        let level5_paging: bool = false;
+        let timeout: usize = DEFAULT_TIMEOUT;
+        let video_mode: Option<(u32, u32)> = None;
+        let font_ranges: Vec<RangeInclusive<char>> = DEFAULT_FONT_RANGES.to_vec();
+        let stack_size: usize = DEFAULT_STACK_SIZE;
+        let baud: u32 = serial::BAUD;
+        let verbosity = serial::Verbosity::Normal;
+        let mut config = Self {
+            entries,
+            selected,
+            fallback,
+            saved,
+            boot_attempts,
+            verify_signatures,
+            level5_paging,
+            timeout,
+            video_mode,
+            font_ranges,
+            stack_size,
+            baud,
+            verbosity,
+        };
+        config.last_entry();
+        config
+    }
+}
+
+impl From<&str> for Config {
+    fn from(config: &str) -> Self {
+        let mut entries: Vec<Entry> = Vec::new();
+        let mut default: Option<String> = None;
+        let mut fallback: Option<String> = None;
+        let mut config: Self = config
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .fold(Self::default(), |mut config, line| {
+                match line.split_once('=') {
+                    Some((key, value)) => {
+                        let value: &str = value.trim();
+                        match key.trim() {
+                            "entry" => {
+                                let mut entry: Entry = config
+                                    .last_entry()
+                                    .clone();
+                                entry.name = value.to_string();
+                                entries.push(entry);
+                            },
+                            "kernel" => {
+                                let entry: &mut Entry = match entries.last_mut() {
+                                    Some(entry) => entry,
+                                    None => config.last_entry(),
+                                };
+                                entry.kernel = value.to_string();
+                                entry.chain_load = false;
+                            },
+                            "efi" => {
+                                let entry: &mut Entry = match entries.last_mut() {
+                                    Some(entry) => entry,
+                                    None => config.last_entry(),
+                                };
+                                entry.kernel = value.to_string();
+                                entry.chain_load = true;
+                            },
+                            "command_line" => match entries.last_mut() {
+                                Some(entry) => entry.command_line = value.to_string(),
+                                None => config.last_entry().command_line = value.to_string(),
+                            },
+                            "module" => match entries.last_mut() {
+                                Some(entry) => entry.modules.push(value.to_string()),
+                                None => config.last_entry().modules.push(value.to_string()),
+                            },
+                            "volume" => match value.try_into() {
+                                Ok(volume) => match entries.last_mut() {
+                                    Some(entry) => entry.volume = Some(volume),
+                                    None => config.last_entry().volume = Some(volume),
+                                },
+                                Err(_) => serial_println!("Can't interpret a volume \"{}\".", value),
+                            },
+                            "server" => match value.try_into() {
+                                Ok(server) => match entries.last_mut() {
+                                    Some(entry) => entry.server = Some(server),
+                                    None => config.last_entry().server = Some(server),
+                                },
+                                Err(_) => serial_println!("Can't interpret a server \"{}\".", value),
+                            },
+                            "default" => default = Some(value.to_string()),
+                            "fallback" => fallback = Some(value.to_string()),
+                            "boot_attempts" => match parse_number(value) {
+                                Some(boot_attempts) => config.boot_attempts = boot_attempts as u32,
+                                None => serial_println!("Can't interpret boot attempts \"{}\".", value),
+                            },
+                            "verify_signatures" => match value.parse() {
+                                Ok(verify_signatures) => config.verify_signatures = verify_signatures,
+                                Err(_) => serial_println!("Can't interpret verify_signatures \"{}\".", value),
+                            },
+                            "level5_paging" => match value.parse() {
+                                Ok(level5_paging) => config.level5_paging = level5_paging,
+                                Err(_) => serial_println!("Can't interpret level5_paging \"{}\".", value),
+                            },
+                            "timeout" => match parse_number(value) {
+                                Some(timeout) => config.timeout = timeout,
+                                None => serial_println!("Can't interpret a timeout \"{}\".", value),
+                            },
+                            "video_mode" => match value
+                                .split_once('x')
+                                .and_then(|(horizontal, vertical)| parse_number(horizontal)
+                                    .zip(parse_number(vertical))) {
+                                Some((horizontal, vertical)) => config.video_mode = Some((horizontal as u32, vertical as u32)),
+                                None => serial_println!("Can't interpret a video mode \"{}\".", value),
+                            },
+                            "font_ranges" => match value
+                                .split(',')
+                                .map(parse_range)
+                                .collect::<Option<Vec<RangeInclusive<char>>>>() {
+                                Some(font_ranges) => config.font_ranges = font_ranges,
+                                None => serial_println!("Can't interpret font ranges \"{}\".", value),
+                            },
+                            "stack_size" => match parse_number(value) {
+                                Some(stack_size) if 0 < stack_size => config.stack_size = stack_size,
+                                _ => serial_println!("Can't interpret a stack size \"{}\".", value),
+                            },
+                            "baud" => match parse_number(value) {
+                                Some(baud) if 0 < baud && baud <= serial::FREQUENCY as usize => config.baud = baud as u32,
+                                _ => serial_println!("Can't interpret a baud rate \"{}\".", value),
+                            },
+                            "verbosity" => match value.try_into() {
+                                Ok(verbosity) => config.verbosity = verbosity,
+                                Err(_) => serial_println!("Can't interpret a verbosity \"{}\".", value),
+                            },
+                            key => serial_println!("Unknown key \"{}\" in {}.", key, PATH),
+                        }
+                    },
+                    None => serial_println!("Can't interpret a line \"{}\" in {}.", line, PATH),
+                }
+                config
+            });
+        // Named entries replace the default entry.
+        if !entries.is_empty() {
+            config.entries = entries;
+        }
+        // The saved entry is selected after all entries are added.
+        if let Some(default) = default {
+            match config.entry(&default) {
+                _ if default == SAVED_ENTRY => config.saved = true,
+                Some(selected) => config.selected = selected,
+                None => serial_println!("Unknown default entry \"{}\" in {}.", default, PATH),
+            }
+        }
+        if let Some(fallback) = fallback {
+            match config.entry(&fallback) {
+                Some(fallback) => config.fallback = Some(fallback),
+                None => serial_println!("Unknown fallback entry \"{}\" in {}.", fallback, PATH),
+            }
+        }
+        config
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Entry {
+    name: String,
+    kernel: String,
+    command_line: String,
+    modules: Vec<String>,
+    // The volume the loader came from if None.
+    volume: Option<simple_file_system::Volume>,
+    // Files are read from the volume if None.
+    server: Option<tftp::Server>,
+    // The kernel is an EFI application to chain-load.
+    chain_load: bool,
+}
+
+impl Entry {
+    pub fn chain_load(&self) -> bool {
+        self.chain_load
+    }
+
+    pub fn command_line(&self) -> &str {
+        &self.command_line
+    }
+
+    pub fn kernel(&self) -> &str {
+        &self.kernel
+    }
+
+    pub fn modules(&self) -> &[String] {
+        &self.modules
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn server(&self) -> Option<&tftp::Server> {
+        self.server.as_ref()
+    }
+
+    pub fn volume(&self) -> Option<&simple_file_system::Volume> {
+        self.volume.as_ref()
+    }
+
+    fn new(name: &str) -> Self {
+        let name: String = name.to_string();
+        let kernel: String = DEFAULT_KERNEL.to_string();
+        let command_line = String::new();
+        let modules: Vec<String> = Vec::new();
+        let volume: Option<simple_file_system::Volume> = None;
+        let server: Option<tftp::Server> = None;
+        let chain_load: bool = false;
+        Self {
+            name,
+            kernel,
+            command_line,
+            modules,
+            volume,
+            server,
+            chain_load,
+        }
+    }
+}
+
+// "<first>-<last>" or a single character as numbers.
+fn parse_range(range: &str) -> Option<RangeInclusive<char>> {
+    let (first, last): (&str, &str) = range
+        .split_once('-')
+        .unwrap_or((range, range));
+    let first: char = char::from_u32(parse_number(first)? as u32)?;
+    let last: char = char::from_u32(parse_number(last)? as u32)?;
+    Some(first..=last)
+}
+
+fn parse_number(number: &str) -> Option<usize> {
+    let number: &str = number.trim();
+    match number.strip_prefix("0x") {
+        Some(number) => usize::from_str_radix(number, 0x10).ok(),
+        None => number.parse().ok(),
+    }
+}
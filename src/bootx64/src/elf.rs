@@ -4,7 +4,9 @@
 
 pub mod header;
 pub mod program;
+pub mod relocation;
 pub mod section;
+pub mod symbol;
 
 use {
     alloc::{
@@ -19,27 +21,44 @@ use {
         serial_println,
     },
     super::{
-        asm::{
-            control,
-            msr::architectural::ia32_efer,
-        },
+        boot_info,
         memory,
-        serial,
-        uefi::{
-            protocols::{
-                console_support::graphics_output,
-                human_interface_infrastructure::font,
-            },
-            services::boot::memory_allocation,
-            tables::system,
-            types::{
-                handle,
-                void,
-            },
-        },
+        uefi::services::boot::memory_allocation,
     },
 };
 
+// A position independent kernel is placed at a random 2 MiB aligned address in this range.
+const KASLR_BEGIN: usize = 0xffff800000000000;
+const KASLR_SIZE: usize = 0x0000010000000000;
+const KASLR_ALIGNMENT: usize = 0x200000;
+
+// Sections passed to the kernel to symbolize backtraces.
+const DEBUG_SECTIONS: [&str; 4] = [
+    ".symtab",
+    ".strtab",
+    ".eh_frame",
+    ".debug_line",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Permission {
+    ReadOnly,
+    ReadWrite,
+    ReadExecute,
+}
+
+impl Permission {
+    // A page shared by two programs.
+    fn merge(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::ReadOnly, other) | (other, Self::ReadOnly) => *other,
+            (Self::ReadWrite, Self::ReadWrite) => Self::ReadWrite,
+            (Self::ReadExecute, Self::ReadExecute) => Self::ReadExecute,
+            _ => panic!("A page shared by writable and executable programs violates W^X!"),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct Elf<'a> {
@@ -50,21 +69,38 @@ pub struct Elf<'a> {
 }
 
 impl Elf<'_> {
-    pub fn new(elf: &[u8]) -> Self {
-        let header: header::Header = elf.into();
-        let programs = program::Program::read(&header, elf);
+    // A position independent ELF is relocated to a base address chosen by the random number.
+    pub fn new(elf: &[u8], random: Option<u64>) -> Self {
+        let mut header: header::Header = elf.into();
+        let mut programs = program::Program::read(&header, elf);
         let sections = section::Section::read(&header, elf);
-        let deployed: BTreeMap<memory::PageRange, memory::Pages> = programs
-            .iter()
-            .map(|program| program.necessary_page_numbers())
-            .fold(
-                BTreeSet::<usize>::new(),
-                |page_numbers, next_page_numbers|
-                    page_numbers
-                        .union(&next_page_numbers)
-                        .cloned()
-                        .collect()
-            )
+        let bias: usize = match (header.is_position_independent(), random) {
+            (true, Some(random)) => {
+                let page_numbers: BTreeSet<usize> = Self::necessary_page_numbers(&programs);
+                let first_page: usize = *page_numbers
+                    .first()
+                    .expect("Can't read an ELF!");
+                let last_page: usize = *page_numbers
+                    .last()
+                    .expect("Can't read an ELF!");
+                let image_size: usize = (last_page + 1 - first_page) * memory_allocation::PAGE_SIZE;
+                let bases: usize = (KASLR_SIZE - image_size) / KASLR_ALIGNMENT;
+                let base: usize = KASLR_BEGIN + (random as usize % bases) * KASLR_ALIGNMENT;
+                base.wrapping_sub(first_page * memory_allocation::PAGE_SIZE)
+            },
+            (true, None) => {
+                serial_println!("No random number is available. The kernel is placed at its link address.");
+                0
+            },
+            (false, _) => 0,
+        };
+        if bias != 0 {
+            header.relocate(bias);
+            programs
+                .iter_mut()
+                .for_each(|program| program.relocate(bias));
+        }
+        let deployed: BTreeMap<memory::PageRange, memory::Pages> = Self::necessary_page_numbers(&programs)
             .into_iter()
             .fold(
                 BTreeSet::<memory::PageRange>::new(),
@@ -106,12 +142,23 @@ impl Elf<'_> {
                 (page_range, pages)
             })
             .collect();
-        Self {
+        let mut elf = Self {
             header,
             programs,
             sections,
             deployed,
+        };
+        if elf.header.is_position_independent() {
+            elf.relocate(bias);
         }
+        elf
+    }
+
+    pub fn debug_sections(&self) -> Vec<&section::Section> {
+        self.sections
+            .iter()
+            .filter(|section| DEBUG_SECTIONS.contains(&section.name()))
+            .collect()
     }
 
     pub fn page_map(&self) -> BTreeMap<usize, usize> {
@@ -126,85 +173,112 @@ impl Elf<'_> {
             .collect()
     }
 
-    pub fn run(&self, kernel_arguments: KernelArguments) {
-        serial_println!("Elf.run()");
-        self.header.run(kernel_arguments)
+    // Permissions of pages given by the flags of loadable programs.
+    // RELRO pages are read only because relocations are already applied by the bootloader.
+    pub fn page_permissions(&self) -> BTreeMap<usize, Permission> {
+        let mut page_permissions: BTreeMap<usize, Permission> = BTreeMap::new();
+        self.programs
+            .iter()
+            .filter(|program| program.is_loadable())
+            .for_each(|program| {
+                let flags: &program::p_flags::Flags = program.flags();
+                let permission: Permission = match (flags.write(), flags.execute()) {
+                    (false, false) => Permission::ReadOnly,
+                    (true, false) => Permission::ReadWrite,
+                    (false, true) => Permission::ReadExecute,
+                    (true, true) => panic!("A writable and executable program violates W^X!"),
+                };
+                program
+                    .necessary_page_numbers()
+                    .into_iter()
+                    .for_each(|page| {
+                        let virtual_address: usize = page * memory_allocation::PAGE_SIZE;
+                        let permission: Permission = match page_permissions.get(&virtual_address) {
+                            Some(other) => other.merge(&permission),
+                            None => permission,
+                        };
+                        page_permissions.insert(virtual_address, permission);
+                    });
+            });
+        self.programs
+            .iter()
+            .filter(|program| program.is_relro())
+            .for_each(|program| program
+                .full_page_numbers()
+                .into_iter()
+                .for_each(|page| {
+                    let virtual_address: usize = page * memory_allocation::PAGE_SIZE;
+                    if let Some(Permission::ReadWrite) = page_permissions.get(&virtual_address) {
+                        page_permissions.insert(virtual_address, Permission::ReadOnly);
+                    }
+                }));
+        page_permissions
     }
-}
 
-#[allow(dead_code)]
-pub struct KernelArguments<'a> {
-    image: handle::Handle<'static>,
-    system: &'a system::System<'a>,
-    memory_size: usize,
-    highest_parallel_offset: usize,
-    physical_page_present_bit_map: &'a [u8],
-    memory_map: &'a memory_allocation::PassedMap<'a>,
-    stack_floor: &'a void::Void,
-    cr0: &'a control::register0::Cr0,
-    cr2: &'a control::register2::Cr2,
-    cr3: &'a control::register3::Cr3,
-    cr4: &'a control::register4::Cr4,
-    ia32_efer: &'a Option<ia32_efer::Ia32Efer>,
-    com1: &'a serial::Serial,
-    com2: &'a serial::Serial,
-    graphics_output: &'a graphics_output::GraphicsOutput<'a>,
-    font: &'a font::Font,
-}
+    pub fn run(&self, boot_info: boot_info::BootInfo) {
+        serial_println!("Elf.run()");
+        self.header.run(boot_info)
+    }
 
-impl<'a> KernelArguments<'a> {
-    pub fn new(
-        image: handle::Handle<'static>,
-        system: &'a system::System<'a>,
-        memory_size: usize,
-        highest_parallel_offset: usize,
-        physical_page_present_bit_map: &'a [u8],
-        memory_map: &'a memory_allocation::PassedMap,
-        stack_floor: &'a void::Void,
-        cr0: &'a control::register0::Cr0,
-        cr2: &'a control::register2::Cr2,
-        cr3: &'a control::register3::Cr3,
-        cr4: &'a control::register4::Cr4,
-        ia32_efer: &'a Option<ia32_efer::Ia32Efer>,
-        com1: &'a serial::Serial,
-        com2: &'a serial::Serial,
-        graphics_output: &'a graphics_output::GraphicsOutput<'a>,
-        font: &'a font::Font,
-    ) -> Self {
-        Self {
-            image,
-            system,
-            memory_size,
-            highest_parallel_offset,
-            physical_page_present_bit_map,
-            memory_map,
-            stack_floor,
-            cr0,
-            cr2,
-            cr3,
-            cr4,
-            ia32_efer,
-            com1,
-            com2,
-            graphics_output,
-            font,
-        }
+    fn necessary_page_numbers(programs: &[program::Program]) -> BTreeSet<usize> {
+        programs
+            .iter()
+            .map(|program| program.necessary_page_numbers())
+            .fold(
+                BTreeSet::<usize>::new(),
+                |page_numbers, next_page_numbers|
+                    page_numbers
+                        .union(&next_page_numbers)
+                        .cloned()
+                        .collect()
+            )
     }
 
-    pub fn move_to_higher_half(mut self) -> usize {
-        self.image = self.image.move_to_higher_half(self.highest_parallel_offset);
-        self.system = self.system.move_to_higher_half(self.highest_parallel_offset);
-        let lower_address: &Self = &self;
-        let lower_address: *const Self = lower_address as *const Self;
-        let lower_address: usize = lower_address as usize;
-        self.highest_parallel_offset + lower_address
+    // Apply R_X86_64_RELATIVE and R_X86_64_64 against defined symbols in .rela.dyn.
+    fn relocate(&mut self, bias: usize) {
+        let relocations: Vec<(usize, usize)> = match self.sections
+            .iter()
+            .find(|section| section.name() == ".rela.dyn") {
+            Some(rela) => {
+                let symbols: Vec<symbol::Symbol> = symbol::Symbol::read(self.sections[rela.sh_link()].bytes());
+                relocation::Relocation::read(rela.bytes())
+                    .into_iter()
+                    .filter_map(|relocation| {
+                        let virtual_address: usize = relocation.r_offset().wrapping_add(bias);
+                        let addend: usize = relocation.r_addend() as usize;
+                        match relocation.r_type() {
+                            relocation::r_type::Type::None => None,
+                            relocation::r_type::Type::Relative => Some((virtual_address, bias.wrapping_add(addend))),
+                            relocation::r_type::Type::R64 => {
+                                let symbol: &symbol::Symbol = &symbols[relocation.r_sym()];
+                                assert!(symbol.is_defined(), "Can't relocate an undefined symbol!");
+                                let symbol_value: usize = if symbol.is_absolute() {
+                                    symbol.st_value()
+                                } else {
+                                    symbol.st_value().wrapping_add(bias)
+                                };
+                                Some((virtual_address, symbol_value.wrapping_add(addend)))
+                            },
+                            relocation::r_type::Type::Unsupported(r_type) => panic!("Can't apply a relocation type {:#x}!", r_type),
+                        }
+                    })
+                    .collect()
+            },
+            None => Vec::new(),
+        };
+        serial_println!("Apply {} relocations with bias {:#x}.", relocations.len(), bias);
+        relocations
+            .into_iter()
+            .for_each(|(virtual_address, value)| self.write(virtual_address, &value.to_le_bytes()));
     }
-}
 
-impl Into<usize> for &KernelArguments<'_> {
-    fn into(self) -> usize {
-        let kernel_arguments: *const KernelArguments = self as *const KernelArguments;
-        kernel_arguments as usize
+    fn write(&mut self, virtual_address: usize, bytes: &[u8]) {
+        let page: usize = virtual_address / memory_allocation::PAGE_SIZE;
+        let offset: usize = virtual_address % memory_allocation::PAGE_SIZE;
+        let (page_range, pages): (&memory::PageRange, &mut memory::Pages) = self.deployed
+            .iter_mut()
+            .find(|(page_range, _)| page_range.contains(page))
+            .expect("Can't write to the ELF!");
+        pages.write(page - page_range.start(), offset, bytes);
     }
 }
-
@@ -15,7 +15,7 @@ use {
         serial_print,
         serial_println,
     },
-    super::KernelArguments,
+    super::super::boot_info,
 };
 
 #[allow(dead_code)]
@@ -79,19 +79,19 @@ const E_SHSTRNDX_LENGTH: usize = mem::size_of::<u16>();
 const E_SHSTRNDX_END: usize = E_SHSTRNDX_BEGIN + E_SHSTRNDX_LENGTH;
 
 impl Header {
-    pub fn run(&self, kernel_arguments: KernelArguments) {
+    pub fn run(&self, boot_info: boot_info::BootInfo) {
         serial_println!("Header.run()");
         serial_println!("self.e_entry = {:#x}", self.e_entry);
-        let stack_floor: usize = kernel_arguments.stack_floor.into();
+        let stack_floor: usize = boot_info.stack_floor();
         serial_println!("stack_floor = {:#x}", stack_floor);
-        let kernel_arguments: usize = (&kernel_arguments).into();
-        serial_println!("kernel_arguments = {:#x}", kernel_arguments);
+        let boot_info: usize = (&boot_info).into();
+        serial_println!("boot_info = {:#x}", boot_info);
         unsafe {
             asm!(
                 "mov rsp, rcx",
                 "call rax",
                 in("rax") self.e_entry,
-                in("rdi") kernel_arguments,
+                in("rdi") boot_info,
                 in("rcx") stack_floor,
             );
         }
@@ -124,6 +124,15 @@ impl Header {
     pub fn e_shstrndx(&self) -> usize {
         self.e_shstrndx as usize
     }
+
+    // Position independent executables are shared objects.
+    pub fn is_position_independent(&self) -> bool {
+        matches!(self.e_type, e_type::Type::Shared)
+    }
+
+    pub fn relocate(&mut self, bias: usize) {
+        self.e_entry = self.e_entry.wrapping_add(bias);
+    }
 }
 
 impl From<&[u8]> for Header {
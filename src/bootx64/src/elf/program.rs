@@ -48,10 +48,32 @@ impl Program {
         &self.bytes
     }
 
+    pub fn flags(&self) -> &p_flags::Flags {
+        &self.header.p_flags
+    }
+
+    pub fn is_loadable(&self) -> bool {
+        matches!(self.header.p_type, p_type::Type::Load)
+    }
+
+    // A range made read only after relocation.
+    pub fn is_relro(&self) -> bool {
+        matches!(self.header.p_type, p_type::Type::GnuRelro)
+    }
+
+    // Pages entirely occupied by the program.
+    pub fn full_page_numbers(&self) -> BTreeSet<usize> {
+        self.header.full_page_numbers()
+    }
+
     pub fn necessary_page_numbers(&self) -> BTreeSet<usize> {
         self.header.necessary_page_numbers()
     }
 
+    pub fn relocate(&mut self, bias: usize) {
+        self.header.relocate(bias);
+    }
+
     pub fn start_page(&self) -> usize {
         self.header.start_page()
     }
@@ -112,6 +134,16 @@ impl Header {
             .collect()
     }
 
+    fn full_page_numbers(&self) -> BTreeSet<usize> {
+        let begin_address: usize = self.p_vaddr;
+        let begin_page: usize = (begin_address + PAGE_SIZE - 1) / PAGE_SIZE;
+        let end_address: usize = self.p_vaddr + self.p_memsz;
+        let end_page: usize = end_address / PAGE_SIZE;
+        (begin_page..end_page)
+            .into_iter()
+            .collect()
+    }
+
     fn necessary_page_numbers(&self) -> BTreeSet<usize> {
         let begin_address: usize = self.p_vaddr;
         let begin_page: usize = begin_address / PAGE_SIZE;
@@ -122,6 +154,10 @@ impl Header {
             .collect()
     }
 
+    fn relocate(&mut self, bias: usize) {
+        self.p_vaddr = self.p_vaddr.wrapping_add(bias);
+    }
+
     fn start_page(&self) -> usize {
         self.p_vaddr / PAGE_SIZE
     }
@@ -12,6 +12,16 @@ pub struct Flags {
     processor_specified: u8,
 }
 
+impl Flags {
+    pub fn execute(&self) -> bool {
+        self.execute
+    }
+
+    pub fn write(&self) -> bool {
+        self.write
+    }
+}
+
 impl From<u32> for Flags {
     fn from(p_flags: u32) -> Self {
         let execute: bool = p_flags & 0x00000001 != 0;
@@ -12,6 +12,7 @@ pub enum Type {
     Shlib,
     Phdr,
     Tls,
+    GnuRelro,
     OperatingSystemSpecific(u32),
     ProcessorSpecific(u32),
 }
@@ -27,6 +28,7 @@ impl From<u32> for Type {
             0x00000005 => Self::Shlib,
             0x00000006 => Self::Phdr,
             0x00000007 => Self::Tls,
+            0x6474e552 => Self::GnuRelro,
             p_type => {
                 if 0x60000000 <= p_type && p_type < 0x70000000 {
                     Self::OperatingSystemSpecific(p_type)
@@ -0,0 +1,81 @@
+// References
+// https://refspecs.linuxfoundation.org/elf/elf.pdf
+// https://refspecs.linuxfoundation.org/elf/x86_64-abi-0.99.pdf
+// 4.4 Relocation
+
+pub mod r_type;
+
+use {
+    alloc::vec::Vec,
+    core::mem,
+};
+
+#[derive(Debug)]
+pub struct Relocation {
+    r_offset: usize,
+    r_sym: usize,
+    r_type: r_type::Type,
+    r_addend: isize,
+}
+
+const R_OFFSET_BEGIN: usize = 0;
+const R_OFFSET_LENGTH: usize = mem::size_of::<usize>();
+const R_OFFSET_END: usize = R_OFFSET_BEGIN + R_OFFSET_LENGTH;
+const R_INFO_BEGIN: usize = R_OFFSET_END;
+const R_INFO_LENGTH: usize = mem::size_of::<usize>();
+const R_INFO_END: usize = R_INFO_BEGIN + R_INFO_LENGTH;
+const R_ADDEND_BEGIN: usize = R_INFO_END;
+const R_ADDEND_LENGTH: usize = mem::size_of::<isize>();
+const R_ADDEND_END: usize = R_ADDEND_BEGIN + R_ADDEND_LENGTH;
+const RELOCATION_SIZE: usize = R_ADDEND_END;
+
+impl Relocation {
+    pub fn read(rela: &[u8]) -> Vec<Self> {
+        rela
+            .chunks(RELOCATION_SIZE)
+            .map(|relocation| relocation.into())
+            .collect()
+    }
+
+    pub fn r_addend(&self) -> isize {
+        self.r_addend
+    }
+
+    pub fn r_offset(&self) -> usize {
+        self.r_offset
+    }
+
+    pub fn r_sym(&self) -> usize {
+        self.r_sym
+    }
+
+    pub fn r_type(&self) -> &r_type::Type {
+        &self.r_type
+    }
+}
+
+impl From<&[u8]> for Relocation {
+    fn from(relocation: &[u8]) -> Self {
+        let r_offset: [u8; R_OFFSET_LENGTH] = relocation[R_OFFSET_BEGIN..R_OFFSET_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let r_offset = usize::from_le_bytes(r_offset);
+        let r_info: [u8; R_INFO_LENGTH] = relocation[R_INFO_BEGIN..R_INFO_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let r_info = usize::from_le_bytes(r_info);
+        let r_sym: usize = r_info >> 32;
+        let r_type: u32 = r_info as u32;
+        let r_type: r_type::Type = r_type.into();
+        let r_addend: [u8; R_ADDEND_LENGTH] = relocation[R_ADDEND_BEGIN..R_ADDEND_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let r_addend = isize::from_le_bytes(r_addend);
+        Self {
+            r_offset,
+            r_sym,
+            r_type,
+            r_addend,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// References
+// https://refspecs.linuxfoundation.org/elf/x86_64-abi-0.99.pdf
+// Table 4.10: Relocation Types
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Type {
+    None,
+    R64,
+    Relative,
+    Unsupported(u32),
+}
+
+impl From<u32> for Type {
+    fn from(r_type: u32) -> Self {
+        match r_type {
+            0x00000000 => Self::None,
+            0x00000001 => Self::R64,
+            0x00000008 => Self::Relative,
+            r_type => Self::Unsupported(r_type),
+        }
+    }
+}
@@ -42,6 +42,14 @@ impl Section {
         }
     }
 
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn read(header: &header::Header, elf: &[u8]) -> Vec<Self> {
         let headers: Vec<Header> = Header::read(header, elf);
         let shstrndx: usize = header.e_shstrndx();
@@ -55,6 +63,10 @@ impl Section {
             .map(|header| Self::new(header, elf, shstrtab))
             .collect()
     }
+
+    pub fn sh_link(&self) -> usize {
+        self.header.sh_link as usize
+    }
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,95 @@
+// References
+// https://refspecs.linuxfoundation.org/elf/elf.pdf
+// https://en.wikipedia.org/wiki/Executable_and_Linkable_Format
+
+use {
+    alloc::vec::Vec,
+    core::mem,
+};
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Symbol {
+    st_name: u32,
+    st_info: u8,
+    st_other: u8,
+    st_shndx: u16,
+    st_value: usize,
+    st_size: usize,
+}
+
+const ST_NAME_BEGIN: usize = 0;
+const ST_NAME_LENGTH: usize = mem::size_of::<u32>();
+const ST_NAME_END: usize = ST_NAME_BEGIN + ST_NAME_LENGTH;
+const ST_INFO_BEGIN: usize = ST_NAME_END;
+const ST_INFO_LENGTH: usize = mem::size_of::<u8>();
+const ST_INFO_END: usize = ST_INFO_BEGIN + ST_INFO_LENGTH;
+const ST_OTHER_BEGIN: usize = ST_INFO_END;
+const ST_OTHER_LENGTH: usize = mem::size_of::<u8>();
+const ST_OTHER_END: usize = ST_OTHER_BEGIN + ST_OTHER_LENGTH;
+const ST_SHNDX_BEGIN: usize = ST_OTHER_END;
+const ST_SHNDX_LENGTH: usize = mem::size_of::<u16>();
+const ST_SHNDX_END: usize = ST_SHNDX_BEGIN + ST_SHNDX_LENGTH;
+const ST_VALUE_BEGIN: usize = ST_SHNDX_END;
+const ST_VALUE_LENGTH: usize = mem::size_of::<usize>();
+const ST_VALUE_END: usize = ST_VALUE_BEGIN + ST_VALUE_LENGTH;
+const ST_SIZE_BEGIN: usize = ST_VALUE_END;
+const ST_SIZE_LENGTH: usize = mem::size_of::<usize>();
+const ST_SIZE_END: usize = ST_SIZE_BEGIN + ST_SIZE_LENGTH;
+const SYMBOL_SIZE: usize = ST_SIZE_END;
+
+// Special section indexes
+const SHN_UNDEF: u16 = 0x0000;
+const SHN_ABS: u16 = 0xfff1;
+
+impl Symbol {
+    pub fn read(symtab: &[u8]) -> Vec<Self> {
+        symtab
+            .chunks(SYMBOL_SIZE)
+            .map(|symbol| symbol.into())
+            .collect()
+    }
+
+    pub fn is_absolute(&self) -> bool {
+        self.st_shndx == SHN_ABS
+    }
+
+    pub fn is_defined(&self) -> bool {
+        self.st_shndx != SHN_UNDEF
+    }
+
+    pub fn st_value(&self) -> usize {
+        self.st_value
+    }
+}
+
+impl From<&[u8]> for Symbol {
+    fn from(symbol: &[u8]) -> Self {
+        let st_name: [u8; ST_NAME_LENGTH] = symbol[ST_NAME_BEGIN..ST_NAME_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let st_name = u32::from_le_bytes(st_name);
+        let st_info: u8 = symbol[ST_INFO_BEGIN];
+        let st_other: u8 = symbol[ST_OTHER_BEGIN];
+        let st_shndx: [u8; ST_SHNDX_LENGTH] = symbol[ST_SHNDX_BEGIN..ST_SHNDX_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let st_shndx = u16::from_le_bytes(st_shndx);
+        let st_value: [u8; ST_VALUE_LENGTH] = symbol[ST_VALUE_BEGIN..ST_VALUE_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let st_value = usize::from_le_bytes(st_value);
+        let st_size: [u8; ST_SIZE_LENGTH] = symbol[ST_SIZE_BEGIN..ST_SIZE_END]
+            .try_into()
+            .expect("Can't read an ELF!");
+        let st_size = usize::from_le_bytes(st_size);
+        Self {
+            st_name,
+            st_info,
+            st_other,
+            st_shndx,
+            st_value,
+            st_size,
+        }
+    }
+}
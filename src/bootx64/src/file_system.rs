@@ -0,0 +1,116 @@
+// Files are read through the simple file system protocol of the firmware if possible.
+// Built-in read-only drivers read volumes the firmware can't read through the Block I/O and Disk I/O protocols.
+// Files on a TFTP server are read through the PXE base code protocol.
+
+mod exfat;
+pub mod tftp;
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::vec::Vec,
+    super::uefi::{
+        protocols::{
+            device_path,
+            media_access::{
+                block_io,
+                disk_io,
+                simple_file_system,
+            },
+        },
+        types::handle,
+    },
+};
+
+pub enum FileSystem<'a> {
+    Firmware(&'a simple_file_system::SimpleFileSystem),
+    Exfat(exfat::Exfat),
+    Tftp(tftp::Tftp),
+}
+
+impl FileSystem<'_> {
+    // A server takes precedence over a volume.
+    // The volume the loader came from if neither a server nor a volume is specified.
+    pub fn select(volume: Option<&simple_file_system::Volume>, server: Option<&tftp::Server>) -> Option<Self> {
+        if let Some(server) = server {
+            return tftp::Tftp::connect(server).map(Self::Tftp);
+        }
+        match simple_file_system::SimpleFileSystem::select(volume) {
+            Some(simple_file_system) => Some(Self::Firmware(simple_file_system)),
+            None => volume.and_then(|volume| block_io::BlockIo::handles()
+                .into_iter()
+                .find_map(|handle| Self::mount(handle, volume))),
+        }
+    }
+
+    pub fn read_file(&self, path: &str) -> Vec<u8> {
+        self.try_read_file(path).expect("Can't read a file!")
+    }
+
+    // Return None if the file doesn't exist.
+    pub fn try_read_file(&self, path: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Firmware(simple_file_system) => simple_file_system.try_read_file(path),
+            Self::Exfat(exfat) => exfat.read_file(path),
+            Self::Tftp(tftp) => tftp.read_file(path),
+        }
+    }
+
+    // Return None if the volume on the handle doesn't match or no built-in driver can read it.
+    fn mount(handle: handle::Handle, volume: &simple_file_system::Volume) -> Option<Self> {
+        if let simple_file_system::Volume::PartitionGuid(partition_guid) = volume {
+            let guid = device_path::DevicePathProtocol::new(handle)?.partition_guid()?;
+            if guid != *partition_guid {
+                return None;
+            }
+        }
+        let exfat = exfat::Exfat::mount(Volume::of(handle)?)?;
+        if let simple_file_system::Volume::Label(label) = volume {
+            if exfat.volume_label() != label {
+                return None;
+            }
+        }
+        serial_println!("Mount an exFAT volume \"{}\" by the built-in driver.", exfat.volume_label());
+        Some(Self::Exfat(exfat))
+    }
+}
+
+// Bytes of a block device.
+#[derive(Debug)]
+pub struct Volume {
+    disk_io: &'static disk_io::DiskIo,
+    media_id: u32,
+    size: u64,
+}
+
+impl Volume {
+    // Return None if no media is present.
+    pub fn of(handle: handle::Handle) -> Option<Self> {
+        let block_io: &block_io::BlockIo = block_io::BlockIo::of(handle)?;
+        let media: &block_io::BlockIoMedia = block_io.media();
+        if !media.media_present() {
+            return None;
+        }
+        let disk_io: &disk_io::DiskIo = disk_io::DiskIo::of(handle)?;
+        let media_id: u32 = media.media_id();
+        let size: u64 = (media.last_block() + 1) * media.block_size() as u64;
+        Some(Self {
+            disk_io,
+            media_id,
+            size,
+        })
+    }
+
+    // Return None if the bytes are out of the volume.
+    pub fn read(&self, offset: u64, length: usize) -> Option<Vec<u8>> {
+        let end: u64 = offset.checked_add(length as u64)?;
+        if self.size < end {
+            return None;
+        }
+        self.disk_io
+            .read(self.media_id, offset, length)
+            .ok()
+    }
+}
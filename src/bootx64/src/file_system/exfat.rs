@@ -0,0 +1,310 @@
+// A read-only exFAT driver.
+// File names are compared case insensitively by Unicode simple case mapping instead of the up-case table.
+//
+// References
+// https://learn.microsoft.com/en-us/windows/win32/fileio/exfat-specification
+
+use {
+    alloc::{
+        string::String,
+        vec::Vec,
+    },
+    core::char,
+    super::Volume,
+};
+
+const BOOT_SECTOR_SIZE: usize = 0x200;
+const FILE_SYSTEM_NAME: &[u8] = b"EXFAT   ";
+const BOOT_SIGNATURE: &[u8] = &[0x55, 0xaa];
+// The main boot checksum sector follows 11 sectors.
+const BOOT_CHECKSUM_SECTOR: usize = 11;
+const MIN_BYTES_PER_SECTOR_SHIFT: u8 = 9;
+const MAX_BYTES_PER_SECTOR_SHIFT: u8 = 12;
+const MAX_BYTES_PER_CLUSTER_SHIFT: u8 = 25;
+const FIRST_CLUSTER: u32 = 2;
+const FAT_ENTRY_SIZE: u64 = 4;
+const DIRECTORY_ENTRY_SIZE: usize = 0x20;
+const END_OF_DIRECTORY: u8 = 0x00;
+const VOLUME_LABEL: u8 = 0x83;
+const FILE: u8 = 0x85;
+const STREAM_EXTENSION: u8 = 0xc0;
+const FILE_NAME: u8 = 0xc1;
+const FILE_NAME_LENGTH: usize = 15;
+const DIRECTORY: u16 = 0x0010;
+const NO_FAT_CHAIN: u8 = 0x02;
+
+#[derive(Debug)]
+pub struct Exfat {
+    volume: Volume,
+    bytes_per_cluster: u64,
+    fat_offset: u64,
+    cluster_heap_offset: u64,
+    cluster_count: u32,
+    root_directory: Node,
+    volume_label: String,
+}
+
+impl Exfat {
+    // Return None if the volume is not a valid exFAT volume.
+    pub fn mount(volume: Volume) -> Option<Self> {
+        let boot_sector: Vec<u8> = volume.read(0, BOOT_SECTOR_SIZE)?;
+        if &boot_sector[0x03..0x0b] != FILE_SYSTEM_NAME || &boot_sector[0x1fe..0x200] != BOOT_SIGNATURE {
+            return None;
+        }
+        let fat_offset: u32 = u32::from_le_bytes(boot_sector[0x50..0x54]
+            .try_into()
+            .expect("Can't read an exFAT boot sector!"));
+        let cluster_heap_offset: u32 = u32::from_le_bytes(boot_sector[0x58..0x5c]
+            .try_into()
+            .expect("Can't read an exFAT boot sector!"));
+        let cluster_count: u32 = u32::from_le_bytes(boot_sector[0x5c..0x60]
+            .try_into()
+            .expect("Can't read an exFAT boot sector!"));
+        let root_directory: u32 = u32::from_le_bytes(boot_sector[0x60..0x64]
+            .try_into()
+            .expect("Can't read an exFAT boot sector!"));
+        let bytes_per_sector_shift: u8 = boot_sector[0x6c];
+        let sectors_per_cluster_shift: u8 = boot_sector[0x6d];
+        if !(MIN_BYTES_PER_SECTOR_SHIFT..=MAX_BYTES_PER_SECTOR_SHIFT).contains(&bytes_per_sector_shift)
+            || MAX_BYTES_PER_CLUSTER_SHIFT < bytes_per_sector_shift + sectors_per_cluster_shift {
+            return None;
+        }
+        let bytes_per_sector: u64 = 1 << bytes_per_sector_shift;
+        let bytes_per_cluster: u64 = bytes_per_sector << sectors_per_cluster_shift;
+        // The checksum skips VolumeFlags and PercentInUse which change while the volume is mounted.
+        let boot_region: Vec<u8> = volume.read(0, (BOOT_CHECKSUM_SECTOR + 1) * bytes_per_sector as usize)?;
+        let (boot_region, boot_checksum): (&[u8], &[u8]) = boot_region.split_at(BOOT_CHECKSUM_SECTOR * bytes_per_sector as usize);
+        let checksum: u32 = boot_region
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matches!(index, 0x6a | 0x6b | 0x70))
+            .fold(0u32, |checksum, (_, byte)| checksum.rotate_right(1).wrapping_add(*byte as u32));
+        let boot_checksum: u32 = u32::from_le_bytes(boot_checksum[0x0..0x4]
+            .try_into()
+            .expect("Can't read an exFAT boot checksum!"));
+        if checksum != boot_checksum {
+            return None;
+        }
+        let fat_offset: u64 = fat_offset as u64 * bytes_per_sector;
+        let cluster_heap_offset: u64 = cluster_heap_offset as u64 * bytes_per_sector;
+        // The root directory has no stream extension and its size is determined by the FAT chain.
+        let root_directory = Node {
+            name: String::new(),
+            directory: true,
+            first_cluster: root_directory,
+            no_fat_chain: false,
+            valid_data_length: u64::MAX,
+            data_length: u64::MAX,
+        };
+        let mut exfat = Self {
+            volume,
+            bytes_per_cluster,
+            fat_offset,
+            cluster_heap_offset,
+            cluster_count,
+            root_directory,
+            volume_label: String::new(),
+        };
+        let root_directory: Vec<u8> = exfat.read(&exfat.root_directory)?;
+        exfat.volume_label = root_directory
+            .chunks(DIRECTORY_ENTRY_SIZE)
+            .take_while(|entry| entry[0] != END_OF_DIRECTORY)
+            .find(|entry| entry[0] == VOLUME_LABEL)
+            .map(|entry| {
+                let length: usize = (entry[1] as usize).min(11);
+                decode_utf16(&entry[0x02..0x02 + 2 * length])
+            })
+            .unwrap_or_default();
+        Some(exfat)
+    }
+
+    // Return None if the file doesn't exist.
+    pub fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        let file: Node = path
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(None, |directory: Option<Node>, name| {
+                let directory: &Node = directory
+                    .as_ref()
+                    .unwrap_or(&self.root_directory);
+                if !directory.directory {
+                    return None;
+                }
+                let name: String = name.to_uppercase();
+                self.children(directory)?
+                    .into_iter()
+                    .find(|child| child.name.to_uppercase() == name)
+                    .map(Some)
+            })??;
+        if file.directory {
+            None
+        } else {
+            self.read(&file)
+        }
+    }
+
+    pub fn volume_label(&self) -> &str {
+        &self.volume_label
+    }
+
+    fn children(&self, directory: &Node) -> Option<Vec<Node>> {
+        let entries: Vec<u8> = self.read(directory)?;
+        let entries: Vec<&[u8]> = entries
+            .chunks(DIRECTORY_ENTRY_SIZE)
+            .take_while(|entry| entry[0] != END_OF_DIRECTORY)
+            .collect();
+        Some(entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry[0] == FILE)
+            .filter_map(|(index, entry)| {
+                let secondary_count: usize = entry[1] as usize;
+                let entry_set: &[&[u8]] = entries.get(index..=index + secondary_count)?;
+                Node::new(entry_set)
+            })
+            .collect())
+    }
+
+    fn cluster_offset(&self, cluster: u32) -> u64 {
+        self.cluster_heap_offset + (cluster - FIRST_CLUSTER) as u64 * self.bytes_per_cluster
+    }
+
+    // Clusters of the node in order.
+    fn clusters(&self, node: &Node) -> Option<Vec<u32>> {
+        if node.no_fat_chain {
+            let clusters: u64 = node.data_length.div_ceil(self.bytes_per_cluster);
+            let clusters: u32 = clusters.try_into().ok()?;
+            let last_cluster: u32 = node.first_cluster.checked_add(clusters)?;
+            if !self.is_valid_cluster(node.first_cluster) || self.cluster_count < last_cluster - FIRST_CLUSTER {
+                return None;
+            }
+            Some((node.first_cluster..last_cluster).collect())
+        } else {
+            let mut clusters: Vec<u32> = Vec::new();
+            let mut cluster: u32 = node.first_cluster;
+            while self.is_valid_cluster(cluster) {
+                // A loop in the FAT chain is a corruption.
+                if self.cluster_count as usize <= clusters.len() {
+                    return None;
+                }
+                clusters.push(cluster);
+                let fat_entry: Vec<u8> = self.volume.read(self.fat_offset + cluster as u64 * FAT_ENTRY_SIZE, FAT_ENTRY_SIZE as usize)?;
+                cluster = u32::from_le_bytes(fat_entry[..]
+                    .try_into()
+                    .expect("Can't read an exFAT FAT entry!"));
+            }
+            Some(clusters)
+        }
+    }
+
+    fn is_valid_cluster(&self, cluster: u32) -> bool {
+        FIRST_CLUSTER <= cluster && cluster - FIRST_CLUSTER < self.cluster_count
+    }
+
+    // Bytes beyond the valid data length are zero.
+    fn read(&self, node: &Node) -> Option<Vec<u8>> {
+        if node.data_length == 0 {
+            return Some(Vec::new());
+        }
+        let clusters: Vec<u32> = self.clusters(node)?;
+        let mut bytes: Vec<u8> = Vec::new();
+        // Contiguous clusters are read at once.
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+        clusters
+            .iter()
+            .for_each(|cluster| match runs.last_mut() {
+                Some((first, length)) if *first + *length == *cluster => *length += 1,
+                _ => runs.push((*cluster, 1)),
+            });
+        let data_length: u64 = node.data_length.min(clusters.len() as u64 * self.bytes_per_cluster);
+        let valid_data_length: u64 = node.valid_data_length.min(data_length);
+        for (first, length) in runs {
+            let remaining: u64 = valid_data_length - bytes.len() as u64;
+            if remaining == 0 {
+                break;
+            }
+            let length: u64 = (length as u64 * self.bytes_per_cluster).min(remaining);
+            bytes.extend(self.volume.read(self.cluster_offset(first), length as usize)?);
+        }
+        bytes.resize(data_length as usize, 0);
+        Some(bytes)
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    name: String,
+    directory: bool,
+    first_cluster: u32,
+    no_fat_chain: bool,
+    valid_data_length: u64,
+    data_length: u64,
+}
+
+impl Node {
+    // A file directory entry followed by a stream extension directory entry and file name directory entries.
+    // Return None if the entry set is broken.
+    fn new(entry_set: &[&[u8]]) -> Option<Self> {
+        let file: &[u8] = entry_set.first()?;
+        let set_checksum: u16 = u16::from_le_bytes(file[0x02..0x04]
+            .try_into()
+            .expect("Can't read an exFAT file directory entry!"));
+        let checksum: u16 = entry_set
+            .iter()
+            .flat_map(|entry| entry.iter())
+            .enumerate()
+            .filter(|(index, _)| !matches!(index, 0x02 | 0x03))
+            .fold(0u16, |checksum, (_, byte)| checksum.rotate_right(1).wrapping_add(*byte as u16));
+        if checksum != set_checksum {
+            return None;
+        }
+        let attributes: u16 = u16::from_le_bytes(file[0x04..0x06]
+            .try_into()
+            .expect("Can't read an exFAT file directory entry!"));
+        let directory: bool = attributes & DIRECTORY != 0;
+        let stream_extension: &[u8] = entry_set.get(1)?;
+        if stream_extension[0] != STREAM_EXTENSION {
+            return None;
+        }
+        let general_secondary_flags: u8 = stream_extension[0x01];
+        let no_fat_chain: bool = general_secondary_flags & NO_FAT_CHAIN != 0;
+        let name_length: usize = stream_extension[0x03] as usize;
+        let valid_data_length: u64 = u64::from_le_bytes(stream_extension[0x08..0x10]
+            .try_into()
+            .expect("Can't read an exFAT stream extension directory entry!"));
+        let first_cluster: u32 = u32::from_le_bytes(stream_extension[0x14..0x18]
+            .try_into()
+            .expect("Can't read an exFAT stream extension directory entry!"));
+        let data_length: u64 = u64::from_le_bytes(stream_extension[0x18..0x20]
+            .try_into()
+            .expect("Can't read an exFAT stream extension directory entry!"));
+        let name: Vec<u8> = entry_set[2..]
+            .iter()
+            .take_while(|entry| entry[0] == FILE_NAME)
+            .flat_map(|entry| entry[0x02..0x02 + 2 * FILE_NAME_LENGTH].iter())
+            .take(2 * name_length)
+            .copied()
+            .collect();
+        if name.len() != 2 * name_length {
+            return None;
+        }
+        let name: String = decode_utf16(&name);
+        Some(Self {
+            name,
+            directory,
+            first_cluster,
+            no_fat_chain,
+            valid_data_length,
+            data_length,
+        })
+    }
+}
+
+fn decode_utf16(bytes: &[u8]) -> String {
+    let characters = bytes
+        .chunks(2)
+        .map(|character| u16::from_le_bytes([character[0], character[1]]));
+    char::decode_utf16(characters)
+        .map(|character| character.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
@@ -0,0 +1,95 @@
+// Files are read from a TFTP server through the PXE base code protocol of the firmware.
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::{
+        format,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    super::super::uefi::protocols::network::pxe_base_code,
+};
+
+const SCHEME: &str = "tftp://";
+
+// A directory on a TFTP server "tftp://<IPv4 address>/<directory>".
+#[derive(Clone, Debug)]
+pub struct Server {
+    address: [u8; 4],
+    directory: String,
+}
+
+impl TryFrom<&str> for Server {
+    type Error = ();
+
+    fn try_from(url: &str) -> Result<Self, Self::Error> {
+        let url: &str = url
+            .trim()
+            .strip_prefix(SCHEME)
+            .ok_or(())?;
+        let (address, directory): (&str, &str) = url
+            .split_once('/')
+            .unwrap_or((url, ""));
+        let address: Vec<u8> = address
+            .split('.')
+            .map(|byte| byte.parse().map_err(|_| ()))
+            .collect::<Result<Vec<u8>, ()>>()?;
+        let address: [u8; 4] = address
+            .try_into()
+            .map_err(|_| ())?;
+        let directory: String = directory
+            .trim_matches('/')
+            .to_string();
+        Ok(Self {
+            address,
+            directory,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Tftp {
+    pxe_base_code: &'static pxe_base_code::PxeBaseCode<'static>,
+    server: Server,
+}
+
+impl Tftp {
+    // Return None if no network interface is available.
+    pub fn connect(server: &Server) -> Option<Self> {
+        let pxe_base_code: &pxe_base_code::PxeBaseCode = pxe_base_code::PxeBaseCode::new()?;
+        if let Err(status) = pxe_base_code.configure() {
+            serial_println!("Can't configure a network interface. status = {:#x?}", status);
+            return None;
+        }
+        serial_println!("Connect to a TFTP server {:?} from {:?}.", server, pxe_base_code.mode().station_ip());
+        let server: Server = server.clone();
+        Some(Self {
+            pxe_base_code,
+            server,
+        })
+    }
+
+    // Return None if the file can't be read.
+    pub fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        let path: &str = path.trim_start_matches('/');
+        let path: String = if self.server.directory.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.server.directory, path)
+        };
+        let address: pxe_base_code::IpAddress = self.server.address.into();
+        match self.pxe_base_code.read_file(&address, &path) {
+            Ok(file) => Some(file),
+            Err(status) => {
+                serial_println!("Can't read {} from a TFTP server. status = {:#x?}", path, status);
+                None
+            },
+        }
+    }
+}
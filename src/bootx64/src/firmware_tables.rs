@@ -0,0 +1,218 @@
+// Firmware tables are located by GUID in the UEFI configuration table.
+// Their physical addresses are passed to the kernel only if they are valid.
+//
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf 4.6 EFI Configuration Table & Properties Table
+// https://uefi.org/specs/ACPI/6.5/05_ACPI_Software_Programming_Model.html 5.2.5.3 Root System Description Pointer (RSDP) Structure
+// https://www.dmtf.org/sites/default/files/standards/documents/DSP0134_3.6.0.pdf 5.2.2 SMBIOS 3.0 (64-bit) Entry Point
+// https://github.com/devicetree-org/devicetree-specification 5.2 Header
+
+use {
+    core::slice,
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    super::uefi::{
+        services::boot::{
+            memory_allocation,
+            protocol_handler,
+        },
+        tables::system,
+    },
+};
+
+#[derive(Debug)]
+pub struct FirmwareTables {
+    rsdp: Option<usize>,
+    smbios: Option<usize>,
+    device_tree: Option<usize>,
+    memory_attributes: Option<usize>,
+}
+
+impl FirmwareTables {
+    pub fn locate(system: &system::System) -> Self {
+        let rsdp: Option<usize> = locate(system, &ACPI_20_TABLE_GUID, "RSDP", rsdp_is_valid);
+        let smbios: Option<usize> = locate(system, &SMBIOS3_TABLE_GUID, "SMBIOS 3 entry point", smbios_is_valid);
+        let device_tree: Option<usize> = locate(system, &DTB_TABLE_GUID, "device tree", device_tree_is_valid);
+        let memory_attributes: Option<usize> = locate(system, &MEMORY_ATTRIBUTES_TABLE_GUID, "memory attributes table", memory_attributes_is_valid);
+        Self {
+            rsdp,
+            smbios,
+            device_tree,
+            memory_attributes,
+        }
+    }
+
+    pub fn device_tree(&self) -> Option<usize> {
+        self.device_tree
+    }
+
+    pub fn memory_attributes(&self) -> Option<usize> {
+        self.memory_attributes
+    }
+
+    pub fn rsdp(&self) -> Option<usize> {
+        self.rsdp
+    }
+
+    pub fn smbios(&self) -> Option<usize> {
+        self.smbios
+    }
+}
+
+// EFI_ACPI_20_TABLE_GUID
+const ACPI_20_TABLE_GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x8868e871,
+    0xe4f1,
+    0x11d3,
+    [
+        0xbc,
+        0x22,
+        0x00,
+        0x80,
+        0xc7,
+        0x3c,
+        0x88,
+        0x81,
+    ],
+);
+
+// SMBIOS3_TABLE_GUID
+const SMBIOS3_TABLE_GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0xf2fd1544,
+    0x9794,
+    0x4a2c,
+    [
+        0x99,
+        0x2e,
+        0xe5,
+        0xbb,
+        0xcf,
+        0x20,
+        0xe3,
+        0x94,
+    ],
+);
+
+// EFI_DTB_TABLE_GUID
+const DTB_TABLE_GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0xb1b621d5,
+    0xf19c,
+    0x41a5,
+    [
+        0x83,
+        0x0b,
+        0xd9,
+        0x15,
+        0x2c,
+        0x69,
+        0xaa,
+        0xe0,
+    ],
+);
+
+// EFI_MEMORY_ATTRIBUTES_TABLE_GUID
+const MEMORY_ATTRIBUTES_TABLE_GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0xdcfa911d,
+    0x26eb,
+    0x469f,
+    [
+        0xa2,
+        0x20,
+        0x38,
+        0xb7,
+        0xdc,
+        0x46,
+        0x12,
+        0x20,
+    ],
+);
+
+const RSDP_SIGNATURE: &[u8] = b"RSD PTR ";
+const RSDP_V1_LENGTH: usize = 0x14;
+const RSDP_V2_LENGTH: usize = 0x24;
+const SMBIOS3_ANCHOR: &[u8] = b"_SM3_";
+const SMBIOS3_LENGTH: usize = 0x18;
+const DEVICE_TREE_MAGIC: u32 = 0xd00dfeed;
+const DEVICE_TREE_HEADER_LENGTH: usize = 0x28;
+const MEMORY_ATTRIBUTES_HEADER_LENGTH: usize = 0x10;
+
+fn locate(system: &system::System, guid: &protocol_handler::Guid, name: &str, is_valid: fn(usize) -> bool) -> Option<usize> {
+    match system.configuration_table(guid) {
+        Some(table) => {
+            let table: usize = table.into();
+            if is_valid(table) {
+                serial_println!("The {} is at {:#x}.", name, table);
+                Some(table)
+            } else {
+                serial_println!("The {} at {:#x} is invalid.", name, table);
+                None
+            }
+        },
+        None => {
+            serial_println!("No {} is found.", name);
+            None
+        },
+    }
+}
+
+fn bytes<'a>(address: usize, length: usize) -> &'a [u8] {
+    let address: *const u8 = address as *const u8;
+    unsafe {
+        slice::from_raw_parts(address, length)
+    }
+}
+
+// The sum of all bytes of a table is 0.
+fn checksum_is_valid(bytes: &[u8]) -> bool {
+    bytes
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte)) == 0
+}
+
+// Both the checksum of the ACPI 1.0 part and the extended checksum of the whole structure are valid.
+fn rsdp_is_valid(address: usize) -> bool {
+    let rsdp: &[u8] = bytes(address, RSDP_V2_LENGTH);
+    let revision: u8 = rsdp[0x0f];
+    let length = u32::from_le_bytes(rsdp[0x14..0x18]
+        .try_into()
+        .expect("Can't read an RSDP!")) as usize;
+    &rsdp[..RSDP_SIGNATURE.len()] == RSDP_SIGNATURE
+        && checksum_is_valid(&rsdp[..RSDP_V1_LENGTH])
+        && 2 <= revision
+        && RSDP_V2_LENGTH <= length
+        && checksum_is_valid(bytes(address, length))
+}
+
+fn smbios_is_valid(address: usize) -> bool {
+    let smbios: &[u8] = bytes(address, SMBIOS3_LENGTH);
+    let length: usize = smbios[0x06] as usize;
+    &smbios[..SMBIOS3_ANCHOR.len()] == SMBIOS3_ANCHOR
+        && SMBIOS3_LENGTH <= length
+        && checksum_is_valid(bytes(address, length))
+}
+
+// A flattened device tree has no checksum, so its magic number and size are checked.
+fn device_tree_is_valid(address: usize) -> bool {
+    let device_tree: &[u8] = bytes(address, DEVICE_TREE_HEADER_LENGTH);
+    let magic: u32 = u32::from_be_bytes(device_tree[0x00..0x04]
+        .try_into()
+        .expect("Can't read a device tree!"));
+    let total_size = u32::from_be_bytes(device_tree[0x04..0x08]
+        .try_into()
+        .expect("Can't read a device tree!")) as usize;
+    magic == DEVICE_TREE_MAGIC && DEVICE_TREE_HEADER_LENGTH <= total_size
+}
+
+// The memory attributes table has no checksum, so its version and descriptor size are checked.
+fn memory_attributes_is_valid(address: usize) -> bool {
+    let memory_attributes: &[u8] = bytes(address, MEMORY_ATTRIBUTES_HEADER_LENGTH);
+    let version: u32 = u32::from_le_bytes(memory_attributes[0x00..0x04]
+        .try_into()
+        .expect("Can't read a memory attributes table!"));
+    let descriptor_size = u32::from_le_bytes(memory_attributes[0x08..0x0c]
+        .try_into()
+        .expect("Can't read a memory attributes table!")) as usize;
+    (1..=2).contains(&version) && memory_allocation::MEMORY_DESCRIPTOR_SIZE <= descriptor_size
+}
@@ -86,6 +86,7 @@ pub struct Gdt {
     fs: u16,
     gs: u16,
     ss: u16,
+    compatibility_mode_cs: u16,
 }
 
 impl Gdt {
@@ -93,10 +94,12 @@ impl Gdt {
         const NULL_DESCRIPTOR: u16 = 0x00;
         const CODE_DESCRIPTOR: u16 = 0x08;
         const DATA_DESCRIPTOR: u16 = 0x10;
-        let descriptors: [u16; 3] = [
+        const COMPATIBILITY_MODE_CODE_DESCRIPTOR: u16 = 0x18;
+        let descriptors: [u16; 4] = [
             NULL_DESCRIPTOR,
             CODE_DESCRIPTOR,
             DATA_DESCRIPTOR,
+            COMPATIBILITY_MODE_CODE_DESCRIPTOR,
         ];
         let descriptors: Vec<Descriptor> = descriptors
             .into_iter()
@@ -104,6 +107,7 @@ impl Gdt {
                 NULL_DESCRIPTOR => Descriptor::null(),
                 CODE_DESCRIPTOR => Descriptor::code(),
                 DATA_DESCRIPTOR => Descriptor::data(),
+                COMPATIBILITY_MODE_CODE_DESCRIPTOR => Descriptor::compatibility_mode_code(),
                 _ => panic!("Can't create a new GDT!"),
             })
             .collect();
@@ -128,6 +132,7 @@ impl Gdt {
         let fs: u16 = DATA_DESCRIPTOR;
         let gs: u16 = DATA_DESCRIPTOR;
         let ss: u16 = DATA_DESCRIPTOR;
+        let compatibility_mode_cs: u16 = COMPATIBILITY_MODE_CODE_DESCRIPTOR;
         Self {
             descriptors,
             region,
@@ -138,9 +143,18 @@ impl Gdt {
             fs,
             gs,
             ss,
+            compatibility_mode_cs,
         }
     }
 
+    pub fn compatibility_mode_cs(&self) -> u16 {
+        self.compatibility_mode_cs
+    }
+
+    pub fn cs(&self) -> u16 {
+        self.cs
+    }
+
     pub fn set(&self) {
         self.register.set();
         unsafe {
@@ -267,6 +281,37 @@ impl Descriptor {
         )
     }
 
+    // A code segment to get out of 64-bit mode temporarily.
+    fn compatibility_mode_code() -> Self {
+        let base: u32 = 0x00000000;
+        let limit: u32 = 0xffffffff;
+        let accessed: bool = false;
+        let readable: bool = true;
+        let conforming: bool = false;
+        let segment_type = SegmentType::Code {
+            accessed,
+            readable,
+            conforming,
+        };
+        let s: bool = true;
+        let dpl: u8 = 0x00;
+        let p: bool = true;
+        let avl: bool = false;
+        let l: bool = false;
+        let db: bool = true;
+        Self::new(
+            base,
+            limit,
+            segment_type,
+            s,
+            dpl,
+            p,
+            avl,
+            l,
+            db,
+        )
+    }
+
     fn data() -> Self {
         let base: u32 = 0x00000000;
         let limit: u32 = 0xffffffff;
@@ -0,0 +1,239 @@
+// GUID partition tables are read through the Block I/O and Disk I/O protocols.
+// Partitions are readable even if the firmware doesn't understand their file systems.
+//
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf 5.3 GUID Partition Table (GPT) Disk Layout
+
+use {
+    alloc::{
+        string::String,
+        vec::Vec,
+    },
+    core::char,
+    super::uefi::{
+        protocols::media_access::{
+            block_io,
+            disk_io,
+        },
+        services::boot::protocol_handler,
+        tables::system,
+        types::handle,
+    },
+};
+
+const SIGNATURE: &[u8] = b"EFI PART";
+const HEADER_LBA: u64 = 1;
+const MIN_HEADER_SIZE: usize = 0x5c;
+const MIN_ENTRY_SIZE: usize = 0x80;
+// Ignore corrupted tables claiming too many entries.
+const MAX_ENTRIES_SIZE: usize = 0x100000;
+const NAME_LENGTH: usize = 36;
+
+#[derive(Debug)]
+pub struct Disk {
+    block_io: &'static block_io::BlockIo<'static>,
+    disk_io: &'static disk_io::DiskIo,
+    guid: protocol_handler::Guid,
+    partitions: Vec<Partition>,
+}
+
+impl Disk {
+    // Whole disks with a valid GPT.
+    pub fn all() -> Vec<Self> {
+        block_io::BlockIo::handles()
+            .into_iter()
+            .filter_map(|handle| Self::of(handle))
+            .collect()
+    }
+
+    // Return None if the handle is not a whole disk with a valid GPT.
+    pub fn of(handle: handle::Handle<'static>) -> Option<Self> {
+        let block_io: &block_io::BlockIo = block_io::BlockIo::of(handle)?;
+        let media: &block_io::BlockIoMedia = block_io.media();
+        if media.logical_partition() || !media.media_present() {
+            return None;
+        }
+        let disk_io: &disk_io::DiskIo = disk_io::DiskIo::of(handle)?;
+        let header: Vec<u8> = block_io
+            .read_blocks(HEADER_LBA, 1)
+            .ok()?;
+        if header.len() < MIN_HEADER_SIZE || &header[..SIGNATURE.len()] != SIGNATURE {
+            return None;
+        }
+        let header_size = u32::from_le_bytes(header[0x0c..0x10]
+            .try_into()
+            .expect("Can't read a GPT header!")) as usize;
+        if header_size < MIN_HEADER_SIZE || header.len() < header_size {
+            return None;
+        }
+        let header_crc32: u32 = u32::from_le_bytes(header[0x10..0x14]
+            .try_into()
+            .expect("Can't read a GPT header!"));
+        // The CRC32 of the header is calculated with the CRC32 field zeroed.
+        let mut zeroed: Vec<u8> = header[..header_size].to_vec();
+        zeroed[0x10..0x14].fill(0);
+        if crc32(&zeroed)? != header_crc32 {
+            return None;
+        }
+        let guid: &[u8; 16] = header[0x38..0x48]
+            .try_into()
+            .expect("Can't read a GPT header!");
+        let guid: protocol_handler::Guid = guid.into();
+        let entries_lba: u64 = u64::from_le_bytes(header[0x48..0x50]
+            .try_into()
+            .expect("Can't read a GPT header!"));
+        let entries = u32::from_le_bytes(header[0x50..0x54]
+            .try_into()
+            .expect("Can't read a GPT header!")) as usize;
+        let entry_size = u32::from_le_bytes(header[0x54..0x58]
+            .try_into()
+            .expect("Can't read a GPT header!")) as usize;
+        let entries_crc32: u32 = u32::from_le_bytes(header[0x58..0x5c]
+            .try_into()
+            .expect("Can't read a GPT header!"));
+        let entries_size: usize = entries.checked_mul(entry_size)?;
+        if entry_size < MIN_ENTRY_SIZE || MAX_ENTRIES_SIZE < entries_size {
+            return None;
+        }
+        let entries: Vec<u8> = disk_io
+            .read(media.media_id(), entries_lba * media.block_size() as u64, entries_size)
+            .ok()?;
+        if crc32(&entries)? != entries_crc32 {
+            return None;
+        }
+        let partitions: Vec<Partition> = entries
+            .chunks(entry_size)
+            .filter_map(|entry| Partition::new(entry))
+            .collect();
+        Some(Self {
+            block_io,
+            disk_io,
+            guid,
+            partitions,
+        })
+    }
+
+    pub fn block_size(&self) -> u32 {
+        self.block_io
+            .media()
+            .block_size()
+    }
+
+    pub fn guid(&self) -> &protocol_handler::Guid {
+        &self.guid
+    }
+
+    pub fn media(&self) -> &block_io::BlockIoMedia {
+        self.block_io.media()
+    }
+
+    pub fn partitions(&self) -> &[Partition] {
+        &self.partitions
+    }
+
+    // Read bytes of a partition.
+    // Return None if the bytes are out of the partition.
+    #[allow(dead_code)]
+    pub fn read_partition(&self, partition: &Partition, offset: u64, length: usize) -> Option<Vec<u8>> {
+        let block_size: u64 = self.block_size() as u64;
+        let size: u64 = partition.blocks() * block_size;
+        let end: u64 = offset.checked_add(length as u64)?;
+        if size < end {
+            return None;
+        }
+        let media_id: u32 = self.block_io
+            .media()
+            .media_id();
+        self.disk_io
+            .read(media_id, partition.first_lba * block_size + offset, length)
+            .ok()
+    }
+}
+
+#[derive(Debug)]
+pub struct Partition {
+    type_guid: protocol_handler::Guid,
+    unique_guid: protocol_handler::Guid,
+    first_lba: u64,
+    last_lba: u64,
+    attributes: u64,
+    name: String,
+}
+
+impl Partition {
+    // Return None if the entry is unused.
+    fn new(entry: &[u8]) -> Option<Self> {
+        let type_guid: &[u8; 16] = entry[0x00..0x10]
+            .try_into()
+            .expect("Can't read a GPT entry!");
+        if type_guid
+            .iter()
+            .all(|byte| *byte == 0) {
+            return None;
+        }
+        let type_guid: protocol_handler::Guid = type_guid.into();
+        let unique_guid: &[u8; 16] = entry[0x10..0x20]
+            .try_into()
+            .expect("Can't read a GPT entry!");
+        let unique_guid: protocol_handler::Guid = unique_guid.into();
+        let first_lba: u64 = u64::from_le_bytes(entry[0x20..0x28]
+            .try_into()
+            .expect("Can't read a GPT entry!"));
+        let last_lba: u64 = u64::from_le_bytes(entry[0x28..0x30]
+            .try_into()
+            .expect("Can't read a GPT entry!"));
+        let attributes: u64 = u64::from_le_bytes(entry[0x30..0x38]
+            .try_into()
+            .expect("Can't read a GPT entry!"));
+        let name: Vec<u16> = entry[0x38..0x38 + 2 * NAME_LENGTH]
+            .chunks(2)
+            .map(|character| u16::from_le_bytes([character[0], character[1]]))
+            .take_while(|character| *character != 0)
+            .collect();
+        let name: String = char::decode_utf16(name.into_iter())
+            .map(|character| character.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect();
+        if last_lba < first_lba {
+            return None;
+        }
+        Some(Self {
+            type_guid,
+            unique_guid,
+            first_lba,
+            last_lba,
+            attributes,
+            name,
+        })
+    }
+
+    pub fn attributes(&self) -> u64 {
+        self.attributes
+    }
+
+    pub fn blocks(&self) -> u64 {
+        self.last_lba - self.first_lba + 1
+    }
+
+    pub fn first_lba(&self) -> u64 {
+        self.first_lba
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_guid(&self) -> &protocol_handler::Guid {
+        &self.type_guid
+    }
+
+    pub fn unique_guid(&self) -> &protocol_handler::Guid {
+        &self.unique_guid
+    }
+}
+
+fn crc32(bytes: &[u8]) -> Option<u32> {
+    system::system()
+        .boot_services
+        .calculate_crc32(bytes)
+        .ok()
+}
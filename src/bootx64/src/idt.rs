@@ -0,0 +1,225 @@
+// A minimal IDT catches exceptions between exiting boot services and the kernel installing its own IDT.
+// Each exception dumps the registers and the page table walk for the faulting address to the serial ports and halts instead of triple faulting.
+//
+// References
+// Intel 64 an IA-32 Architectures Software Developer's Manual, Volume 3, Chapter 6 Interrupt and Exception Handling
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::vec::Vec,
+    core::{
+        arch::{
+            asm,
+            global_asm,
+        },
+        mem,
+    },
+    super::{
+        asm::{
+            self,
+            control,
+        },
+        memory::paging,
+        serial,
+    },
+};
+
+const EXCEPTIONS: usize = 32;
+// Each stub is aligned to 16 bytes.
+const STUB_SIZE: usize = 0x10;
+const PAGE_FAULT: u64 = 14;
+// 64-bit interrupt gate
+const GATE_TYPE: u64 = 0xe;
+const NAMES: [&str; EXCEPTIONS] = [
+    "Divide Error",
+    "Debug",
+    "NMI Interrupt",
+    "Breakpoint",
+    "Overflow",
+    "BOUND Range Exceeded",
+    "Invalid Opcode",
+    "Device Not Available",
+    "Double Fault",
+    "Coprocessor Segment Overrun",
+    "Invalid TSS",
+    "Segment Not Present",
+    "Stack Fault",
+    "General Protection",
+    "Page Fault",
+    "Reserved",
+    "x87 FPU Floating-Point Error",
+    "Alignment Check",
+    "Machine Check",
+    "SIMD Floating-Point Exception",
+    "Virtualization Exception",
+    "Control Protection Exception",
+    "Reserved",
+    "Reserved",
+    "Reserved",
+    "Reserved",
+    "Reserved",
+    "Reserved",
+    "Hypervisor Injection Exception",
+    "VMM Communication Exception",
+    "Security Exception",
+    "Reserved",
+];
+
+// The page tables walked by the handler.
+static mut PAGING: Option<*const paging::State<'static>> = None;
+
+// Stubs push a dummy error code unless the processor pushes one, push the vector and jump to the common entry.
+// The common entry pushes the general purpose registers and passes them to the handler as a frame.
+global_asm!(
+    ".global exception_stubs",
+    ".balign 16",
+    "exception_stubs:",
+    ".irp vector, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31",
+    ".balign 16",
+    ".if !((\\vector == 8) || ((10 <= \\vector) && (\\vector <= 14)) || (\\vector == 17) || (\\vector == 21) || (\\vector == 29) || (\\vector == 30))",
+    "push 0",
+    ".endif",
+    "push \\vector",
+    "jmp exception_common",
+    ".endr",
+    "exception_common:",
+    "push rax",
+    "push rbx",
+    "push rcx",
+    "push rdx",
+    "push rsi",
+    "push rdi",
+    "push rbp",
+    "push r8",
+    "push r9",
+    "push r10",
+    "push r11",
+    "push r12",
+    "push r13",
+    "push r14",
+    "push r15",
+    "mov rdi, rsp",
+    "call {}",
+    sym handle,
+);
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct Idt {
+    region: Vec<u64>,
+    base: u64,
+    limit: u16,
+}
+
+impl Idt {
+    pub fn new(cs: u16) -> Self {
+        let stubs: usize;
+        unsafe {
+            asm!(
+                "lea {}, [rip + exception_stubs]",
+                out(reg) stubs,
+            );
+        }
+        let region: Vec<u64> = (0..EXCEPTIONS)
+            .flat_map(|vector| {
+                let offset: u64 = (stubs + vector * STUB_SIZE) as u64;
+                let present: u64 = 1 << 47;
+                let low: u64 = (offset & 0xffff) | (cs as u64) << 16 | GATE_TYPE << 40 | present | (offset >> 16 & 0xffff) << 48;
+                let high: u64 = offset >> 32;
+                [low, high]
+            })
+            .collect();
+        let base: *const u64 = region.as_ptr();
+        let base: u64 = base as u64;
+        let limit: usize = region.len() * mem::size_of::<u64>() - 1;
+        let limit: u16 = limit as u16;
+        Self {
+            region,
+            base,
+            limit,
+        }
+    }
+
+    // The handler walks the page tables of the paging state.
+    pub fn set(&self, paging: &paging::State) {
+        let paging: *const paging::State = paging;
+        let paging: *const paging::State<'static> = paging as *const paging::State<'static>;
+        unsafe {
+            PAGING = Some(paging);
+        }
+        let base: u128 = self.base as u128;
+        let base: u128 = base << 16;
+        let limit: u128 = self.limit as u128;
+        let idtr: u128 = base | limit;
+        let idtrp: &u128 = &idtr;
+        let idtrp: *const u128 = idtrp as *const u128;
+        let idtrp: usize = idtrp as usize;
+        unsafe {
+            asm!(
+                "lidt [{}]",
+                in(reg) idtrp,
+            );
+        }
+    }
+}
+
+// Registers in the order the stubs and the processor push them.
+#[derive(Debug)]
+#[repr(C)]
+struct Frame {
+    r15: u64,
+    r14: u64,
+    r13: u64,
+    r12: u64,
+    r11: u64,
+    r10: u64,
+    r9: u64,
+    r8: u64,
+    rbp: u64,
+    rdi: u64,
+    rsi: u64,
+    rdx: u64,
+    rcx: u64,
+    rbx: u64,
+    rax: u64,
+    vector: u64,
+    error_code: u64,
+    rip: u64,
+    cs: u64,
+    rflags: u64,
+    rsp: u64,
+    ss: u64,
+}
+
+extern "sysv64" fn handle(frame: &Frame) -> ! {
+    // The dump must not be hidden by the verbosity.
+    serial::set_verbosity(serial::Verbosity::Normal);
+    let name: &str = NAMES
+        .get(frame.vector as usize)
+        .copied()
+        .unwrap_or("Unknown");
+    serial_println!("Exception {} ({}) error_code = {:#x}", frame.vector, name, frame.error_code);
+    serial_println!("frame = {:#x?}", frame);
+    let cr2 = control::register2::Cr2::get();
+    serial_println!("cr2 = {:#x?}", cr2);
+    serial_println!("cr3 = {:#x?}", control::register3::Cr3::get());
+    let address: u64 = if frame.vector == PAGE_FAULT {
+        cr2.page_fault_linear_address()
+    } else {
+        frame.rip
+    };
+    if let Some(paging) = unsafe {
+        PAGING
+    } {
+        let paging: &paging::State = unsafe {
+            &*paging
+        };
+        paging.print_state_at_address(address as usize);
+    }
+    loop {
+        asm::hlt();
+    }
+}
@@ -7,15 +7,29 @@ extern crate alloc;
 
 mod allocator;
 mod asm;
+mod boot_info;
+mod boot_state;
+mod chain;
+mod config;
 mod elf;
+mod file_system;
+mod firmware_tables;
 mod gdt;
+mod gpt;
+mod idt;
 mod memory;
+mod menu;
+mod module;
+mod profile;
+mod random;
 mod serial;
+mod signature;
 mod uefi;
 
 use {
     alloc::{
         collections::btree_map::BTreeMap,
+        string::String,
         vec::Vec,
     },
     asm::{
@@ -27,7 +41,10 @@ use {
     memory::paging,
     uefi::{
         protocols::{
-            console_support::graphics_output,
+            console_support::{
+                edid_active,
+                graphics_output,
+            },
             human_interface_infrastructure::font,
             media_access::simple_file_system,
         },
@@ -41,17 +58,43 @@ use {
     },
 };
 
+// Messages printed before exiting boot services are saved to the boot volume for machines without serial ports.
+const BOOT_LOG: &str = "/theos-boot.log";
+// Unmapped pages above and below the kernel stack.
+const STACK_GUARD_PAGES: usize = 1;
+
 #[no_mangle]
 fn efi_main(image_handle: handle::Handle<'static>, system_table: &'static mut system::System<'static>) -> status::Status {
-    serial::Serial::init_com1();
-    serial::Serial::init_com2();
+    serial::Serial::init_com1(serial::BAUD);
+    serial::Serial::init_com2(serial::BAUD);
     system::init_system(image_handle, system_table);
+    serial::start_log();
     uefi_println!("Hello, World!");
-    let mut kernel = Kernel::new();
+    let mut config = config::Config::read(simple_file_system::SimpleFileSystem::new());
+    config.add_kernels(simple_file_system::SimpleFileSystem::new());
+    serial::Serial::init_com1(config.baud());
+    serial::Serial::init_com2(config.baud());
+    serial::set_verbosity(config.verbosity());
+    serial_println!("config = {:#x?}", config);
+    boot_state::restore(&mut config);
+    menu::run(&mut config);
+    boot_state::save(&config);
+    // Return to the menu when a chain-loaded application exits.
+    while config.chain_load() {
+        if let Err(status) = chain::run(&config) {
+            uefi_println!("Can't chain-load {}. status = {:#x?}", config.kernel(), status);
+        }
+        menu::stay(&mut config);
+        boot_state::save(&config);
+    }
+    let mut kernel = Kernel::new(&config);
+    save_boot_log();
+    let timer = profile::Timer::start("exit_boot_services");
     let memory_map: &memory_allocation::Map = &system::exit_boot_services();
-    serial_println!("memory_map = {:#x?}", memory_map);
-    let memory_map: memory_allocation::PassedMap = memory_map.into();
-    kernel.run(system::image(), system::system(), &memory_map, serial::Serial::com1(), serial::Serial::com2());
+    timer.stop();
+    serial_verbose_println!("memory_map = {:#x?}", memory_map);
+    let memory_map: boot_info::MemoryMap = memory_map.into();
+    kernel.run(system::image(), system::system(), memory_map, serial::Serial::com1(), serial::Serial::com2());
     panic!("Can't run the kernel!");
 }
 
@@ -61,6 +104,7 @@ struct Kernel<'a> {
     elf: elf::Elf<'a>,
     cpuid: Option<cpuid::Cpuid>,
     gdt: gdt::Gdt,
+    idt: idt::Idt,
     memory_size: usize,
     highest_parallel_offset: usize,
     physical_page_present_bit_map: memory::PhysicalPagePresentBitMap,
@@ -68,60 +112,148 @@ struct Kernel<'a> {
     paging: paging::State<'a>,
     stack: memory::Pages<'a>,
     stack_floor: &'a void::Void,
-    cr0: control::register0::Cr0,
-    cr2: control::register2::Cr2,
-    cr3: control::register3::Cr3,
     cr4: control::register4::Cr4,
-    ia32_efer: Option<ia32_efer::Ia32Efer>,
     graphics_output: &'a graphics_output::GraphicsOutput<'a>,
-    font: font::Font,
+    font: boot_info::FontBitmaps,
+    firmware_tables: firmware_tables::FirmwareTables,
+    command_line: String,
+    modules: Vec<module::Loaded<'a>>,
+    passed_modules: Vec<module::Module>,
+    debug_sections: Vec<module::Loaded<'a>>,
+    passed_debug_sections: Vec<module::Module>,
 }
 
 impl Kernel<'_> {
-    fn new() -> Self {
+    fn new(config: &config::Config) -> Self {
+        let timer = profile::Timer::start("memory map");
         let memory_map = memory_allocation::Map::new();
         let memory_size: memory_allocation::PhysicalAddress = memory_map.get_memory_size();
         let memory_size = memory_size as usize;
-        let highest_parallel_offset: usize = usize::MAX - (memory_size - 1);
         serial_println!("memory_size = {:#x?}", memory_size);
         let memory_map: Vec<memory_allocation::MemoryDescriptor> = (&memory_map).into();
         let physical_page_present_bit_map: memory::PhysicalPagePresentBitMap = (&memory_map).into();
+        timer.stop();
         let cpuid: Option<cpuid::Cpuid> = cpuid::Cpuid::new();
         let supports_5_level_paging: bool = match cpuid {
             Some(ref cpuid) => cpuid.supports_5_level_paging(),
             None => false,
         };
         serial_println!("supports_5_level_paging = {:?}", supports_5_level_paging);
+        let supports_1_gib_pages: bool = match cpuid {
+            Some(ref cpuid) => cpuid.supports_1_gib_pages(),
+            None => false,
+        };
+        serial_println!("supports_1_gib_pages = {:?}", supports_1_gib_pages);
         let mut ia32_efer: Option<ia32_efer::Ia32Efer> = ia32_efer::Ia32Efer::get(&cpuid);
         if let Some(ia32_efer) = ia32_efer.as_mut() {
             if !ia32_efer.nxe() {
                 ia32_efer.set_nxe();
             }
         }
+        let timer = profile::Timer::start("paging setup");
         let cr0 = control::register0::Cr0::get();
-        let cr2 = control::register2::Cr2::get();
         let cr3 = control::register3::Cr3::get();
         let cr4 = control::register4::Cr4::get();
-        let mut paging = paging::State::new(&cr0, &cr3, &cr4, &ia32_efer, memory_size);
+        // Switch to 5-level paging only if the boot configuration asks for it and the processor supports it.
+        let level5: bool = config.level5_paging() && supports_5_level_paging && cr4.la57_enableable();
+        serial_println!("level5 = {:?}", level5);
+        let mut paging = paging::State::new(&cr0, &cr3, &cr4, &ia32_efer, memory_size, level5, supports_1_gib_pages);
+        let highest_parallel_offset: usize = paging.highest_parallel_offset(memory_size);
+        serial_println!("highest_parallel_offset = {:#x?}", highest_parallel_offset);
         paging.map_highest_parallel(memory_size);
-        // Open the file system.
-        let simple_file_system = simple_file_system::SimpleFileSystem::new();
-        let elf: Vec<u8> = simple_file_system.read_file("/kernel.elf");
-        let elf = elf::Elf::new(&elf[..]);
+        timer.stop();
+        // Open the file system of the selected entry.
+        let timer = profile::Timer::start("ELF load");
+        let verifier = signature::Verifier::new(config.verify_signatures());
+        let (file_system, elf, command_line, module_paths): (file_system::FileSystem, Vec<u8>, String, &[String]) = match file_system::FileSystem::select(config.volume(), config.server())
+            .and_then(|file_system| file_system
+                .try_read_file(config.kernel())
+                .map(|elf| (file_system, elf))) {
+            // Only a missing kernel falls back. A kernel with a wrong signature never boots.
+            Some((file_system, elf)) => {
+                verifier.enforce(&file_system, config.kernel(), &elf);
+                (file_system, elf, config.command_line().into(), config.modules())
+            },
+            None => {
+                let fallback: &config::Entry = config
+                    .fallback()
+                    .expect("Can't read a kernel!");
+                serial_println!("Can't read {} from {:?}. Fall back to {}.", config.kernel(), config.volume(), fallback.name());
+                let file_system: file_system::FileSystem = file_system::FileSystem::select(fallback.volume(), fallback.server())
+                    .expect("Can't find a volume!");
+                let elf: Vec<u8> = file_system.read_file(fallback.kernel());
+                verifier.enforce(&file_system, fallback.kernel(), &elf);
+                (file_system, elf, fallback.command_line().into(), fallback.modules())
+            },
+        };
+        let elf = elf::Elf::new(&elf[..], random::generate(&cpuid));
+        timer.stop();
         let gdt: Vec<gdt::Descriptor> = gdt::Register::get().into();
-        serial_println!("old gdt = {:#x?}", gdt);
+        serial_verbose_println!("old gdt = {:#x?}", gdt);
         let gdt = gdt::Gdt::new();
-        serial_println!("new gdt = {:#x?}", gdt);
+        serial_verbose_println!("new gdt = {:#x?}", gdt);
+        let idt = idt::Idt::new(gdt.cs());
+        serial_verbose_println!("idt = {:#x?}", idt);
         let code_page_map: BTreeMap<usize, usize> = elf.page_map();
-        let stack_floor = highest_parallel_offset;
-        let stack = memory::Pages::new(0x10);
+        // A stack overflow or underflow causes a page fault at a guard page instead of corrupting the neighbors.
+        let stack_floor: usize = highest_parallel_offset - STACK_GUARD_PAGES * memory_allocation::PAGE_SIZE;
+        let stack = memory::Pages::new(config.stack_pages());
         let stack_pages: usize = stack.pages();
         let stack_page_map: BTreeMap<usize, usize> = stack
             .physical_addresses()
             .enumerate()
             .map(|(i, physical_address)| (physical_address, stack_floor - (stack_pages - i) * memory_allocation::PAGE_SIZE))
             .collect();
+        // Load modules and debug sections below the stack.
+        let module_files: Vec<(&str, Vec<u8>)> = module_paths
+            .iter()
+            .filter_map(|path| match file_system.try_read_file(path) {
+                Some(bytes) => {
+                    verifier.enforce(&file_system, path, &bytes);
+                    Some((path.as_str(), bytes))
+                },
+                None => {
+                    serial_println!("Can't read a module {}.", path);
+                    None
+                },
+            })
+            .collect();
+        let modules_ceiling: usize = stack_floor - (stack_pages + STACK_GUARD_PAGES) * memory_allocation::PAGE_SIZE;
+        let modules: Vec<module::Loaded> = module::Loaded::load_below(
+            module_files
+                .iter()
+                .map(|(name, bytes)| (*name, &bytes[..])),
+            modules_ceiling,
+        );
+        // The global allocator is unavailable after exiting boot services, so the modules and the debug sections are described here.
+        let passed_modules: Vec<module::Module> = modules
+            .iter()
+            .map(|module| module.into())
+            .collect();
+        let debug_sections_ceiling: usize = module::Loaded::floor(&modules, modules_ceiling);
+        let debug_sections: Vec<module::Loaded> = module::Loaded::load_below(
+            elf
+                .debug_sections()
+                .into_iter()
+                .map(|section| (section.name(), section.bytes())),
+            debug_sections_ceiling,
+        );
+        let passed_debug_sections: Vec<module::Module> = debug_sections
+            .iter()
+            .map(|debug_section| debug_section.into())
+            .collect();
+        let module_page_map: BTreeMap<usize, usize> = modules
+            .iter()
+            .map(|module| module.page_map())
+            .flatten()
+            .collect();
+        let debug_section_page_map: BTreeMap<usize, usize> = debug_sections
+            .iter()
+            .map(|debug_section| debug_section.page_map())
+            .flatten()
+            .collect();
         let stack_floor: &void::Void = stack_floor.into();
+        let timer = profile::Timer::start("page mapping");
         let mut page_map: BTreeMap<usize, usize> = BTreeMap::<usize, usize>::new();
         code_page_map
             .iter()
@@ -133,26 +265,79 @@ impl Kernel<'_> {
             .for_each(|(physical_address, virtual_address)| {
                 page_map.insert(*physical_address, *virtual_address);
             });
+        module_page_map
+            .iter()
+            .for_each(|(physical_address, virtual_address)| {
+                page_map.insert(*physical_address, *virtual_address);
+            });
+        debug_section_page_map
+            .iter()
+            .for_each(|(physical_address, virtual_address)| {
+                page_map.insert(*physical_address, *virtual_address);
+            });
         page_map
             .iter()
             .for_each(|(physical_address, virtual_address)| {
                 paging.divide_page(*virtual_address);
                 paging.set_physical_address(*virtual_address, *physical_address);
             });
-        code_page_map
-            .values()
-            .for_each(|virtual_address| paging.set_code_page(*virtual_address));
+        // Apply W^X permissions of the kernel programs.
+        elf
+            .page_permissions()
+            .into_iter()
+            .for_each(|(virtual_address, permission)| match permission {
+                elf::Permission::ReadOnly => paging.set_read_only_page(virtual_address),
+                elf::Permission::ReadWrite => paging.set_data_page(virtual_address),
+                elf::Permission::ReadExecute => paging.set_code_page(virtual_address),
+            });
         stack_page_map
             .values()
             .for_each(|virtual_address| paging.set_data_page(*virtual_address));
+        module_page_map
+            .values()
+            .for_each(|virtual_address| paging.set_data_page(*virtual_address));
+        debug_section_page_map
+            .values()
+            .for_each(|virtual_address| paging.set_read_only_page(*virtual_address));
+        timer.stop();
         // Get a graphic output protocol.
+        let timer = profile::Timer::start("GOP");
         let graphics_output: &graphics_output::GraphicsOutput = graphics_output::GraphicsOutput::new();
+        graphics_output
+            .modes()
+            .into_iter()
+            .for_each(|(mode_number, information)| serial_println!("video mode {}: {:x?}", mode_number, information));
+        // Prefer the resolution in the configuration, then the native resolution of the panel.
+        let native_resolution: Option<(u32, u32)> = edid_active::EdidActive::new().and_then(|edid_active| edid_active.native_resolution());
+        serial_println!("native_resolution = {:?}", native_resolution);
+        let resolution: Option<(u32, u32)> = config
+            .video_mode()
+            .into_iter()
+            .chain(native_resolution)
+            .find(|(horizontal_resolution, vertical_resolution)| if graphics_output.set_resolution(*horizontal_resolution, *vertical_resolution) {
+                true
+            } else {
+                serial_println!("Can't set a video mode {}x{}.", horizontal_resolution, vertical_resolution);
+                false
+            });
+        match resolution {
+            Some((horizontal_resolution, vertical_resolution)) => serial_println!("Set a video mode {}x{}.", horizontal_resolution, vertical_resolution),
+            None => serial_println!("Keep the video mode {}.", graphics_output.mode_number()),
+        }
+        serial_println!("video mode = {:x?}", graphics_output.information());
+        timer.stop();
         // Get a font.
-        let font = font::Font::new();
+        let timer = profile::Timer::start("font extraction");
+        let font = font::Font::new(config.font_ranges());
+        let font: boot_info::FontBitmaps = (&font).into();
+        timer.stop();
+        // Locate firmware tables for the kernel.
+        let firmware_tables = firmware_tables::FirmwareTables::locate(system::system());
         Self {
             elf,
             cpuid,
             gdt,
+            idt,
             memory_size,
             highest_parallel_offset,
             physical_page_present_bit_map,
@@ -160,13 +345,15 @@ impl Kernel<'_> {
             paging,
             stack,
             stack_floor,
-            cr0,
-            cr2,
-            cr3,
             cr4,
-            ia32_efer,
             graphics_output,
             font,
+            firmware_tables,
+            command_line,
+            modules,
+            passed_modules,
+            debug_sections,
+            passed_debug_sections,
         }
     }
 
@@ -174,7 +361,7 @@ impl Kernel<'_> {
         self,
         image: handle::Handle<'static>,
         system: &system::System,
-        memory_map: &memory_allocation::PassedMap,
+        memory_map: boot_info::MemoryMap,
         com1: &serial::Serial,
         com2: &serial::Serial,
     ) {
@@ -182,6 +369,7 @@ impl Kernel<'_> {
             elf,
             cpuid,
             gdt,
+            idt,
             memory_size,
             highest_parallel_offset,
             physical_page_present_bit_map,
@@ -189,52 +377,81 @@ impl Kernel<'_> {
             paging,
             stack,
             stack_floor,
-            cr0,
-            cr2,
-            cr3,
             cr4,
-            ia32_efer,
             graphics_output,
             font,
+            firmware_tables,
+            command_line,
+            // The modules and the debug sections are kept until the kernel runs.
+            modules: _modules,
+            passed_modules,
+            debug_sections: _debug_sections,
+            passed_debug_sections,
         } = self;
         let physical_page_present_bit_map: &[u8] = (&physical_page_present_bit_map).into();
-        let cr0: &control::register0::Cr0 = &cr0;
-        let cr2: &control::register2::Cr2 = &cr2;
-        let cr4: &control::register4::Cr4 = &cr4;
-        let ia32_efer: &Option<ia32_efer::Ia32Efer> = &ia32_efer;
-        let cr3: &control::register3::Cr3 = &control::register3::Cr3::set(paging.get_cr3());
-        let font: &font::Font = &font;
-        let kernel_arguments = elf::KernelArguments::new(
+        gdt.set();
+        // Faults from here on are reported instead of triple faulting.
+        idt.set(&paging);
+        if paging.level5() && !cr4.la57() {
+            control::register4::Cr4::enable_la57(paging.get_cr3(), gdt.compatibility_mode_cs(), gdt.cs());
+        } else {
+            control::register3::Cr3::set(paging.get_cr3());
+        }
+        control::register0::Cr0::enable_write_protect();
+        let stack_floor: usize = stack_floor.into();
+        let frame_buffer: boot_info::FrameBuffer = graphics_output.into();
+        let narrow_width: u16 = font.narrow_width();
+        let font: boot_info::Font = (&font).into();
+        let command_line: &str = &command_line;
+        let modules: &[module::Module] = &passed_modules;
+        let debug_sections: &[module::Module] = &passed_debug_sections;
+        let boot_info = boot_info::BootInfo::new(
             image,
             system,
             memory_size,
             highest_parallel_offset,
-            physical_page_present_bit_map,
             memory_map,
+            physical_page_present_bit_map,
             stack_floor,
-            cr0,
-            cr2,
-            cr3,
-            cr4,
-            ia32_efer,
+            frame_buffer,
             com1,
             com2,
-            graphics_output,
+            command_line,
+            modules,
+            debug_sections,
             font,
+            &firmware_tables,
+            profile::phases(),
+            serial::take_ring_buffer(),
+            narrow_width,
         );
-        gdt.set();
         serial_println!("Kernel.run()");
-        serial_println!("kernel.page_map = {:#x?}", &page_map);
-        page_map
-            .values()
-            .for_each(|virtual_address| paging.print_state_at_address(*virtual_address));
-        elf.run(kernel_arguments)
+        serial_verbose_println!("kernel.page_map = {:#x?}", &page_map);
+        if serial::verbosity() == serial::Verbosity::Verbose {
+            page_map
+                .values()
+                .for_each(|virtual_address| paging.print_state_at_address(*virtual_address));
+        }
+        elf.run(boot_info)
+    }
+}
+
+// The log is taken first so that a panic while saving it doesn't save it again.
+fn save_boot_log() {
+    if let Some(log) = serial::take_log() {
+        if simple_file_system::SimpleFileSystem::new()
+            .try_write_file(BOOT_LOG, log.as_bytes())
+            .is_none() {
+            serial_println!("Can't write {}.", BOOT_LOG);
+        }
     }
 }
 
 #[panic_handler]
 fn panic(panic: &PanicInfo) -> ! {
+    serial::set_verbosity(serial::Verbosity::Normal);
     serial_println!("{}", panic);
+    save_boot_log();
     loop {
         asm::hlt();
     }
@@ -24,11 +24,20 @@ pub struct Pages<'a> {
 
 impl Pages<'_> {
     pub fn new(pages: usize) -> Self {
-        let mut physical_address: memory_allocation::PhysicalAddress = 0;
+        let physical_address: memory_allocation::PhysicalAddress = 0;
+        Self::allocate(memory_allocation::AllocateType::AllocateAnyPages, pages, physical_address)
+    }
+
+    // Allocate pages whose physical addresses are not more than max_address.
+    pub fn new_below(pages: usize, max_address: memory_allocation::PhysicalAddress) -> Self {
+        Self::allocate(memory_allocation::AllocateType::AllocateMaxAddress, pages, max_address)
+    }
+
+    fn allocate(allocate_type: memory_allocation::AllocateType, pages: usize, mut physical_address: memory_allocation::PhysicalAddress) -> Self {
         system::system()
             .boot_services
             .allocate_pages(
-                memory_allocation::AllocateType::AllocateAnyPages,
+                allocate_type,
                 memory_allocation::MemoryType::LoaderData,
                 pages,
                 &mut physical_address,
@@ -2,6 +2,7 @@
 // Intel 64 and IA-32 Architectures Software Developer's Manual, Volume 3 System Programming Guide, Chapter 4 Paging
 
 pub mod level4;
+pub mod level5;
 
 use {
     crate::{
@@ -26,7 +27,9 @@ pub enum State<'a> {
     Level4 {
         cr3: level4::Cr3<'a>,
     },
-    Level5,
+    Level5 {
+        cr3: level5::Cr3<'a>,
+    },
 }
 
 impl State<'_> {
@@ -39,7 +42,11 @@ impl State<'_> {
                     .expect("Can't create a paging mode!")
                     .lme() {
                     if cr4.la57() {
-                        Self::Level5
+                        let cr3: u64 = cr3.into();
+                        let cr3: level5::Cr3 = cr3.into();
+                        Self::Level5 {
+                            cr3,
+                        }
                     } else {
                         let cr3: u64 = cr3.into();
                         let cr3: level4::Cr3 = cr3.into();
@@ -58,18 +65,24 @@ impl State<'_> {
         }
     }
 
-    pub fn new(cr0: &Cr0, cr3: &Cr3, cr4: &Cr4, ia32_efer: &Option<Ia32Efer>, memory_size: usize) -> Self {
+    // If level5 is true, 5-level paging is prepared even though the firmware runs with 4-level paging.
+    // If page_size_1_gib is true, physical memory is mapped with 1GiB pages.
+    pub fn new(cr0: &Cr0, cr3: &Cr3, cr4: &Cr4, ia32_efer: &Option<Ia32Efer>, memory_size: usize, level5: bool, page_size_1_gib: bool) -> Self {
         if cr0.pg() {
             if cr4.pae() {
                 if ia32_efer
                     .as_ref()
                     .expect("Can't create a paging mode!")
                     .lme() {
-                    if cr4.la57() {
-                        Self::Level5
+                    if cr4.la57() || level5 {
+                        let cr3: u64 = cr3.into();
+                        let cr3 = level5::Cr3::new(cr3, memory_size, page_size_1_gib);
+                        Self::Level5 {
+                            cr3,
+                        }
                     } else {
                         let cr3: u64 = cr3.into();
-                        let cr3 = level4::Cr3::new(cr3, memory_size);
+                        let cr3 = level4::Cr3::new(cr3, memory_size, page_size_1_gib);
                         Self::Level4 {
                             cr3,
                         }
@@ -90,10 +103,34 @@ impl State<'_> {
             Self::Level4 {
                 cr3,
             } => cr3.into(),
+            Self::Level5 {
+                cr3,
+            } => cr3.into(),
             _ => panic!("Can't get CR3!"),
         }
     }
 
+    pub fn highest_parallel_offset(&self, memory_size: usize) -> usize {
+        match self {
+            Self::Level4 {
+                cr3,
+            } => cr3.highest_parallel_offset(memory_size),
+            Self::Level5 {
+                cr3,
+            } => cr3.highest_parallel_offset(memory_size),
+            _ => usize::MAX - (memory_size - 1),
+        }
+    }
+
+    pub fn level5(&self) -> bool {
+        match self {
+            Self::Level5 {
+                cr3: _,
+            } => true,
+            _ => false,
+        }
+    }
+
     pub fn divide_page(&mut self, virtual_address: usize) {
         match self {
             Self::Disable => {
@@ -107,7 +144,10 @@ impl State<'_> {
             } => {
                 cr3.divide_child(virtual_address);
             },
-            Self::Level5 => {
+            Self::Level5 {
+                cr3,
+            } => {
+                cr3.divide_child(virtual_address);
             },
         }
     }
@@ -125,7 +165,10 @@ impl State<'_> {
             } => {
                 cr3.set_physical_address(virtual_address, physical_address);
             },
-            Self::Level5 => {
+            Self::Level5 {
+                cr3,
+            } => {
+                cr3.set_physical_address(virtual_address, physical_address);
             },
         }
     }
@@ -143,7 +186,10 @@ impl State<'_> {
             } => {
                 cr3.set_code_page(virtual_address);
             },
-            Self::Level5 => {
+            Self::Level5 {
+                cr3,
+            } => {
+                cr3.set_code_page(virtual_address);
             },
         }
     }
@@ -161,7 +207,31 @@ impl State<'_> {
             } => {
                 cr3.set_data_page(virtual_address);
             },
-            Self::Level5 => {
+            Self::Level5 {
+                cr3,
+            } => {
+                cr3.set_data_page(virtual_address);
+            },
+        }
+    }
+
+    pub fn set_read_only_page(&mut self, virtual_address: usize) {
+        match self {
+            Self::Disable => {
+            },
+            Self::Bit32 => {
+            },
+            Self::Pae => {
+            },
+            Self::Level4 {
+                cr3,
+            } => {
+                cr3.set_read_only_page(virtual_address);
+            },
+            Self::Level5 {
+                cr3,
+            } => {
+                cr3.set_read_only_page(virtual_address);
             },
         }
     }
@@ -179,7 +249,10 @@ impl State<'_> {
             } => {
                 cr3.map_highest_parallel(memory_size);
             },
-            Self::Level5 => {
+            Self::Level5 {
+                cr3,
+            } => {
+                cr3.map_highest_parallel(memory_size);
             },
         }
     }
@@ -199,7 +272,11 @@ impl State<'_> {
                 serial_println!("Paging level 4");
                 cr3.print_state_at_address(virtual_address);
             },
-            Self::Level5 => {
+            Self::Level5 {
+                cr3,
+            } => {
+                serial_println!("Paging level 5");
+                cr3.print_state_at_address(virtual_address);
             },
         }
         serial_println!("Paging state at address {:#x?} end.", virtual_address);
@@ -17,23 +17,48 @@ use {
     super::super::Pages,
 };
 
-fn cannonicalize(virtual_address: usize) -> usize {
-    if virtual_address & (1 << (PageMapLevel4Entry::INDEX_SHIFT_END - 1)) == 0 {
-        virtual_address & !(usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_END)
+// Virtual addresses are sign extended from the bit at index_shift_end - 1.
+pub fn cannonicalize(virtual_address: usize, index_shift_end: usize) -> usize {
+    if virtual_address & (1 << (index_shift_end - 1)) == 0 {
+        virtual_address & !(usize::MAX << index_shift_end)
     } else {
-        virtual_address | (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_END)
+        virtual_address | (usize::MAX << index_shift_end)
     }
 }
 
+// The highest parallel offset is aligned to the size of pages mapping it.
+pub fn highest_parallel_offset(memory_size: usize, page_size: PageSize) -> usize {
+    let page_size: usize = (&page_size).into();
+    (usize::MAX - (memory_size - 1)) & !(page_size - 1)
+}
+
+// Virtual addresses and physical addresses of the pages mapping the physical memory at the highest parallel offset.
+pub fn highest_parallel_pages(memory_size: usize, page_size: PageSize) -> BTreeMap<usize, usize> {
+    let highest_parallel_offset: usize = highest_parallel_offset(memory_size, page_size);
+    let page_size: usize = (&page_size).into();
+    let pages: usize = (memory_size + page_size - 1) / page_size;
+    let virtual_addresses: Vec<usize> = (0..pages)
+        .map(|page| highest_parallel_offset + page * page_size)
+        .collect();
+    let physical_addresses: Vec<usize> = (0..pages)
+        .map(|page| page * page_size)
+        .collect();
+    virtual_addresses
+        .into_iter()
+        .zip(physical_addresses.into_iter())
+        .collect()
+}
+
 #[allow(dead_code)]
 pub struct Cr3<'a> {
     pwt: bool,
     pcd: bool,
-    page_map_level_4_table_page: Option<Pages<'a>>,
-    page_map_level_4_entries: Vec<PageMapLevel4Entry<'a>>,
+    page_map_level_4_table: PageMapLevel4Table<'a>,
+    // Whether the processor supports 1GiB pages.
+    page_size_1_gib: bool,
 }
 
-const ENTRIES: usize = 0x200;
+pub const ENTRIES: usize = 0x200;
 
 impl Cr3<'_> {
     const PWT_SHIFT: usize = 3;
@@ -43,77 +68,176 @@ impl Cr3<'_> {
     const PCD_MASK: u64 = 1 << Self::PCD_SHIFT;
     const PAGE_DIRECTORY_BASE_MASK: u64 = 0xfffffffffffff000;
 
-    pub fn new(cr3: u64, memory_size: usize) -> Self {
+    pub fn new(cr3: u64, memory_size: usize, page_size_1_gib: bool) -> Self {
+        let pwt: bool = cr3 & Self::PWT_MASK != 0;
+        let pcd: bool = cr3 & Self::PCD_MASK != 0;
+        let virtual_address: usize = 0;
+        let page_map_level_4_table = PageMapLevel4Table::new(virtual_address, PageMapLevel4Entry::INDEX_SHIFT_END, memory_size, page_size_1_gib);
+        Self {
+            pwt,
+            pcd,
+            page_map_level_4_table,
+            page_size_1_gib,
+        }
+    }
+
+    pub fn divide_child(&mut self, virtual_address: usize) {
+        self.page_map_level_4_table.divide_child(virtual_address);
+    }
+
+    pub fn set_physical_address(&mut self, virtual_address: usize, physical_address: usize) {
+        self.page_map_level_4_table.set_physical_address(virtual_address, physical_address);
+    }
+
+    pub fn set_code_page(&mut self, virtual_address: usize) {
+        self.page_map_level_4_table.set_code_page(virtual_address);
+    }
+
+    pub fn set_data_page(&mut self, virtual_address: usize) {
+        self.page_map_level_4_table.set_data_page(virtual_address);
+    }
+
+    pub fn set_read_only_page(&mut self, virtual_address: usize) {
+        self.page_map_level_4_table.set_read_only_page(virtual_address);
+    }
+
+    pub fn highest_parallel_offset(&self, memory_size: usize) -> usize {
+        let page_size: PageSize = PageSize::highest_parallel(self.page_size_1_gib);
+        highest_parallel_offset(memory_size, page_size)
+    }
+
+    pub fn map_highest_parallel(&mut self, memory_size: usize) {
+        let page_size: PageSize = PageSize::highest_parallel(self.page_size_1_gib);
+        highest_parallel_pages(memory_size, page_size)
+            .into_iter()
+            .for_each(|(virtual_address, physical_address)| self.page_map_level_4_table.map_page(virtual_address, physical_address, page_size));
+    }
+
+    pub fn print_state_at_address(&self, virtual_address: usize) {
+        serial_println!("cr3.pwt = {:#x?}", &self.pwt);
+        serial_println!("cr3.pcd = {:#x?}", &self.pcd);
+        self.page_map_level_4_table.print_state_at_address(virtual_address);
+    }
+}
+
+impl fmt::Debug for Cr3<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Cr3")
+            .field("pwt", &self.pwt)
+            .field("pcd", &self.pcd)
+            .field("page_map_level_4_table", &self.page_map_level_4_table)
+            .field("page_size_1_gib", &self.page_size_1_gib)
+            .finish()
+    }
+}
+
+impl From<u64> for Cr3<'_> {
+	fn from(cr3: u64) -> Self {
         let pwt: bool = cr3 & Self::PWT_MASK != 0;
         let pcd: bool = cr3 & Self::PCD_MASK != 0;
+        let page_map_level_4_table: u64 = cr3 & Self::PAGE_DIRECTORY_BASE_MASK;
+        let virtual_address: usize = 0;
+        let page_map_level_4_table = PageMapLevel4Table::read(page_map_level_4_table, virtual_address, PageMapLevel4Entry::INDEX_SHIFT_END);
+        let page_size_1_gib: bool = false;
+        Self {
+            pwt,
+            pcd,
+            page_map_level_4_table,
+            page_size_1_gib,
+        }
+	}
+}
+
+impl Into<u64> for &Cr3<'_> {
+    fn into(self) -> u64 {
+        let pwt: u64 = if self.pwt {
+            Cr3::PWT_MASK
+        } else {
+            0
+        };
+        let pcd: u64 = if self.pcd {
+            Cr3::PCD_MASK
+        } else {
+            0
+        };
+        let page_map_level_4_table: u64 = self.page_map_level_4_table.address();
+        pwt | pcd | page_map_level_4_table
+    }
+}
+
+// A PML4 table maps the whole virtual address space with 4-level paging or the virtual addresses of a PML5 entry with 5-level paging.
+#[allow(dead_code)]
+pub struct PageMapLevel4Table<'a> {
+    page_map_level_4_table_page: Option<Pages<'a>>,
+    page_map_level_4_table: u64,
+    page_map_level_4_entries: Vec<PageMapLevel4Entry<'a>>,
+}
+
+impl PageMapLevel4Table<'_> {
+    // The PML4 entries map virtual addresses from virtual_address sign extended from the bit at index_shift_end - 1.
+    pub fn new(virtual_address: usize, index_shift_end: usize, memory_size: usize, page_size_1_gib: bool) -> Self {
         let mut page_map_level_4_table_page: Option<Pages> = Some(Pages::new(1));
-        let page_map_level_4_table: &mut [u8] = page_map_level_4_table_page
+        let page_map_level_4_table: u64 = page_map_level_4_table_page
+            .as_ref()
+            .expect("Can't create a new PML4 table!")
+            .physical_address();
+        let page_map_level_4_table_bytes: &mut [u8] = page_map_level_4_table_page
             .as_mut()
-            .expect("Can't create a new CR3 structure!")
+            .expect("Can't create a new PML4 table!")
             .bytes();
-        let page_map_level_4_table_len: usize = page_map_level_4_table.len();
-        let page_map_level_4_table: *mut u8 = page_map_level_4_table.as_mut_ptr();
-        let page_map_level_4_table: *mut u64 = page_map_level_4_table as *mut u64;
+        let page_map_level_4_table_len: usize = page_map_level_4_table_bytes.len();
+        let page_map_level_4_table_bytes: *mut u8 = page_map_level_4_table_bytes.as_mut_ptr();
+        let page_map_level_4_table_bytes: *mut u64 = page_map_level_4_table_bytes as *mut u64;
         let page_map_level_4_table_len: usize = page_map_level_4_table_len / 8;
-        let page_map_level_4_table: &mut [u64] = unsafe {
-            slice::from_raw_parts_mut(page_map_level_4_table, page_map_level_4_table_len)
+        let page_map_level_4_table_bytes: &mut [u64] = unsafe {
+            slice::from_raw_parts_mut(page_map_level_4_table_bytes, page_map_level_4_table_len)
         };
-        let page_map_level_4_entries: Vec<PageMapLevel4Entry> = page_map_level_4_table
+        let page_map_level_4_entries: Vec<PageMapLevel4Entry> = page_map_level_4_table_bytes
             .into_iter()
             .enumerate()
-            .map(|(index, page_map_level_4_entry)| (cannonicalize(index << PageMapLevel4Entry::INDEX_SHIFT_BEGIN), page_map_level_4_entry))
-            .map(|(virtual_address, page_map_level_4_entry)| PageMapLevel4Entry::new(virtual_address, page_map_level_4_entry, memory_size))
+            .map(|(index, page_map_level_4_entry)| (cannonicalize(virtual_address + (index << PageMapLevel4Entry::INDEX_SHIFT_BEGIN), index_shift_end), page_map_level_4_entry))
+            .map(|(virtual_address, page_map_level_4_entry)| PageMapLevel4Entry::new(virtual_address, page_map_level_4_entry, memory_size, page_size_1_gib))
             .collect();
         Self {
-            pwt,
-            pcd,
             page_map_level_4_table_page,
+            page_map_level_4_table,
             page_map_level_4_entries,
         }
     }
 
-    pub fn divide_child(&mut self, virtual_address: usize) {
-        self.page_map_level_4_entries
-            .iter_mut()
-            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
-            .expect("Can't divide a page!")
-            .divide_child(virtual_address);
-    }
-
-    pub fn set_physical_address(&mut self, virtual_address: usize, physical_address: usize) {
-        self.page_map_level_4_entries
+    pub fn read(page_map_level_4_table: u64, virtual_address: usize, index_shift_end: usize) -> Self {
+        let page_map_level_4_table_page: Option<Pages> = None;
+        let page_map_level_4_table_entries: *mut [u64; ENTRIES] = page_map_level_4_table as *mut [u64; ENTRIES];
+        let page_map_level_4_table_entries: &mut [u64; ENTRIES] = unsafe {
+            &mut *page_map_level_4_table_entries
+        };
+        let page_map_level_4_entries: Vec<PageMapLevel4Entry> = page_map_level_4_table_entries
             .iter_mut()
-            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
-            .expect("Can't set a physical address!")
-            .set_physical_address(virtual_address, physical_address);
+            .enumerate()
+            .filter_map(|(i, page_map_level_4_entry)| PageMapLevel4Entry::read(cannonicalize(virtual_address + (i << PageMapLevel4Entry::INDEX_SHIFT_BEGIN), index_shift_end), page_map_level_4_entry))
+            .collect();
+        Self {
+            page_map_level_4_table_page,
+            page_map_level_4_table,
+            page_map_level_4_entries,
+        }
     }
 
-    pub fn set_code_page(&mut self, virtual_address: usize) {
-        self.page_map_level_4_entries
-            .iter_mut()
-            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
-            .expect("Can't set a code page!")
-            .set_code_page(virtual_address);
+    pub fn address(&self) -> u64 {
+        self.page_map_level_4_table
     }
 
-    pub fn set_data_page(&mut self, virtual_address: usize) {
+    pub fn divide_child(&mut self, virtual_address: usize) {
         self.page_map_level_4_entries
             .iter_mut()
             .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
-            .expect("Can't set a data page!")
-            .set_data_page(virtual_address);
+            .expect("Can't divide a page!")
+            .divide_child(virtual_address);
     }
 
-    pub fn map_highest_parallel(&mut self, memory_size: usize) {
-        let page_size = PageSize::PageSize2MiB;
-        let page_size_usize: usize = (&page_size).into();
-        let pages: usize = (memory_size + page_size_usize - 1) / page_size_usize;
-        let virtual_addresses: Vec<usize> = (0..pages)
-            .map(|page| usize::MAX - (memory_size - 1) + page * page_size_usize)
-            .collect();
-        let physical_addresses: Vec<usize> = (0..pages)
-            .map(|page| page * page_size_usize)
-            .collect();
+    // Map a writable page of the kernel.
+    pub fn map_page(&mut self, virtual_address: usize, physical_address: usize, page_size: PageSize) {
         let writable: bool = true;
         let user_mode_access: bool = false;
         let page_write_through: bool = false;
@@ -123,41 +247,6 @@ impl Cr3<'_> {
         let restart: bool = false;
         let protection_key: u8 = 0;
         let execute_disable: bool = false;
-        let virtual_address2physical_address: BTreeMap<usize, usize> = virtual_addresses
-            .into_iter()
-            .zip(physical_addresses.into_iter())
-            .collect();
-        virtual_address2physical_address
-            .into_iter()
-            .for_each(|(virtual_address, physical_address)| self.set_page(
-                virtual_address,
-                physical_address,
-                page_size,
-                writable,
-                user_mode_access,
-                page_write_through,
-                page_cache_disable,
-                page_attribute_table,
-                global,
-                restart,
-                protection_key,
-                execute_disable));
-    }
-
-    fn set_page(
-        &mut self, 
-        virtual_address: usize,
-        physical_address: usize,
-        page_size: PageSize,
-        writable: bool,
-        user_mode_access: bool,
-        page_write_through: bool,
-        page_cache_disable: bool,
-        page_attribute_table: bool,
-        global: bool,
-        restart: bool,
-        protection_key: u8,
-        execute_disable: bool) {
         self.page_map_level_4_entries
             .iter_mut()
             .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
@@ -178,67 +267,53 @@ impl Cr3<'_> {
     }
 
     pub fn print_state_at_address(&self, virtual_address: usize) {
-        serial_println!("cr3.pwt = {:#x?}", &self.pwt);
-        serial_println!("cr3.pcd = {:#x?}", &self.pcd);
         if let Some(page_map_level_4_entry) = self.page_map_level_4_entries
             .iter()
             .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN)) {
             page_map_level_4_entry.print_state_at_address(virtual_address);
         }
     }
-}
 
-impl fmt::Debug for Cr3<'_> {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter
-            .debug_struct("Cr3")
-            .field("pwt", &self.pwt)
-            .field("pcd", &self.pcd)
-            .field("page_map_level_4_entries", &self.page_map_level_4_entries)
-            .finish()
+    pub fn set_code_page(&mut self, virtual_address: usize) {
+        self.page_map_level_4_entries
+            .iter_mut()
+            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a code page!")
+            .set_code_page(virtual_address);
     }
-}
 
-impl From<u64> for Cr3<'_> {
-	fn from(cr3: u64) -> Self {
-        let pwt: bool = cr3 & Self::PWT_MASK != 0;
-        let pcd: bool = cr3 & Self::PCD_MASK != 0;
-        let page_map_level_4_table_page: Option<Pages> = None;
-        let page_map_level_4_table: u64 = cr3 & Self::PAGE_DIRECTORY_BASE_MASK;
-        let page_map_level_4_table: *mut [u64; ENTRIES] = page_map_level_4_table as *mut [u64; ENTRIES];
-        let page_map_level_4_table: &mut [u64; ENTRIES] = unsafe {
-            &mut *page_map_level_4_table
-        };
-        let page_map_level_4_entries: Vec<PageMapLevel4Entry> = page_map_level_4_table
+    pub fn set_data_page(&mut self, virtual_address: usize) {
+        self.page_map_level_4_entries
             .iter_mut()
-            .enumerate()
-            .filter_map(|(i, page_map_level_4_entry)| PageMapLevel4Entry::read(cannonicalize(i << PageMapLevel4Entry::INDEX_SHIFT_BEGIN), page_map_level_4_entry))
-            .collect();
-        Self {
-            pwt,
-            pcd,
-            page_map_level_4_table_page,
-            page_map_level_4_entries,
-        }
-	}
+            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a data page!")
+            .set_data_page(virtual_address);
+    }
+
+    pub fn set_physical_address(&mut self, virtual_address: usize, physical_address: usize) {
+        self.page_map_level_4_entries
+            .iter_mut()
+            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a physical address!")
+            .set_physical_address(virtual_address, physical_address);
+    }
+
+    pub fn set_read_only_page(&mut self, virtual_address: usize) {
+        self.page_map_level_4_entries
+            .iter_mut()
+            .find(|page_map_level_4_entry| page_map_level_4_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel4Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a read only page!")
+            .set_read_only_page(virtual_address);
+    }
 }
 
-impl Into<u64> for &Cr3<'_> {
-    fn into(self) -> u64 {
-        let pwt: u64 = if self.pwt {
-            Cr3::PWT_MASK
-        } else {
-            0
-        };
-        let pcd: u64 = if self.pcd {
-            Cr3::PCD_MASK
-        } else {
-            0
-        };
-        let page_map_level_4_table: &u64 = self.page_map_level_4_entries[0].page_map_level_4_entry;
-        let page_map_level_4_table: *const u64 = page_map_level_4_table as *const u64;
-        let page_map_level_4_table: u64 = page_map_level_4_table as u64;
-        pwt | pcd | page_map_level_4_table
+impl fmt::Debug for PageMapLevel4Table<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("PageMapLevel4Table")
+            .field("page_map_level_4_table", &self.page_map_level_4_table)
+            .field("page_map_level_4_entries", &self.page_map_level_4_entries)
+            .finish()
     }
 }
 
@@ -283,7 +358,8 @@ impl<'a> PageMapLevel4Entry<'a> {
     const INDEX_SHIFT_BEGIN: usize = 39;
     const INDEX_SHIFT_END: usize = 48;
 
-    fn new(virtual_address: usize, page_map_level_4_entry: &'a mut u64, memory_size: usize) -> Self {
+    // If page_size_1_gib is false, the identity mapping is divided into 2MiB pages.
+    fn new(virtual_address: usize, page_map_level_4_entry: &'a mut u64, memory_size: usize, page_size_1_gib: bool) -> Self {
         let present: bool = virtual_address < memory_size;
         let writable: bool = true;
         let user_mode_access: bool = false;
@@ -326,14 +402,14 @@ impl<'a> PageMapLevel4Entry<'a> {
             Some(page_directory_pointer_table) => page_directory_pointer_table
                 .into_iter()
                 .enumerate()
-                .map(|(index, page_directory_pointer_entry)| (cannonicalize(virtual_address + (index << PageDirectoryPointerEntry::INDEX_SHIFT_BEGIN)), page_directory_pointer_entry))
+                .map(|(index, page_directory_pointer_entry)| (virtual_address + (index << PageDirectoryPointerEntry::INDEX_SHIFT_BEGIN), page_directory_pointer_entry))
                 .map(|(virtual_address, page_directory_pointer_entry)| PageDirectoryPointerEntry::new(virtual_address, page_directory_pointer_entry, memory_size))
                 .collect(),
             None => Vec::<PageDirectoryPointerEntry>::new(),
         };
         page_directory_pointer_entries
             .iter_mut()
-            .filter(|page_directory_pointer_entry| page_directory_pointer_entry.present)
+            .filter(|page_directory_pointer_entry| page_directory_pointer_entry.present && !page_size_1_gib)
             .for_each(|page_directory_pointer_entry| page_directory_pointer_entry.divide());
         let present_in_entry: u64 = if present {
             Self::PRESENT_MASK
@@ -480,7 +556,7 @@ impl<'a> PageMapLevel4Entry<'a> {
                 Some(page_directory_pointer_table) => page_directory_pointer_table
                     .into_iter()
                     .enumerate()
-                    .map(|(index, page_directory_pointer_entry)| (cannonicalize(self.virtual_address + (index << PageDirectoryPointerEntry::INDEX_SHIFT_BEGIN)), page_directory_pointer_entry))
+                    .map(|(index, page_directory_pointer_entry)| (self.virtual_address + (index << PageDirectoryPointerEntry::INDEX_SHIFT_BEGIN), page_directory_pointer_entry))
                     .map(|(virtual_address, page_directory_pointer_entry)| PageDirectoryPointerEntry::add(virtual_address, page_directory_pointer_entry))
                     .collect(),
                 None => Vec::<PageDirectoryPointerEntry>::new(),
@@ -596,6 +672,15 @@ impl<'a> PageMapLevel4Entry<'a> {
             .set_data_page(virtual_address);
     }
 
+    fn set_read_only_page(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        self.page_directory_pointer_entries
+            .iter_mut()
+            .find(|page_directory_pointer_entry| page_directory_pointer_entry.virtual_address == virtual_address & (usize::MAX << PageDirectoryPointerEntry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a read only page!")
+            .set_read_only_page(virtual_address);
+    }
+
     fn set_page(
         &mut self, 
         virtual_address: usize,
@@ -1110,7 +1195,7 @@ impl<'a> PageDirectoryPointerEntry<'a> {
                     .iter_mut()
                     .enumerate()
                     .map(|(i, page_directory_entry)| PageDirectoryEntry::new(
-                        cannonicalize(self.virtual_address + (i << PageDirectoryEntry::INDEX_SHIFT_BEGIN)),
+                        self.virtual_address + (i << PageDirectoryEntry::INDEX_SHIFT_BEGIN),
                         page_directory_entry,
                         self.writable,
                         self.user_mode_access,
@@ -1237,9 +1322,8 @@ impl<'a> PageDirectoryPointerEntry<'a> {
         assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
         if self.page_size_1_gib {
             self.writable = true;
-            self.execute_disable = false;
-            *self.page_directory_pointer_entry |= Self::WRITABLE_MASK;
-            *self.page_directory_pointer_entry &= !Self::EXECUTE_DISABLE_MASK;
+            self.execute_disable = true;
+            *self.page_directory_pointer_entry |= Self::WRITABLE_MASK | Self::EXECUTE_DISABLE_MASK;
         } else {
             self.page_directory_entries
                 .as_mut()
@@ -1251,6 +1335,24 @@ impl<'a> PageDirectoryPointerEntry<'a> {
         }
     }
 
+    fn set_read_only_page(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        if self.page_size_1_gib {
+            self.writable = false;
+            self.execute_disable = true;
+            *self.page_directory_pointer_entry &= !Self::WRITABLE_MASK;
+            *self.page_directory_pointer_entry |= Self::EXECUTE_DISABLE_MASK;
+        } else {
+            self.page_directory_entries
+                .as_mut()
+                .expect("Can't set a read only page!")
+                .iter_mut()
+                .find(|page_directory_entry| page_directory_entry.virtual_address == virtual_address & (usize::MAX << PageDirectoryEntry::INDEX_SHIFT_BEGIN))
+                .expect("Can't set a read only page!")
+                .set_read_only_page(virtual_address);
+        }
+    }
+
     fn set_page(
         &mut self, 
         virtual_address: usize,
@@ -1710,7 +1812,7 @@ impl<'a> PageDirectoryEntry<'a> {
                     .iter_mut()
                     .enumerate()
                     .map(|(i, page_entry)| PageEntry::new(
-                        cannonicalize(self.virtual_address + (i << PageEntry::INDEX_SHIFT_BEGIN)),
+                        self.virtual_address + (i << PageEntry::INDEX_SHIFT_BEGIN),
                         page_entry,
                         self.writable,
                         self.user_mode_access,
@@ -1819,9 +1921,8 @@ impl<'a> PageDirectoryEntry<'a> {
         assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
         if self.page_size_2_mib {
             self.writable = true;
-            self.execute_disable = false;
-            *self.page_directory_entry |= Self::WRITABLE_MASK;
-            *self.page_directory_entry &= !Self::EXECUTE_DISABLE_MASK;
+            self.execute_disable = true;
+            *self.page_directory_entry |= Self::WRITABLE_MASK | Self::EXECUTE_DISABLE_MASK;
         } else {
             self.page_entries
                 .as_mut()
@@ -1833,6 +1934,24 @@ impl<'a> PageDirectoryEntry<'a> {
         }
     }
 
+    fn set_read_only_page(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        if self.page_size_2_mib {
+            self.writable = false;
+            self.execute_disable = true;
+            *self.page_directory_entry &= !Self::WRITABLE_MASK;
+            *self.page_directory_entry |= Self::EXECUTE_DISABLE_MASK;
+        } else {
+            self.page_entries
+                .as_mut()
+                .expect("Can't set a read only page!")
+                .iter_mut()
+                .find(|page_entry| page_entry.virtual_address == virtual_address)
+                .expect("Can't set a read only page!")
+                .set_read_only_page();
+        }
+    }
+
     fn set_page(
         &mut self, 
         virtual_address: usize,
@@ -2209,17 +2328,22 @@ impl<'a> PageEntry<'a> {
     }
 
     fn set_code_page(&mut self) {
-        self.writable = true;
+        self.writable = false;
         self.execute_disable = false;
-        *self.page_entry |= Self::WRITABLE_MASK;
-        *self.page_entry &= !Self::EXECUTE_DISABLE_MASK;
+        *self.page_entry &= !(Self::WRITABLE_MASK | Self::EXECUTE_DISABLE_MASK);
     }
 
     fn set_data_page(&mut self) {
         self.writable = true;
         self.execute_disable = true;
-        *self.page_entry |= Self::WRITABLE_MASK;
-        *self.page_entry &= !Self::EXECUTE_DISABLE_MASK;
+        *self.page_entry |= Self::WRITABLE_MASK | Self::EXECUTE_DISABLE_MASK;
+    }
+
+    fn set_read_only_page(&mut self) {
+        self.writable = false;
+        self.execute_disable = true;
+        *self.page_entry &= !Self::WRITABLE_MASK;
+        *self.page_entry |= Self::EXECUTE_DISABLE_MASK;
     }
 
     fn set_page(
@@ -2358,12 +2482,22 @@ impl fmt::Debug for PageEntry<'_> {
 }
 
 #[derive(Clone, Copy, Debug)]
-enum PageSize {
+pub enum PageSize {
     PageSize4KiB,
     PageSize2MiB,
     PageSize1GiB,
 }
 
+impl PageSize {
+    pub fn highest_parallel(page_size_1_gib: bool) -> Self {
+        if page_size_1_gib {
+            Self::PageSize1GiB
+        } else {
+            Self::PageSize2MiB
+        }
+    }
+}
+
 impl From<usize> for PageSize {
     fn from(page_size: usize) -> PageSize {
         match page_size {
@@ -0,0 +1,518 @@
+// References
+// Intel 64 and IA-32 Architectures Software Developer's Manual, Volume 3 System Programming Guide, Chapter 4 Paging, Section 5 4-Level Paging And 5-Level Paging
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    alloc::vec::Vec,
+    core::{
+        slice,
+        fmt,
+    },
+    super::{
+        level4::{
+            ENTRIES,
+            PageMapLevel4Table,
+            PageSize,
+            cannonicalize,
+            highest_parallel_offset,
+            highest_parallel_pages,
+        },
+        super::Pages,
+    },
+};
+
+#[allow(dead_code)]
+pub struct Cr3<'a> {
+    pwt: bool,
+    pcd: bool,
+    page_map_level_5_table_page: Option<Pages<'a>>,
+    page_map_level_5_entries: Vec<PageMapLevel5Entry<'a>>,
+    // Whether the processor supports 1GiB pages.
+    page_size_1_gib: bool,
+}
+
+impl Cr3<'_> {
+    const PWT_SHIFT: usize = 3;
+    const PCD_SHIFT: usize = 4;
+
+    const PWT_MASK: u64 = 1 << Self::PWT_SHIFT;
+    const PCD_MASK: u64 = 1 << Self::PCD_SHIFT;
+    const PAGE_DIRECTORY_BASE_MASK: u64 = 0xfffffffffffff000;
+
+    // The PML5 table is loaded to CR3 in compatibility mode when the bootloader enables 5-level paging by itself.
+    const PAGE_MAP_LEVEL_5_TABLE_MAX_ADDRESS: u64 = 0x00000000ffffffff;
+
+    pub fn new(cr3: u64, memory_size: usize, page_size_1_gib: bool) -> Self {
+        let pwt: bool = cr3 & Self::PWT_MASK != 0;
+        let pcd: bool = cr3 & Self::PCD_MASK != 0;
+        let mut page_map_level_5_table_page: Option<Pages> = Some(Pages::new_below(1, Self::PAGE_MAP_LEVEL_5_TABLE_MAX_ADDRESS));
+        let page_map_level_5_table: &mut [u8] = page_map_level_5_table_page
+            .as_mut()
+            .expect("Can't create a new CR3 structure!")
+            .bytes();
+        let page_map_level_5_table_len: usize = page_map_level_5_table.len();
+        let page_map_level_5_table: *mut u8 = page_map_level_5_table.as_mut_ptr();
+        let page_map_level_5_table: *mut u64 = page_map_level_5_table as *mut u64;
+        let page_map_level_5_table_len: usize = page_map_level_5_table_len / 8;
+        let page_map_level_5_table: &mut [u64] = unsafe {
+            slice::from_raw_parts_mut(page_map_level_5_table, page_map_level_5_table_len)
+        };
+        let page_map_level_5_entries: Vec<PageMapLevel5Entry> = page_map_level_5_table
+            .into_iter()
+            .enumerate()
+            .map(|(index, page_map_level_5_entry)| (cannonicalize(index << PageMapLevel5Entry::INDEX_SHIFT_BEGIN, PageMapLevel5Entry::INDEX_SHIFT_END), page_map_level_5_entry))
+            .map(|(virtual_address, page_map_level_5_entry)| PageMapLevel5Entry::new(virtual_address, page_map_level_5_entry, memory_size, page_size_1_gib))
+            .collect();
+        Self {
+            pwt,
+            pcd,
+            page_map_level_5_table_page,
+            page_map_level_5_entries,
+            page_size_1_gib,
+        }
+    }
+
+    pub fn divide_child(&mut self, virtual_address: usize) {
+        self.page_map_level_5_entries
+            .iter_mut()
+            .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't divide a page!")
+            .divide_child(virtual_address);
+    }
+
+    pub fn set_physical_address(&mut self, virtual_address: usize, physical_address: usize) {
+        self.page_map_level_5_entries
+            .iter_mut()
+            .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a physical address!")
+            .set_physical_address(virtual_address, physical_address);
+    }
+
+    pub fn set_code_page(&mut self, virtual_address: usize) {
+        self.page_map_level_5_entries
+            .iter_mut()
+            .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a code page!")
+            .set_code_page(virtual_address);
+    }
+
+    pub fn set_data_page(&mut self, virtual_address: usize) {
+        self.page_map_level_5_entries
+            .iter_mut()
+            .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a data page!")
+            .set_data_page(virtual_address);
+    }
+
+    pub fn set_read_only_page(&mut self, virtual_address: usize) {
+        self.page_map_level_5_entries
+            .iter_mut()
+            .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN))
+            .expect("Can't set a read only page!")
+            .set_read_only_page(virtual_address);
+    }
+
+    pub fn highest_parallel_offset(&self, memory_size: usize) -> usize {
+        let page_size: PageSize = PageSize::highest_parallel(self.page_size_1_gib);
+        highest_parallel_offset(memory_size, page_size)
+    }
+
+    pub fn map_highest_parallel(&mut self, memory_size: usize) {
+        let page_size: PageSize = PageSize::highest_parallel(self.page_size_1_gib);
+        highest_parallel_pages(memory_size, page_size)
+            .into_iter()
+            .for_each(|(virtual_address, physical_address)| self.page_map_level_5_entries
+                .iter_mut()
+                .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN))
+                .expect("Can't set a page!")
+                .map_page(virtual_address, physical_address, page_size));
+    }
+
+    pub fn print_state_at_address(&self, virtual_address: usize) {
+        serial_println!("cr3.pwt = {:#x?}", &self.pwt);
+        serial_println!("cr3.pcd = {:#x?}", &self.pcd);
+        if let Some(page_map_level_5_entry) = self.page_map_level_5_entries
+            .iter()
+            .find(|page_map_level_5_entry| page_map_level_5_entry.virtual_address == virtual_address & (usize::MAX << PageMapLevel5Entry::INDEX_SHIFT_BEGIN)) {
+            page_map_level_5_entry.print_state_at_address(virtual_address);
+        }
+    }
+}
+
+impl fmt::Debug for Cr3<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Cr3")
+            .field("pwt", &self.pwt)
+            .field("pcd", &self.pcd)
+            .field("page_map_level_5_entries", &self.page_map_level_5_entries)
+            .field("page_size_1_gib", &self.page_size_1_gib)
+            .finish()
+    }
+}
+
+impl From<u64> for Cr3<'_> {
+    fn from(cr3: u64) -> Self {
+        let pwt: bool = cr3 & Self::PWT_MASK != 0;
+        let pcd: bool = cr3 & Self::PCD_MASK != 0;
+        let page_map_level_5_table_page: Option<Pages> = None;
+        let page_map_level_5_table: u64 = cr3 & Self::PAGE_DIRECTORY_BASE_MASK;
+        let page_map_level_5_table: *mut [u64; ENTRIES] = page_map_level_5_table as *mut [u64; ENTRIES];
+        let page_map_level_5_table: &mut [u64; ENTRIES] = unsafe {
+            &mut *page_map_level_5_table
+        };
+        let page_map_level_5_entries: Vec<PageMapLevel5Entry> = page_map_level_5_table
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, page_map_level_5_entry)| PageMapLevel5Entry::read(cannonicalize(i << PageMapLevel5Entry::INDEX_SHIFT_BEGIN, PageMapLevel5Entry::INDEX_SHIFT_END), page_map_level_5_entry))
+            .collect();
+        let page_size_1_gib: bool = false;
+        Self {
+            pwt,
+            pcd,
+            page_map_level_5_table_page,
+            page_map_level_5_entries,
+            page_size_1_gib,
+        }
+    }
+}
+
+impl Into<u64> for &Cr3<'_> {
+    fn into(self) -> u64 {
+        let pwt: u64 = if self.pwt {
+            Cr3::PWT_MASK
+        } else {
+            0
+        };
+        let pcd: u64 = if self.pcd {
+            Cr3::PCD_MASK
+        } else {
+            0
+        };
+        let page_map_level_5_table: &u64 = self.page_map_level_5_entries[0].page_map_level_5_entry;
+        let page_map_level_5_table: *const u64 = page_map_level_5_table as *const u64;
+        let page_map_level_5_table: u64 = page_map_level_5_table as u64;
+        pwt | pcd | page_map_level_5_table
+    }
+}
+
+#[allow(dead_code)]
+struct PageMapLevel5Entry<'a> {
+    present: bool,
+    virtual_address: usize,
+    page_map_level_5_entry: &'a mut u64,
+    writable: bool,
+    user_mode_access: bool,
+    page_write_through: bool,
+    page_cache_disable: bool,
+    accessed: bool,
+    restart: bool,
+    page_map_level_4_table: Option<PageMapLevel4Table<'a>>,
+    execute_disable: bool,
+}
+
+impl<'a> PageMapLevel5Entry<'a> {
+    const PRESENT_SHIFT: usize = 0;
+    const WRITABLE_SHIFT: usize = 1;
+    const USER_MODE_ACCESS_SHIFT: usize = 2;
+    const PAGE_WRITE_THROUGH_SHIFT: usize = 3;
+    const PAGE_CACHE_DISABLE_SHIFT: usize = 4;
+    const ACCESSED_SHIFT: usize = 5;
+    const RESTART_SHIFT: usize = 11;
+    const PAGE_MAP_LEVEL_4_TABLE_SHIFT_BEGIN: usize = 12;
+    const PAGE_MAP_LEVEL_4_TABLE_SHIFT_END: usize = 52;
+    const EXECUTE_DISABLE_SHIFT: usize = 63;
+
+    const PRESENT_MASK: u64 = 1 << Self::PRESENT_SHIFT;
+    const WRITABLE_MASK: u64 = 1 << Self::WRITABLE_SHIFT;
+    const USER_MODE_ACCESS_MASK: u64 = 1 << Self::USER_MODE_ACCESS_SHIFT;
+    const PAGE_WRITE_THROUGH_MASK: u64 = 1 << Self::PAGE_WRITE_THROUGH_SHIFT;
+    const PAGE_CACHE_DISABLE_MASK: u64 = 1 << Self::PAGE_CACHE_DISABLE_SHIFT;
+    const ACCESSED_MASK: u64 = 1 << Self::ACCESSED_SHIFT;
+    const RESTART_MASK: u64 = 1 << Self::RESTART_SHIFT;
+    const PAGE_MAP_LEVEL_4_TABLE_MASK: u64 = (1 << Self::PAGE_MAP_LEVEL_4_TABLE_SHIFT_END) - (1 << Self::PAGE_MAP_LEVEL_4_TABLE_SHIFT_BEGIN);
+    const EXECUTE_DISABLE_MASK: u64 = 1 << Self::EXECUTE_DISABLE_SHIFT;
+
+    const INDEX_SHIFT_BEGIN: usize = 48;
+    const INDEX_SHIFT_END: usize = 57;
+
+    fn new(virtual_address: usize, page_map_level_5_entry: &'a mut u64, memory_size: usize, page_size_1_gib: bool) -> Self {
+        let present: bool = virtual_address < memory_size;
+        let writable: bool = true;
+        let user_mode_access: bool = false;
+        let page_write_through: bool = false;
+        let page_cache_disable: bool = false;
+        let accessed: bool = false;
+        let restart: bool = false;
+        let execute_disable: bool = false;
+        let page_map_level_4_table: Option<PageMapLevel4Table> = if present {
+            Some(PageMapLevel4Table::new(virtual_address, Self::INDEX_SHIFT_END, memory_size, page_size_1_gib))
+        } else {
+            None
+        };
+        let page_map_level_4_table_address: Option<u64> = page_map_level_4_table
+            .as_ref()
+            .map(|page_map_level_4_table| page_map_level_4_table.address());
+        *page_map_level_5_entry = Self::entry(
+            present,
+            writable,
+            user_mode_access,
+            page_write_through,
+            page_cache_disable,
+            accessed,
+            restart,
+            page_map_level_4_table_address,
+            execute_disable);
+        Self {
+            present,
+            virtual_address,
+            page_map_level_5_entry,
+            writable,
+            user_mode_access,
+            page_write_through,
+            page_cache_disable,
+            accessed,
+            restart,
+            page_map_level_4_table,
+            execute_disable,
+        }
+    }
+
+    fn read(virtual_address: usize, page_map_level_5_entry: &'a mut u64) -> Option<Self> {
+        if *page_map_level_5_entry & Self::PRESENT_MASK != 0 {
+            let present: bool = *page_map_level_5_entry & Self::PRESENT_MASK != 0;
+            let writable: bool = *page_map_level_5_entry & Self::WRITABLE_MASK != 0;
+            let user_mode_access: bool = *page_map_level_5_entry & Self::USER_MODE_ACCESS_MASK != 0;
+            let page_write_through: bool = *page_map_level_5_entry & Self::PAGE_WRITE_THROUGH_MASK != 0;
+            let page_cache_disable: bool = *page_map_level_5_entry & Self::PAGE_CACHE_DISABLE_MASK != 0;
+            let accessed: bool = *page_map_level_5_entry & Self::ACCESSED_MASK != 0;
+            let restart: bool = *page_map_level_5_entry & Self::RESTART_MASK != 0;
+            let page_map_level_4_table: u64 = *page_map_level_5_entry & Self::PAGE_MAP_LEVEL_4_TABLE_MASK;
+            let page_map_level_4_table: Option<PageMapLevel4Table> = Some(PageMapLevel4Table::read(page_map_level_4_table, virtual_address, Self::INDEX_SHIFT_END));
+            let execute_disable: bool = *page_map_level_5_entry & Self::EXECUTE_DISABLE_MASK != 0;
+            Some(Self {
+                present,
+                virtual_address,
+                page_map_level_5_entry,
+                writable,
+                user_mode_access,
+                page_write_through,
+                page_cache_disable,
+                accessed,
+                restart,
+                page_map_level_4_table,
+                execute_disable,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn enable(
+        &mut self,
+        writable: bool,
+        user_mode_access: bool,
+        page_write_through: bool,
+        page_cache_disable: bool,
+        restart: bool,
+        execute_disable: bool) {
+        if !self.present {
+            // The new PML4 entries are not present, because memory_size is 0.
+            let memory_size: usize = 0;
+            let page_size_1_gib: bool = false;
+            let page_map_level_4_table = PageMapLevel4Table::new(self.virtual_address, Self::INDEX_SHIFT_END, memory_size, page_size_1_gib);
+            let page_map_level_4_table_address: Option<u64> = Some(page_map_level_4_table.address());
+            self.present = true;
+            self.writable = writable;
+            self.user_mode_access = user_mode_access;
+            self.page_write_through = page_write_through;
+            self.page_cache_disable = page_cache_disable;
+            self.restart = restart;
+            self.execute_disable = execute_disable;
+            self.page_map_level_4_table = Some(page_map_level_4_table);
+            *self.page_map_level_5_entry = Self::entry(
+                self.present,
+                self.writable,
+                self.user_mode_access,
+                self.page_write_through,
+                self.page_cache_disable,
+                self.accessed,
+                self.restart,
+                page_map_level_4_table_address,
+                self.execute_disable);
+        }
+    }
+
+    fn entry(
+        present: bool,
+        writable: bool,
+        user_mode_access: bool,
+        page_write_through: bool,
+        page_cache_disable: bool,
+        accessed: bool,
+        restart: bool,
+        page_map_level_4_table_address: Option<u64>,
+        execute_disable: bool) -> u64 {
+        let present_in_entry: u64 = if present {
+            Self::PRESENT_MASK
+        } else {
+            0
+        };
+        let writable_in_entry: u64 = if writable {
+            Self::WRITABLE_MASK
+        } else {
+            0
+        };
+        let user_mode_access_in_entry: u64 = if user_mode_access {
+            Self::USER_MODE_ACCESS_MASK
+        } else {
+            0
+        };
+        let page_write_through_in_entry: u64 = if page_write_through {
+            Self::PAGE_WRITE_THROUGH_MASK
+        } else {
+            0
+        };
+        let page_cache_disable_in_entry: u64 = if page_cache_disable {
+            Self::PAGE_CACHE_DISABLE_MASK
+        } else {
+            0
+        };
+        let accessed_in_entry: u64 = if accessed {
+            Self::ACCESSED_MASK
+        } else {
+            0
+        };
+        let restart_in_entry: u64 = if restart {
+            Self::RESTART_MASK
+        } else {
+            0
+        };
+        let execute_disable_in_entry: u64 = if execute_disable {
+            Self::EXECUTE_DISABLE_MASK
+        } else {
+            0
+        };
+        present_in_entry
+        | writable_in_entry
+        | user_mode_access_in_entry
+        | page_write_through_in_entry
+        | page_cache_disable_in_entry
+        | accessed_in_entry
+        | restart_in_entry
+        | page_map_level_4_table_address.unwrap_or(0)
+        | execute_disable_in_entry
+    }
+
+    fn divide_child(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        let writable: bool = true;
+        let user_mode_access: bool = false;
+        let page_write_through: bool = false;
+        let page_cache_disable: bool = false;
+        let restart: bool = false;
+        let execute_disable: bool = false;
+        self.enable(
+            writable,
+            user_mode_access,
+            page_write_through,
+            page_cache_disable,
+            restart,
+            execute_disable);
+        self.page_map_level_4_table
+            .as_mut()
+            .expect("Can't divide a page!")
+            .divide_child(virtual_address);
+    }
+
+    fn set_physical_address(&mut self, virtual_address: usize, physical_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        self.page_map_level_4_table
+            .as_mut()
+            .expect("Can't set a physical address!")
+            .set_physical_address(virtual_address, physical_address);
+    }
+
+    fn set_code_page(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        self.page_map_level_4_table
+            .as_mut()
+            .expect("Can't set a code page!")
+            .set_code_page(virtual_address);
+    }
+
+    fn set_data_page(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        self.page_map_level_4_table
+            .as_mut()
+            .expect("Can't set a data page!")
+            .set_data_page(virtual_address);
+    }
+
+    fn set_read_only_page(&mut self, virtual_address: usize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        self.page_map_level_4_table
+            .as_mut()
+            .expect("Can't set a read only page!")
+            .set_read_only_page(virtual_address);
+    }
+
+    fn map_page(&mut self, virtual_address: usize, physical_address: usize, page_size: PageSize) {
+        assert!(virtual_address & (usize::MAX << Self::INDEX_SHIFT_BEGIN) == self.virtual_address);
+        let writable: bool = true;
+        let user_mode_access: bool = false;
+        let page_write_through: bool = false;
+        let page_cache_disable: bool = false;
+        let restart: bool = false;
+        let execute_disable: bool = false;
+        self.enable(
+            writable,
+            user_mode_access,
+            page_write_through,
+            page_cache_disable,
+            restart,
+            execute_disable);
+        self.page_map_level_4_table
+            .as_mut()
+            .expect("Can't set a page!")
+            .map_page(virtual_address, physical_address, page_size);
+    }
+
+    fn print_state_at_address(&self, virtual_address: usize) {
+        serial_println!("page_map_level_5_entry.present = {:#x?}", &self.present);
+        serial_println!("page_map_level_5_entry.virtual_address = {:#x?}", &self.virtual_address);
+        serial_println!("page_map_level_5_entry.page_map_level_5_entry = {:#x?}", &self.page_map_level_5_entry);
+        serial_println!("page_map_level_5_entry.writable = {:#x?}", &self.writable);
+        serial_println!("page_map_level_5_entry.user_mode_access = {:#x?}", &self.user_mode_access);
+        serial_println!("page_map_level_5_entry.page_write_through = {:#x?}", &self.page_write_through);
+        serial_println!("page_map_level_5_entry.page_cache_disable = {:#x?}", &self.page_cache_disable);
+        serial_println!("page_map_level_5_entry.accessed = {:#x?}", &self.accessed);
+        serial_println!("page_map_level_5_entry.restart = {:#x?}", &self.restart);
+        serial_println!("page_map_level_5_entry.execute_disable = {:#x?}", &self.execute_disable);
+        if let Some(page_map_level_4_table) = self.page_map_level_4_table.as_ref() {
+            page_map_level_4_table.print_state_at_address(virtual_address);
+        }
+    }
+}
+
+impl fmt::Debug for PageMapLevel5Entry<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("PageMapLevel5Entry")
+            .field("present", &self.present)
+            .field("virtual_address", &self.virtual_address)
+            .field("page_map_level_5_entry", &self.page_map_level_5_entry)
+            .field("writable", &self.writable)
+            .field("user_mode_access", &self.user_mode_access)
+            .field("page_write_through", &self.page_write_through)
+            .field("page_cache_disable", &self.page_cache_disable)
+            .field("accessed", &self.accessed)
+            .field("restart", &self.restart)
+            .field("execute_disable", &self.execute_disable)
+            .field("page_map_level_4_table", &self.page_map_level_4_table)
+            .finish()
+    }
+}
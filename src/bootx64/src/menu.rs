@@ -0,0 +1,382 @@
+// The boot menu is shown on both the UEFI console and COM1 before exiting boot services.
+//
+// Up/Down or a digit: select a boot entry
+// Enter: boot the selected entry
+// e: edit the command line of the selected entry
+// f: pick a fallback entry booted if the selected kernel can't be read
+// d: enter the diagnostic prompt
+
+use {
+    alloc::{
+        string::String,
+        vec::Vec,
+    },
+    core::{
+        fmt::{
+            self,
+            Write,
+        },
+        mem,
+        ptr,
+    },
+    super::{
+        config,
+        gpt,
+        serial,
+        uefi::{
+            protocols::{
+                console_support::{
+                    graphics_output,
+                    simple_text_input,
+                },
+                media_access::{
+                    block_io,
+                    simple_file_system,
+                },
+            },
+            services::{
+                boot::{
+                    memory_allocation,
+                    protocol_handler,
+                },
+                runtime::variable,
+            },
+            tables::{
+                runtime_services,
+                system,
+            },
+        },
+    },
+};
+
+macro_rules! menu_print {
+    ($($arg:tt)*) => (print(format_args!($($arg)*)));
+}
+
+macro_rules! menu_println {
+    () => (menu_print!("\n"));
+    ($fmt:expr) => (menu_print!(concat!($fmt, "\n")));
+    ($fmt:expr, $($arg:tt)*) => (menu_print!(concat!($fmt, "\n"), $($arg)*));
+}
+
+// Microseconds between polling keys.
+const POLLING_INTERVAL: usize = 10000;
+const POLLS_PER_SECOND: usize = 1000000 / POLLING_INTERVAL;
+
+// Whether the last byte from COM1 is '\r'.
+static mut CARRIAGE_RETURN: bool = false;
+
+pub fn run(config: &mut config::Config) {
+    if config.timeout() == 0 {
+        return;
+    }
+    print_entries(config);
+    let key: Option<Key> = (0..config.timeout() * POLLS_PER_SECOND)
+        .find_map(|poll| {
+            if poll % POLLS_PER_SECOND == 0 {
+                menu_print!("\rBoot in {} seconds. Press any key to stop. ", config.timeout() - poll / POLLS_PER_SECOND);
+            }
+            let key: Option<Key> = read_key();
+            if key.is_none() {
+                stall(POLLING_INTERVAL);
+            }
+            key
+        });
+    menu_println!();
+    if let Some(key) = key {
+        // Firmware resets the system if the watchdog timer expires while the user stays in the menu.
+        if system::system()
+            .boot_services
+            .set_watchdog_timer(0)
+            .is_err() {
+            menu_println!("Can't disable the watchdog timer.");
+        }
+        interact(config, key);
+    }
+}
+
+// Wait for the user to select an entry without a timeout.
+pub fn stay(config: &mut config::Config) {
+    print_entries(config);
+    menu_println!("[Up/Down/0-9] select, [Enter] boot, [e] edit the command line, [f] pick a fallback, [d] diagnose");
+    let key: Key = wait_key();
+    interact(config, key);
+}
+
+fn interact(config: &mut config::Config, key: Key) {
+    let mut key: Key = key;
+    loop {
+        match key {
+            Key::Enter => return,
+            Key::Up => config.select(config.selected().saturating_sub(1)),
+            Key::Down => config.select(config.selected() + 1),
+            Key::Char('e') => {
+                menu_print!("command_line: ");
+                if let Some(command_line) = read_line(config.command_line()) {
+                    config.set_command_line(command_line);
+                }
+            },
+            Key::Char('f') => {
+                menu_print!("Fallback entry number (none if empty): ");
+                if let Some(fallback) = read_line("") {
+                    config.set_fallback(fallback.trim().parse().ok());
+                }
+            },
+            Key::Char('d') => diagnose(),
+            Key::Char(digit) => if let Some(selected) = digit.to_digit(10) {
+                config.select(selected as usize);
+            },
+            _ => {},
+        }
+        print_entries(config);
+        menu_println!("[Up/Down/0-9] select, [Enter] boot, [e] edit the command line, [f] pick a fallback, [d] diagnose");
+        key = wait_key();
+    }
+}
+
+fn print_entries(config: &config::Config) {
+    menu_println!("THEOS boot menu");
+    config
+        .entries()
+        .iter()
+        .enumerate()
+        .for_each(|(i, entry)| {
+            let cursor: char = if i == config.selected() {
+                '>'
+            } else {
+                ' '
+            };
+            menu_println!("{} {}: {} {} {}", cursor, i, entry.name(), entry.kernel(), entry.command_line());
+        });
+    match config.fallback() {
+        Some(fallback) => menu_println!("Fallback: {}", fallback.name()),
+        None => menu_println!("Fallback: none"),
+    }
+}
+
+// A minimal prompt to inspect the firmware before exiting boot services.
+fn diagnose() {
+    menu_println!("Diagnostic prompt. Type \"help\" to list commands.");
+    loop {
+        menu_print!("diagnose> ");
+        let command: Option<String> = read_line("");
+        match command.as_deref().map(|command| command.trim()) {
+            Some("help") => {
+                menu_println!("memory_map: print the memory map");
+                menu_println!("gop: print graphics output modes");
+                menu_println!("pci: print PCI I/O protocol handles");
+                menu_println!("ls [path]: list a directory of the boot volume");
+                menu_println!("disks: print GPT disks and partitions");
+                menu_println!("variables: print names of UEFI variables and the variable storage");
+                menu_println!("exit: return to the boot menu");
+            },
+            Some("memory_map") => {
+                let memory_map = memory_allocation::Map::new();
+                let memory_map: Vec<memory_allocation::MemoryDescriptor> = (&memory_map).into();
+                memory_map
+                    .iter()
+                    .for_each(|memory_descriptor| menu_println!("{:x?}", memory_descriptor));
+            },
+            Some("gop") => {
+                let graphics_output: &graphics_output::GraphicsOutput = graphics_output::GraphicsOutput::new();
+                let mode_number: u32 = graphics_output.mode_number();
+                graphics_output
+                    .modes()
+                    .into_iter()
+                    .for_each(|(number, information)| {
+                        let cursor: char = if number == mode_number {
+                            '>'
+                        } else {
+                            ' '
+                        };
+                        menu_println!("{} {}: {:x?}", cursor, number, information);
+                    });
+            },
+            Some("pci") => {
+                // EFI_PCI_IO_PROTOCOL_GUID
+                let guid = protocol_handler::Guid::new(
+                    0x4cf5b200,
+                    0x68b8,
+                    0x4ca5,
+                    [
+                        0x9e,
+                        0xec,
+                        0xb2,
+                        0x3e,
+                        0x3f,
+                        0x50,
+                        0x02,
+                        0x9a
+                    ],
+                );
+                match system::system()
+                    .boot_services
+                    .locate_handle_buffer(protocol_handler::LocateSearchType::ByProtocol, &guid) {
+                    Ok(handles) => {
+                        menu_println!("{} PCI I/O protocol handles", handles.len());
+                        handles
+                            .iter()
+                            .for_each(|handle| menu_println!("{:?}", handle));
+                    },
+                    Err(error) => menu_println!("Can't locate PCI I/O protocol handles! (status = {:#x})", error),
+                }
+            },
+            Some(command) if command == "ls" || command.starts_with("ls ") => {
+                let path: &str = match command[2..].trim() {
+                    "" => "/",
+                    path => path,
+                };
+                let simple_file_system: &simple_file_system::SimpleFileSystem = simple_file_system::SimpleFileSystem::new();
+                if let Some(information) = simple_file_system
+                    .open_volume()
+                    .file_system_information() {
+                    menu_println!("volume \"{}\": {:#x} bytes free of {:#x} bytes", information.volume_label(), information.free_space(), information.volume_size());
+                }
+                match simple_file_system.directory(path) {
+                    Some(children) => children
+                        .iter()
+                        .for_each(|child| if child.is_directory() {
+                            menu_println!("{}/", child.file_name());
+                        } else {
+                            menu_println!("{} ({:#x} bytes)", child.file_name(), child.file_size());
+                        }),
+                    None => menu_println!("Can't find a directory {}.", path),
+                }
+            },
+            Some("disks") => gpt::Disk::all()
+                .iter()
+                .for_each(|disk| {
+                    let media: &block_io::BlockIoMedia = disk.media();
+                    menu_println!("disk {} ({:#x} blocks of {} bytes, removable = {}, read_only = {})", disk.guid(), media.last_block() + 1, disk.block_size(), media.removable_media(), media.read_only());
+                    disk.partitions()
+                        .iter()
+                        .for_each(|partition| menu_println!("  partition {} type {} LBA {:#x} ({:#x} blocks, attributes {:#x}) \"{}\"", partition.unique_guid(), partition.type_guid(), partition.first_lba(), partition.blocks(), partition.attributes(), partition.name()));
+                }),
+            Some("variables") => {
+                let runtime_services: &runtime_services::RuntimeServices = system::system().runtime_services;
+                runtime_services
+                    .variable_names()
+                    .iter()
+                    .for_each(|(name, vendor)| menu_println!("{} {}", vendor, name));
+                let attributes: u32 = variable::NON_VOLATILE | variable::BOOTSERVICE_ACCESS | variable::RUNTIME_ACCESS;
+                match runtime_services.query_variable_info(attributes) {
+                    Ok((maximum_storage_size, remaining_storage_size, maximum_variable_size)) => menu_println!("non-volatile storage: {:#x} bytes remaining of {:#x} bytes, up to {:#x} bytes per variable", remaining_storage_size, maximum_storage_size, maximum_variable_size),
+                    Err(error) => menu_println!("Can't query variable storage! (status = {:#x})", error),
+                }
+            },
+            Some("exit") | None => return,
+            Some("") => {},
+            Some(command) => menu_println!("Unknown command \"{}\".", command),
+        }
+    }
+}
+
+fn print(args: fmt::Arguments) {
+    system::print(args);
+    serial::Serial::com1()
+        .write_fmt(args)
+        .expect("Can't print to COM1!");
+}
+
+// Return None if the user cancels the input by escape.
+fn read_line(initial: &str) -> Option<String> {
+    let mut line = String::from(initial);
+    menu_print!("{}", line);
+    loop {
+        match wait_key() {
+            Key::Enter => {
+                menu_println!();
+                return Some(line);
+            },
+            Key::Escape => {
+                menu_println!();
+                return None;
+            },
+            Key::Backspace => if line.pop().is_some() {
+                menu_print!("\u{8} \u{8}");
+            },
+            Key::Char(character) => {
+                line.push(character);
+                menu_print!("{}", character);
+            },
+            Key::Up | Key::Down => {},
+        }
+    }
+}
+
+fn stall(microseconds: usize) {
+    system::system()
+        .boot_services
+        .stall(microseconds)
+        .expect("Can't stall!");
+}
+
+fn wait_key() -> Key {
+    loop {
+        if let Some(key) = read_key() {
+            return key;
+        }
+        stall(POLLING_INTERVAL);
+    }
+}
+
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+}
+
+// Return None if no key is pressed on both the UEFI console and COM1.
+fn read_key() -> Option<Key> {
+    system::system()
+        .con_in
+        .read_key_stroke()
+        .and_then(|key| match (key.scan_code(), key.unicode_char()) {
+            (simple_text_input::SCAN_UP, _) => Some(Key::Up),
+            (simple_text_input::SCAN_DOWN, _) => Some(Key::Down),
+            (simple_text_input::SCAN_ESC, _) => Some(Key::Escape),
+            (_, Some('\r')) | (_, Some('\n')) => Some(Key::Enter),
+            (_, Some('\u{8}')) => Some(Key::Backspace),
+            (_, Some(character)) => Some(Key::Char(character)),
+            (_, None) => None,
+        })
+        .or_else(|| {
+            let com1: &serial::Serial = serial::Serial::com1();
+            com1
+                .read_byte()
+                .map(|byte| {
+                    let carriage_return: *mut bool = ptr::addr_of_mut!(CARRIAGE_RETURN);
+                    let carriage_return: &mut bool = unsafe {
+                        &mut *carriage_return
+                    };
+                    let follows_carriage_return: bool = mem::replace(carriage_return, byte == b'\r');
+                    (byte, follows_carriage_return)
+                })
+                .and_then(|(byte, follows_carriage_return)| match byte {
+                    // A terminal may send "\r\n" for a single Enter.
+                    b'\n' if follows_carriage_return => None,
+                    b'\r' | b'\n' => Some(Key::Enter),
+                    0x08 | 0x7f => Some(Key::Backspace),
+                    // An arrow key is sent as an escape sequence "\x1b[A" or "\x1b[B".
+                    0x1b => {
+                        stall(POLLING_INTERVAL);
+                        match com1.read_byte() {
+                            Some(b'[') => {
+                                stall(POLLING_INTERVAL);
+                                match com1.read_byte() {
+                                    Some(b'A') => Some(Key::Up),
+                                    Some(b'B') => Some(Key::Down),
+                                    _ => None,
+                                }
+                            },
+                            _ => Some(Key::Escape),
+                        }
+                    },
+                    byte if byte.is_ascii_graphic() || byte == b' ' => Some(Key::Char(byte as char)),
+                    _ => None,
+                })
+        })
+}
@@ -0,0 +1,97 @@
+// Boot modules are files (an initial ramdisk, fonts, test payloads and so on) loaded alongside the kernel.
+// Each module is loaded into physical pages and mapped into the higher half below the kernel stack.
+// Debug sections of the kernel are passed in the same way.
+
+use {
+    alloc::{
+        collections::btree_map::BTreeMap,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    super::{
+        boot_info,
+        memory,
+        uefi::services::boot::memory_allocation,
+    },
+};
+
+#[derive(Debug)]
+pub struct Loaded<'a> {
+    name: String,
+    pages: memory::Pages<'a>,
+    virtual_address: usize,
+    size: usize,
+}
+
+impl Loaded<'_> {
+    // The module ends at virtual_address_ceiling.
+    pub fn new(name: &str, bytes: &[u8], virtual_address_ceiling: usize) -> Self {
+        let name: String = name.to_string();
+        let size: usize = bytes.len();
+        let pages: usize = (size + memory_allocation::PAGE_SIZE - 1) / memory_allocation::PAGE_SIZE;
+        let pages: usize = pages.max(1);
+        let mut pages = memory::Pages::new(pages);
+        pages.write(0, 0, bytes);
+        let virtual_address: usize = virtual_address_ceiling - pages.pages() * memory_allocation::PAGE_SIZE;
+        Self {
+            name,
+            pages,
+            virtual_address,
+            size,
+        }
+    }
+
+    // Place files downward from the ceiling leaving an unmapped page between each of them.
+    pub fn load_below<'b>(files: impl Iterator<Item = (&'b str, &'b [u8])>, ceiling: usize) -> Vec<Self> {
+        files.fold(Vec::new(), |mut loaded, (name, bytes)| {
+            let ceiling: usize = Self::floor(&loaded, ceiling);
+            loaded.push(Self::new(name, bytes, ceiling));
+            loaded
+        })
+    }
+
+    // The ceiling of files placed below the loaded files.
+    pub fn floor(loaded: &[Self], ceiling: usize) -> usize {
+        match loaded.last() {
+            Some(last) => last.virtual_address - memory_allocation::PAGE_SIZE,
+            None => ceiling,
+        }
+    }
+
+    pub fn page_map(&self) -> BTreeMap<usize, usize> {
+        self.pages
+            .physical_addresses()
+            .enumerate()
+            .map(|(i, physical_address)| (physical_address, self.virtual_address + i * memory_allocation::PAGE_SIZE))
+            .collect()
+    }
+}
+
+// A module passed to the kernel.
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+pub struct Module {
+    name: boot_info::Array,
+    physical_address: u64,
+    virtual_address: u64,
+    size: u64,
+}
+
+impl Into<Module> for &Loaded<'_> {
+    fn into(self) -> Module {
+        let name: boot_info::Array = self.name.as_bytes().into();
+        let physical_address: u64 = self.pages.physical_address();
+        let virtual_address: u64 = self.virtual_address as u64;
+        let size: u64 = self.size as u64;
+        Module {
+            name,
+            physical_address,
+            virtual_address,
+            size,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// Loader phases are timestamped with the TSC and handed to the kernel.
+// Phases are recorded without allocating memory so that exiting boot services can be measured.
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    core::ptr,
+    super::{
+        asm,
+        boot_info,
+    },
+};
+
+const MAX_PHASES: usize = 0x10;
+
+static mut PHASES: [boot_info::Phase; MAX_PHASES] = [boot_info::Phase::NONE; MAX_PHASES];
+static mut RECORDED_PHASES: usize = 0;
+
+pub struct Timer {
+    name: &'static str,
+    begin: u64,
+}
+
+impl Timer {
+    pub fn start(name: &'static str) -> Self {
+        let begin: u64 = asm::rdtsc();
+        Self {
+            name,
+            begin,
+        }
+    }
+
+    pub fn stop(self) {
+        let end: u64 = asm::rdtsc();
+        serial_println!("{} took {} TSC ticks.", self.name, end - self.begin);
+        let phases: *mut [boot_info::Phase; MAX_PHASES] = ptr::addr_of_mut!(PHASES);
+        let phases: &mut [boot_info::Phase; MAX_PHASES] = unsafe {
+            &mut *phases
+        };
+        let recorded_phases: *mut usize = ptr::addr_of_mut!(RECORDED_PHASES);
+        let recorded_phases: &mut usize = unsafe {
+            &mut *recorded_phases
+        };
+        // Phases beyond the capacity are only printed.
+        if let Some(phase) = phases.get_mut(*recorded_phases) {
+            *phase = boot_info::Phase::new(self.name, self.begin, end);
+            *recorded_phases += 1;
+        }
+    }
+}
+
+pub fn phases() -> &'static [boot_info::Phase] {
+    let phases: *const [boot_info::Phase; MAX_PHASES] = ptr::addr_of!(PHASES);
+    let phases: &[boot_info::Phase; MAX_PHASES] = unsafe {
+        &*phases
+    };
+    let recorded_phases: usize = unsafe {
+        RECORDED_PHASES
+    };
+    &phases[..recorded_phases]
+}
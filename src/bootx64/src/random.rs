@@ -0,0 +1,37 @@
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    super::{
+        asm::{
+            self,
+            cpuid,
+        },
+        uefi::protocols::random_number_generator,
+    },
+};
+
+// Get a random number from the UEFI RNG protocol, RDSEED or RDRAND in this order.
+pub fn generate(cpuid: &Option<cpuid::Cpuid>) -> Option<u64> {
+    random_number_generator::RandomNumberGenerator::new()
+        .and_then(|random_number_generator| random_number_generator.get_u64())
+        .map(|random| {
+            serial_println!("A random number is generated by the UEFI RNG protocol.");
+            random
+        })
+        .or_else(|| match cpuid {
+            Some(cpuid) if cpuid.supports_rdseed() => asm::rdseed().map(|random| {
+                serial_println!("A random number is generated by RDSEED.");
+                random
+            }),
+            _ => None,
+        })
+        .or_else(|| match cpuid {
+            Some(cpuid) if cpuid.supports_rdrand() => asm::rdrand().map(|random| {
+                serial_println!("A random number is generated by RDRAND.");
+                random
+            }),
+            _ => None,
+        })
+}
@@ -9,9 +9,13 @@ mod line_status_register;
 mod modem_control_register;
 
 use {
-    core::fmt::{
-        self,
-        Write,
+    alloc::string::String,
+    core::{
+        fmt::{
+            self,
+            Write,
+        },
+        ptr,
     },
     super::asm,
 };
@@ -27,23 +31,158 @@ macro_rules! serial_println {
     ($fmt:expr, $($arg:tt)*) => (serial_print!(concat!($fmt, "\n"), $($arg)*));
 }
 
+// Print only if the verbosity is Verbosity::Verbose.
+#[macro_export]
+macro_rules! serial_verbose_println {
+    ($($arg:tt)*) => (if $crate::serial::verbosity() == $crate::serial::Verbosity::Verbose {
+        serial_println!($($arg)*);
+    });
+}
+
 pub fn print(args: fmt::Arguments) {
-    Serial::com1().write_fmt(args).expect("Can't print to COM1!");
-    Serial::com2().write_fmt(args).expect("Can't print to COM2!");
+    if verbosity() != Verbosity::Quiet {
+        Serial::com1().write_fmt(args).expect("Can't print to COM1!");
+        Serial::com2().write_fmt(args).expect("Can't print to COM2!");
+    }
+    record(args);
+    // The log is kept regardless of the verbosity for machines without serial ports.
+    let log: *mut Option<String> = ptr::addr_of_mut!(LOG);
+    if let Some(log) = unsafe {
+        (*log).as_mut()
+    } {
+        log.write_fmt(args).expect("Can't write a boot log!");
+    }
+}
+
+// Keep printed messages until take_log is called.
+// The log must be taken before exiting boot services because it allocates memory from boot services.
+pub fn start_log() {
+    unsafe {
+        LOG = Some(String::new());
+    }
+}
+
+pub fn take_log() -> Option<String> {
+    let log: *mut Option<String> = ptr::addr_of_mut!(LOG);
+    unsafe {
+        (*log).take()
+    }
+}
+
+// Keep a message in the ring buffer handed to the kernel regardless of the verbosity.
+pub fn record(args: fmt::Arguments) {
+    let ring_buffer: *mut RingBuffer = ptr::addr_of_mut!(RING_BUFFER);
+    unsafe {
+        (*ring_buffer).write_fmt(args)
+    }.expect("Can't write to the ring buffer!");
+}
+
+// Stop recording messages and return them from the oldest one.
+pub fn take_ring_buffer() -> &'static [u8] {
+    let ring_buffer: *mut RingBuffer = ptr::addr_of_mut!(RING_BUFFER);
+    unsafe {
+        (*ring_buffer).take()
+    }
+}
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    unsafe {
+        VERBOSITY = verbosity;
+    }
+}
+
+pub fn verbosity() -> Verbosity {
+    unsafe {
+        VERBOSITY
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verbosity {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+impl TryFrom<&str> for Verbosity {
+    type Error = ();
+
+    fn try_from(verbosity: &str) -> Result<Self, Self::Error> {
+        match verbosity {
+            "quiet" => Ok(Self::Quiet),
+            "normal" => Ok(Self::Normal),
+            "verbose" => Ok(Self::Verbose),
+            _ => Err(()),
+        }
+    }
+}
+
+// The ring buffer doesn't allocate memory so that it keeps messages after exiting boot services.
+struct RingBuffer {
+    bytes: [u8; RING_BUFFER_SIZE],
+    written: usize,
+    taken: bool,
+}
+
+impl RingBuffer {
+    const fn new() -> Self {
+        let bytes: [u8; RING_BUFFER_SIZE] = [0; RING_BUFFER_SIZE];
+        let written: usize = 0;
+        let taken: bool = false;
+        Self {
+            bytes,
+            written,
+            taken,
+        }
+    }
+
+    fn take(&mut self) -> &[u8] {
+        self.taken = true;
+        if RING_BUFFER_SIZE < self.written {
+            // Overwritten messages are lost and the oldest remaining byte comes first.
+            self.bytes.rotate_left(self.written % RING_BUFFER_SIZE);
+            &self.bytes
+        } else {
+            &self.bytes[..self.written]
+        }
+    }
+}
+
+impl Write for RingBuffer {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        if !self.taken {
+            string
+                .bytes()
+                .for_each(|byte| {
+                    self.bytes[self.written % RING_BUFFER_SIZE] = byte;
+                    self.written += 1;
+                });
+        }
+        Ok(())
+    }
 }
 
 pub struct Serial {
     port: asm::Port,
+    baud: u32,
 }
 
 static mut COM1: Option<Serial> = None;
 static mut COM2: Option<Serial> = None;
+static mut LOG: Option<String> = None;
+static mut RING_BUFFER: RingBuffer = RingBuffer::new();
+static mut VERBOSITY: Verbosity = Verbosity::Normal;
 const COM1PORT: asm::Port = 0x03f8;
 const COM2PORT: asm::Port = 0x02f8;
-const BAUD: u32 = 9600;
-const FREQUENCY: u32 = 115200;
+const RING_BUFFER_SIZE: usize = 0x10000;
+pub const BAUD: u32 = 9600;
+pub const FREQUENCY: u32 = 115200;
 
 impl Serial {
+    pub fn baud(&self) -> u32 {
+        self.baud
+    }
+
     pub fn com1<'a>() -> &'a mut Self {
         unsafe {
             COM1.as_mut().expect("Can't get a serial port COM1!")
@@ -56,15 +195,15 @@ impl Serial {
         }
     }
 
-    pub fn init_com1() {
+    pub fn init_com1(baud: u32) {
         unsafe {
-            COM1 = Some(Self::new(COM1PORT, BAUD));
+            COM1 = Some(Self::new(COM1PORT, baud));
         }
     }
 
-    pub fn init_com2() {
+    pub fn init_com2(baud: u32) {
         unsafe {
-            COM2 = Some(Self::new(COM2PORT, BAUD));
+            COM2 = Some(Self::new(COM2PORT, baud));
         }
     }
 
@@ -72,6 +211,7 @@ impl Serial {
         // A new serial interface.
         let serial = Self {
             port,
+            baud,
         };
 
         // Disable all interrupts.
@@ -131,6 +271,20 @@ impl Serial {
         serial
     }
 
+    pub fn port(&self) -> asm::Port {
+        self.port
+    }
+
+    // Return None if no byte is received.
+    pub fn read_byte(&self) -> Option<u8> {
+        let line_status_register: line_status_register::LineStatusRegister = self.into();
+        if line_status_register.data_ready() {
+            Some(asm::inb(self.port))
+        } else {
+            None
+        }
+    }
+
     fn can_send(&self) -> bool {
         let line_status_register: line_status_register::LineStatusRegister = self.into();
         line_status_register.empty_transmitter_holding_register()
@@ -24,6 +24,10 @@ const TRANSMITTER_EMPTY: u8 = 0x40;
 const IMPENDING_ERROR: u8 = 0x80;
 
 impl LineStatusRegister {
+    pub fn data_ready(&self) -> bool {
+        self.data_ready
+    }
+
     pub fn empty_transmitter_holding_register(&self) -> bool {
         self.empty_transmitter_holding_register
     }
@@ -0,0 +1,131 @@
+// Kernels and modules are verified by detached Ed25519 signatures in "<path>.sig" before they are booted.
+// A public key embedded at build time by the environment variable THEOS_PUBLIC_KEY (64 hexadecimal digits) is always enforced.
+// Otherwise a public key in the UEFI variable TheosPublicKey is enforced regardless of the boot configuration.
+// The variable must not be accessible at runtime so that only the firmware setup or a loader before exiting boot services can write it.
+// "verify_signatures = true" in the boot configuration refuses to boot without the public key, but "verify_signatures = false" can't turn verification off.
+//
+// Signatures can be made by OpenSSL.
+// openssl genpkey -algorithm ed25519 -out theos.pem
+// openssl pkey -in theos.pem -pubout -outform DER | tail -c 32 | xxd -p -c 32
+// openssl pkeyutl -sign -inkey theos.pem -rawin -in kernel.elf -out kernel.elf.sig
+
+mod ed25519;
+mod sha512;
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+        uefi_print,
+        uefi_println,
+    },
+    alloc::{
+        format,
+        string::String,
+        vec::Vec,
+    },
+    super::{
+        boot_state,
+        file_system,
+        uefi::{
+            services::runtime::variable,
+            tables::system,
+        },
+    },
+};
+
+const EMBEDDED_PUBLIC_KEY: Option<&str> = option_env!("THEOS_PUBLIC_KEY");
+const PUBLIC_KEY: &str = "TheosPublicKey";
+const SIGNATURE_EXTENSION: &str = ".sig";
+const SECURE_BOOT: variable::Variable<bool> = variable::Variable::new("SecureBoot", &variable::GLOBAL_VARIABLE, variable::BOOTSERVICE_ACCESS | variable::RUNTIME_ACCESS);
+const SETUP_MODE: variable::Variable<bool> = variable::Variable::new("SetupMode", &variable::GLOBAL_VARIABLE, variable::BOOTSERVICE_ACCESS | variable::RUNTIME_ACCESS);
+
+#[derive(Debug)]
+pub struct Verifier {
+    // Files are not verified if None.
+    public_key: Option<[u8; ed25519::PUBLIC_KEY_SIZE]>,
+}
+
+impl Verifier {
+    // Panic if verification is required but no public key is available.
+    pub fn new(required: bool) -> Self {
+        let secure_boot: Option<bool> = SECURE_BOOT.get();
+        let setup_mode: Option<bool> = SETUP_MODE.get();
+        serial_println!("secure_boot = {:?}", secure_boot);
+        serial_println!("setup_mode = {:?}", setup_mode);
+        let public_key: Option<[u8; ed25519::PUBLIC_KEY_SIZE]> = match EMBEDDED_PUBLIC_KEY {
+            Some(public_key) => Some(parse_public_key(public_key).expect("Can't read the embedded public key!")),
+            None => match read_public_key() {
+                Some(public_key) => Some(public_key),
+                None if required => panic!("Can't find a public key to verify signatures!"),
+                None => None,
+            },
+        };
+        if public_key.is_none() && secure_boot == Some(true) {
+            serial_println!("Secure Boot is enabled but the kernel isn't verified.");
+        }
+        Self {
+            public_key,
+        }
+    }
+
+    // Refuse to boot if the file isn't signed by the public key.
+    pub fn enforce(&self, file_system: &file_system::FileSystem, path: &str, bytes: &[u8]) {
+        if !self.verify(file_system, path, bytes) {
+            uefi_println!("The signature of {} doesn't match! Refuse to boot.", path);
+            panic!("Can't verify a signature of {}!", path);
+        }
+    }
+
+    // Return true if the file is signed by the public key or no public key is required.
+    fn verify(&self, file_system: &file_system::FileSystem, path: &str, bytes: &[u8]) -> bool {
+        let public_key: &[u8; ed25519::PUBLIC_KEY_SIZE] = match self.public_key.as_ref() {
+            Some(public_key) => public_key,
+            None => return true,
+        };
+        let signature_path: String = format!("{}{}", path, SIGNATURE_EXTENSION);
+        let verified: bool = file_system
+            .try_read_file(&signature_path)
+            .and_then(|signature| signature
+                .as_slice()
+                .try_into()
+                .ok()
+                .map(|signature| ed25519::verify(public_key, bytes, signature)))
+            .unwrap_or(false);
+        if verified {
+            serial_println!("Verified a signature of {}.", path);
+        } else {
+            serial_println!("Can't verify a signature of {}!", path);
+        }
+        verified
+    }
+}
+
+fn parse_public_key(public_key: &str) -> Option<[u8; ed25519::PUBLIC_KEY_SIZE]> {
+    let public_key: &str = public_key.trim();
+    if public_key.len() != 2 * ed25519::PUBLIC_KEY_SIZE {
+        return None;
+    }
+    let public_key: Vec<u8> = (0..public_key.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(public_key.get(index..index + 2)?, 0x10).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    public_key
+        .try_into()
+        .ok()
+}
+
+// Return None if the variable doesn't exist or the operating system can modify it.
+fn read_public_key() -> Option<[u8; ed25519::PUBLIC_KEY_SIZE]> {
+    let (attributes, public_key): (u32, Vec<u8>) = system::system()
+        .runtime_services
+        .get_variable(PUBLIC_KEY, &boot_state::VENDOR)
+        .ok()?;
+    if attributes & variable::RUNTIME_ACCESS != 0 {
+        serial_println!("Ignore {} accessible at runtime.", PUBLIC_KEY);
+        return None;
+    }
+    public_key
+        .try_into()
+        .ok()
+}
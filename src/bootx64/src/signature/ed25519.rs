@@ -0,0 +1,474 @@
+// Ed25519 signature verification
+//
+// References
+// https://www.rfc-editor.org/rfc/rfc8032 5.1 Ed25519ph, Ed25519ctx, and Ed25519
+
+use {
+    core::ops::{
+        Add,
+        Mul,
+        Neg,
+        Sub,
+    },
+    super::sha512,
+};
+
+pub const PUBLIC_KEY_SIZE: usize = 0x20;
+pub const SIGNATURE_SIZE: usize = 0x40;
+
+// The encoding of the base point.
+const BASE_POINT: [u8; 0x20] = [
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+];
+// The order of the base point L = 2^252 + 27742317777372353535851937790883648493 in little endian.
+const ORDER: [u64; 4] = [
+    0x5812631a5cf5d3ed,
+    0x14def9dea2f79cd6,
+    0x0000000000000000,
+    0x1000000000000000,
+];
+// Exponents in little endian
+// p - 2 for inversions
+const INVERSE_EXPONENT: [u8; 0x20] = [
+    0xeb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+];
+// (p - 5) / 8 for square roots
+const SQUARE_ROOT_EXPONENT: [u8; 0x20] = [
+    0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x0f,
+];
+// d = -121665 / 121666
+const D: FieldElement = FieldElement {
+    limbs: [
+        0x34dca135978a3,
+        0x1a8283b156ebd,
+        0x5e7a26001c029,
+        0x739c663a03cbb,
+        0x52036cee2b6ff,
+    ],
+};
+// 2d
+const D2: FieldElement = FieldElement {
+    limbs: [
+        0x69b9426b2f159,
+        0x35050762add7a,
+        0x3cf44c0038052,
+        0x6738cc7407977,
+        0x2406d9dc56dff,
+    ],
+};
+// 2^((p - 1) / 4)
+const SQUARE_ROOT_OF_MINUS_ONE: FieldElement = FieldElement {
+    limbs: [
+        0x61b274a0ea0b0,
+        0x0d5a5fc8f189d,
+        0x7ef5e9cbd0c60,
+        0x78595a6804c9e,
+        0x2b8324804fc1d,
+    ],
+};
+
+// Check a signature of a message by a public key.
+pub fn verify(public_key: &[u8; PUBLIC_KEY_SIZE], message: &[u8], signature: &[u8; SIGNATURE_SIZE]) -> bool {
+    let r: &[u8; 0x20] = signature[..0x20]
+        .try_into()
+        .expect("Can't read a signature!");
+    let s: &[u8; 0x20] = signature[0x20..]
+        .try_into()
+        .expect("Can't read a signature!");
+    // Reject malleable signatures.
+    if !is_reduced(s) {
+        return false;
+    }
+    let (a, base_point): (Point, Point) = match Point::decode(public_key).zip(Point::decode(&BASE_POINT)) {
+        Some(points) => points,
+        None => return false,
+    };
+    if Point::decode(r).is_none() {
+        return false;
+    }
+    let h: [u8; 0x40] = sha512::digest(&[r, public_key, message]);
+    let h: [u8; 0x20] = reduce(&h);
+    // [S]B = R + [h]A
+    let r_check: Point = base_point.multiply(s) + (-a).multiply(&h);
+    r_check.encode() == *r
+}
+
+// Whether a scalar is less than the order.
+fn is_reduced(scalar: &[u8; 0x20]) -> bool {
+    let scalar: [u64; 4] = [0, 1, 2, 3].map(|index| u64::from_le_bytes(scalar[8 * index..8 * (index + 1)]
+        .try_into()
+        .expect("Can't read a scalar!")));
+    scalar
+        .iter()
+        .zip(ORDER.iter())
+        .rev()
+        .find(|(scalar, order)| scalar != order)
+        .map_or(false, |(scalar, order)| scalar < order)
+}
+
+// A 512-bit little endian number modulo the order.
+fn reduce(number: &[u8; 0x40]) -> [u8; 0x20] {
+    // The remainder is less than the order and doubling it fits in 5 words.
+    let mut remainder: [u64; 5] = [0; 5];
+    (0..number.len() * 8)
+        .rev()
+        .for_each(|bit| {
+            let bit: u64 = (number[bit / 8] >> (bit % 8) & 1) as u64;
+            let mut carry: u64 = bit;
+            remainder
+                .iter_mut()
+                .for_each(|word| {
+                    let next_carry: u64 = *word >> 63;
+                    *word = *word << 1 | carry;
+                    carry = next_carry;
+                });
+            let order: [u64; 5] = [ORDER[0], ORDER[1], ORDER[2], ORDER[3], 0];
+            let less: bool = remainder
+                .iter()
+                .zip(order.iter())
+                .rev()
+                .find(|(remainder, order)| remainder != order)
+                .map_or(false, |(remainder, order)| remainder < order);
+            if !less {
+                let mut borrow: bool = false;
+                remainder
+                    .iter_mut()
+                    .zip(order.iter())
+                    .for_each(|(word, order)| {
+                        let (difference, borrow0): (u64, bool) = word.overflowing_sub(*order);
+                        let (difference, borrow1): (u64, bool) = difference.overflowing_sub(borrow as u64);
+                        *word = difference;
+                        borrow = borrow0 || borrow1;
+                    });
+            }
+        });
+    let mut reduced: [u8; 0x20] = [0; 0x20];
+    reduced
+        .chunks_mut(8)
+        .zip(remainder.iter())
+        .for_each(|(bytes, word)| bytes.copy_from_slice(&word.to_le_bytes()));
+    reduced
+}
+
+// An element of the field modulo p = 2^255 - 19 in 51-bit limbs.
+#[derive(Clone, Copy)]
+struct FieldElement {
+    limbs: [u64; 5],
+}
+
+impl FieldElement {
+    const LIMB_BITS: u32 = 51;
+    const LIMB_MASK: u64 = (1 << Self::LIMB_BITS) - 1;
+    // 2p in limbs keeps subtraction non-negative.
+    const TWO_P: [u64; 5] = [
+        0xfffffffffffda,
+        0xffffffffffffe,
+        0xffffffffffffe,
+        0xffffffffffffe,
+        0xffffffffffffe,
+    ];
+
+    fn new(small: u64) -> Self {
+        let limbs: [u64; 5] = [small, 0, 0, 0, 0];
+        Self {
+            limbs,
+        }
+    }
+
+    // The most significant bit is ignored.
+    fn decode(bytes: &[u8; 0x20]) -> Self {
+        let words: [u64; 4] = [0, 1, 2, 3].map(|index| u64::from_le_bytes(bytes[8 * index..8 * (index + 1)]
+            .try_into()
+            .expect("Can't read a field element!")));
+        let limbs: [u64; 5] = [
+            words[0] & Self::LIMB_MASK,
+            (words[0] >> 51 | words[1] << 13) & Self::LIMB_MASK,
+            (words[1] >> 38 | words[2] << 26) & Self::LIMB_MASK,
+            (words[2] >> 25 | words[3] << 39) & Self::LIMB_MASK,
+            words[3] >> 12 & Self::LIMB_MASK,
+        ];
+        Self {
+            limbs,
+        }
+    }
+
+    // The canonical encoding less than p.
+    fn encode(&self) -> [u8; 0x20] {
+        let mut limbs: [u64; 5] = self.carry().carry().limbs;
+        // Subtract p if the element is not less than p.
+        let mut quotient: u64 = (limbs[0] + 19) >> Self::LIMB_BITS;
+        limbs[1..]
+            .iter()
+            .for_each(|limb| quotient = (limb + quotient) >> Self::LIMB_BITS);
+        limbs[0] += 19 * quotient;
+        (0..4).for_each(|index| {
+            limbs[index + 1] += limbs[index] >> Self::LIMB_BITS;
+            limbs[index] &= Self::LIMB_MASK;
+        });
+        limbs[4] &= Self::LIMB_MASK;
+        let words: [u64; 4] = [
+            limbs[0] | limbs[1] << 51,
+            limbs[1] >> 13 | limbs[2] << 38,
+            limbs[2] >> 26 | limbs[3] << 25,
+            limbs[3] >> 39 | limbs[4] << 12,
+        ];
+        let mut bytes: [u8; 0x20] = [0; 0x20];
+        bytes
+            .chunks_mut(8)
+            .zip(words.iter())
+            .for_each(|(bytes, word)| bytes.copy_from_slice(&word.to_le_bytes()));
+        bytes
+    }
+
+    // Carry each limb into the next one so that each limb fits in about 51 bits.
+    fn carry(&self) -> Self {
+        let mut limbs: [u64; 5] = self.limbs;
+        (0..4).for_each(|index| {
+            limbs[index + 1] += limbs[index] >> Self::LIMB_BITS;
+            limbs[index] &= Self::LIMB_MASK;
+        });
+        limbs[0] += 19 * (limbs[4] >> Self::LIMB_BITS);
+        limbs[4] &= Self::LIMB_MASK;
+        Self {
+            limbs,
+        }
+    }
+
+    fn invert(&self) -> Self {
+        self.pow(&INVERSE_EXPONENT)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.encode()[0] & 1 != 0
+    }
+
+    fn is_zero(&self) -> bool {
+        self.encode() == [0; 0x20]
+    }
+
+    fn pow(&self, exponent: &[u8; 0x20]) -> Self {
+        (0..exponent.len() * 8)
+            .rev()
+            .fold(Self::new(1), |power, bit| {
+                let power: Self = power * power;
+                if exponent[bit / 8] >> (bit % 8) & 1 != 0 {
+                    power * *self
+                } else {
+                    power
+                }
+            })
+    }
+}
+
+impl Add for FieldElement {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut limbs: [u64; 5] = self.limbs;
+        limbs
+            .iter_mut()
+            .zip(other.limbs.iter())
+            .for_each(|(limb, other)| *limb += other);
+        Self {
+            limbs,
+        }.carry()
+    }
+}
+
+impl Mul for FieldElement {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let a: [u128; 5] = self.limbs.map(|limb| limb as u128);
+        let b: [u128; 5] = other.limbs.map(|limb| limb as u128);
+        // 2^255 = 19 modulo p
+        let b19: [u128; 5] = b.map(|limb| 19 * limb);
+        let products: [u128; 5] = [
+            a[0] * b[0] + a[1] * b19[4] + a[2] * b19[3] + a[3] * b19[2] + a[4] * b19[1],
+            a[0] * b[1] + a[1] * b[0] + a[2] * b19[4] + a[3] * b19[3] + a[4] * b19[2],
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + a[3] * b19[4] + a[4] * b19[3],
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0] + a[4] * b19[4],
+            a[0] * b[4] + a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + a[4] * b[0],
+        ];
+        let mut limbs: [u64; 5] = [0; 5];
+        let mut carry: u128 = 0;
+        products
+            .iter()
+            .zip(limbs.iter_mut())
+            .for_each(|(product, limb)| {
+                let sum: u128 = product + carry;
+                *limb = sum as u64 & Self::LIMB_MASK;
+                carry = sum >> Self::LIMB_BITS;
+            });
+        limbs[0] += 19 * carry as u64;
+        Self {
+            limbs,
+        }.carry()
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(0) - self
+    }
+}
+
+impl Sub for FieldElement {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let mut limbs: [u64; 5] = self.limbs;
+        limbs
+            .iter_mut()
+            .zip(other.limbs.iter())
+            .zip(Self::TWO_P.iter())
+            .for_each(|((limb, other), two_p)| *limb = *limb + two_p - other);
+        Self {
+            limbs,
+        }.carry()
+    }
+}
+
+// A point on the twisted Edwards curve -x^2 + y^2 = 1 + d x^2 y^2 in extended coordinates.
+#[derive(Clone, Copy)]
+struct Point {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+    t: FieldElement,
+}
+
+impl Point {
+    fn identity() -> Self {
+        let x = FieldElement::new(0);
+        let y = FieldElement::new(1);
+        let z = FieldElement::new(1);
+        let t = FieldElement::new(0);
+        Self {
+            x,
+            y,
+            z,
+            t,
+        }
+    }
+
+    // Return None if the bytes don't encode a point.
+    fn decode(bytes: &[u8; 0x20]) -> Option<Self> {
+        let y = FieldElement::decode(bytes);
+        // The y coordinate must be less than p.
+        let mut y_bytes: [u8; 0x20] = *bytes;
+        y_bytes[0x1f] &= 0x7f;
+        if y.encode() != y_bytes {
+            return None;
+        }
+        let negative: bool = bytes[0x1f] & 0x80 != 0;
+        let one = FieldElement::new(1);
+        let y2: FieldElement = y * y;
+        let u: FieldElement = y2 - one;
+        let v: FieldElement = D * y2 + one;
+        let v3: FieldElement = v * v * v;
+        let v7: FieldElement = v3 * v3 * v;
+        let mut x: FieldElement = u * v3 * (u * v7).pow(&SQUARE_ROOT_EXPONENT);
+        let vx2: FieldElement = v * x * x;
+        if (vx2 - u).is_zero() {
+        } else if (vx2 + u).is_zero() {
+            x = x * SQUARE_ROOT_OF_MINUS_ONE;
+        } else {
+            return None;
+        }
+        if x.is_zero() && negative {
+            return None;
+        }
+        if x.is_negative() != negative {
+            x = -x;
+        }
+        let z = FieldElement::new(1);
+        let t: FieldElement = x * y;
+        Some(Self {
+            x,
+            y,
+            z,
+            t,
+        })
+    }
+
+    fn encode(&self) -> [u8; 0x20] {
+        let z_inverse: FieldElement = self.z.invert();
+        let x: FieldElement = self.x * z_inverse;
+        let y: FieldElement = self.y * z_inverse;
+        let mut bytes: [u8; 0x20] = y.encode();
+        if x.is_negative() {
+            bytes[0x1f] |= 0x80;
+        }
+        bytes
+    }
+
+    // Multiply by a little endian scalar.
+    fn multiply(&self, scalar: &[u8; 0x20]) -> Self {
+        (0..scalar.len() * 8)
+            .rev()
+            .fold(Self::identity(), |product, bit| {
+                let product: Self = product + product;
+                if scalar[bit / 8] >> (bit % 8) & 1 != 0 {
+                    product + *self
+                } else {
+                    product
+                }
+            })
+    }
+}
+
+// The unified addition formula also doubles a point.
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let two = FieldElement::new(2);
+        let a: FieldElement = (self.y - self.x) * (other.y - other.x);
+        let b: FieldElement = (self.y + self.x) * (other.y + other.x);
+        let c: FieldElement = self.t * D2 * other.t;
+        let d: FieldElement = self.z * two * other.z;
+        let e: FieldElement = b - a;
+        let f: FieldElement = d - c;
+        let g: FieldElement = d + c;
+        let h: FieldElement = b + a;
+        let x: FieldElement = e * f;
+        let y: FieldElement = g * h;
+        let z: FieldElement = f * g;
+        let t: FieldElement = e * h;
+        Self {
+            x,
+            y,
+            z,
+            t,
+        }
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        let x: FieldElement = -self.x;
+        let y: FieldElement = self.y;
+        let z: FieldElement = self.z;
+        let t: FieldElement = -self.t;
+        Self {
+            x,
+            y,
+            z,
+            t,
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// References
+// https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf 6.4 SHA-512
+
+const BLOCK_SIZE: usize = 0x80;
+const INITIAL_HASH: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+const ROUND_CONSTANTS: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+// The digest of the concatenated messages.
+pub fn digest(messages: &[&[u8]]) -> [u8; 64] {
+    let length: usize = messages
+        .iter()
+        .map(|message| message.len())
+        .sum();
+    // Append 0x80, zeros and the length in bits so that the padded message consists of whole blocks.
+    let padding_length: usize = BLOCK_SIZE - (length + 1 + 0x10) % BLOCK_SIZE;
+    let padding_length: usize = padding_length % BLOCK_SIZE;
+    let bit_length: u128 = (length as u128) << 3;
+    let mut padding: [u8; 1 + BLOCK_SIZE + 0x10] = [0; 1 + BLOCK_SIZE + 0x10];
+    padding[0] = 0x80;
+    padding[1 + padding_length..1 + padding_length + 0x10].copy_from_slice(&bit_length.to_be_bytes());
+    let padding: &[u8] = &padding[..1 + padding_length + 0x10];
+    let mut hash: [u64; 8] = INITIAL_HASH;
+    let mut block: [u8; BLOCK_SIZE] = [0; BLOCK_SIZE];
+    let mut filled: usize = 0;
+    messages
+        .iter()
+        .chain([padding].iter())
+        .flat_map(|message| message.iter())
+        .for_each(|byte| {
+            block[filled] = *byte;
+            filled += 1;
+            if filled == BLOCK_SIZE {
+                compress(&mut hash, &block);
+                filled = 0;
+            }
+        });
+    let mut digest: [u8; 64] = [0; 64];
+    digest
+        .chunks_mut(8)
+        .zip(hash.iter())
+        .for_each(|(bytes, word)| bytes.copy_from_slice(&word.to_be_bytes()));
+    digest
+}
+
+fn compress(hash: &mut [u64; 8], block: &[u8; BLOCK_SIZE]) {
+    let mut schedule: [u64; 80] = [0; 80];
+    block
+        .chunks(8)
+        .enumerate()
+        .for_each(|(index, word)| schedule[index] = u64::from_be_bytes(word
+            .try_into()
+            .expect("Can't read a SHA-512 block!")));
+    (16..schedule.len()).for_each(|index| {
+        let sigma0: u64 = schedule[index - 15].rotate_right(1) ^ schedule[index - 15].rotate_right(8) ^ schedule[index - 15] >> 7;
+        let sigma1: u64 = schedule[index - 2].rotate_right(19) ^ schedule[index - 2].rotate_right(61) ^ schedule[index - 2] >> 6;
+        schedule[index] = schedule[index - 16]
+            .wrapping_add(sigma0)
+            .wrapping_add(schedule[index - 7])
+            .wrapping_add(sigma1);
+    });
+    let mut working: [u64; 8] = *hash;
+    schedule
+        .iter()
+        .zip(ROUND_CONSTANTS.iter())
+        .for_each(|(word, constant)| {
+            let [a, b, c, d, e, f, g, h]: [u64; 8] = working;
+            let sum1: u64 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+            let choose: u64 = (e & f) ^ (!e & g);
+            let temporary1: u64 = h
+                .wrapping_add(sum1)
+                .wrapping_add(choose)
+                .wrapping_add(*constant)
+                .wrapping_add(*word);
+            let sum0: u64 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+            let majority: u64 = (a & b) ^ (a & c) ^ (b & c);
+            let temporary2: u64 = sum0.wrapping_add(majority);
+            working = [temporary1.wrapping_add(temporary2), a, b, c, d.wrapping_add(temporary1), e, f, g];
+        });
+    hash
+        .iter_mut()
+        .zip(working.iter())
+        .for_each(|(hash, working)| *hash = hash.wrapping_add(*working));
+}
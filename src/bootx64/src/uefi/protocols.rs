@@ -13,11 +13,15 @@
 // 19 Compression Algorithm Specification
 // 20 ACPI Protocols
 // 21 String Services
+// 24 Network Protocols - SNP, PXE, BIS and HTTP Boot
 // 34 HII (Human INterface Infrastructure) Protocols
+// 37 Secure Technologies
 
 pub mod console_support;
 pub mod efi_loaded_image;
 pub mod human_interface_infrastructure;
 pub mod device_path;
 pub mod media_access;
+pub mod network;
+pub mod random_number_generator;
 
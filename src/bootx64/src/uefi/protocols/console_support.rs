@@ -2,6 +2,7 @@
 // https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
 // 12 Console Support
 
+pub mod edid_active;
 pub mod graphics_output;
 pub mod simple_text_input;
 pub mod simple_text_output;
@@ -0,0 +1,95 @@
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 12.9.2.5 EDID Active Protocol
+// https://en.wikipedia.org/wiki/Extended_Display_Identification_Data
+
+use {
+    core::slice,
+    super::super::super::{
+        services::boot::protocol_handler,
+        tables::system,
+        types::void,
+    },
+};
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+const EDID_LENGTH: usize = 0x80;
+// The first detailed timing descriptor describes the preferred timing, that is the native resolution of the panel.
+const PREFERRED_TIMING: usize = 0x36;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct EdidActive<'a> {
+    size_of_edid: u32,
+    edid: &'a u8,
+}
+
+impl EdidActive<'_> {
+    // Return None if the firmware doesn't provide the protocol.
+    pub fn new() -> Option<&'static Self> {
+        let guid = protocol_handler::Guid::new(
+            0xbd8c1056,
+            0x9f36,
+            0x44ec,
+            [
+                0x92,
+                0xa8,
+                0xa6,
+                0x33,
+                0x7f,
+                0x81,
+                0x79,
+                0x86
+            ],
+        );
+        let edid_active = void::Void::new();
+        let mut edid_active: &void::Void = &edid_active;
+        system::system()
+            .boot_services
+            .locate_protocol(
+                &guid,
+                void::Void::null(),
+                &mut edid_active,
+            )
+            .ok()?;
+        let edid_active: *const void::Void = &*edid_active;
+        let edid_active: usize = edid_active as usize;
+        let edid_active: *const Self = edid_active as *const Self;
+        Some(unsafe {
+            &*edid_active
+        })
+    }
+
+    // Horizontal and vertical resolutions of the preferred timing.
+    pub fn native_resolution(&self) -> Option<(u32, u32)> {
+        let edid: &[u8] = self.edid()?;
+        let timing: &[u8] = &edid[PREFERRED_TIMING..PREFERRED_TIMING + 0x12];
+        let pixel_clock: u16 = u16::from_le_bytes([timing[0x00], timing[0x01]]);
+        // A display descriptor instead of a detailed timing descriptor.
+        if pixel_clock == 0 {
+            return None;
+        }
+        let horizontal_resolution: u32 = timing[0x02] as u32 | ((timing[0x04] as u32 & 0xf0) << 4);
+        let vertical_resolution: u32 = timing[0x05] as u32 | ((timing[0x07] as u32 & 0xf0) << 4);
+        Some((horizontal_resolution, vertical_resolution))
+    }
+
+    // Return the base EDID block if its header and checksum are valid.
+    fn edid(&self) -> Option<&[u8]> {
+        let size_of_edid: usize = self.size_of_edid as usize;
+        if size_of_edid < EDID_LENGTH {
+            return None;
+        }
+        let edid: &[u8] = unsafe {
+            slice::from_raw_parts(self.edid, EDID_LENGTH)
+        };
+        let checksum: u8 = edid
+            .iter()
+            .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        if edid[..EDID_HEADER.len()] == EDID_HEADER && checksum == 0 {
+            Some(edid)
+        } else {
+            None
+        }
+    }
+}
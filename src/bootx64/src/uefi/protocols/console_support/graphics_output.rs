@@ -3,6 +3,7 @@
 // 12.9 Graphics Output Protocol
 
 use {
+    alloc::vec::Vec,
     super::super::super::{
         services::boot::{
             memory_allocation,
@@ -60,6 +61,60 @@ impl GraphicsOutput<'_> {
             &*graphics_output
         }
     }
+
+    pub fn frame_buffer_base(&self) -> memory_allocation::PhysicalAddress {
+        self.mode.frame_buffer_base
+    }
+
+    pub fn frame_buffer_size(&self) -> usize {
+        self.mode.frame_buffer_size
+    }
+
+    // The information of the current mode.
+    pub fn information(&self) -> &ModeInformation {
+        self.mode.info
+    }
+
+    // Pairs of a mode number and its information.
+    pub fn modes(&self) -> Vec<(u32, &ModeInformation)> {
+        (0..self.mode.max_mode)
+            .filter_map(|mode_number| self
+                .query_mode(mode_number)
+                .map(|information| (mode_number, information)))
+            .collect()
+    }
+
+    pub fn mode_number(&self) -> u32 {
+        self.mode.mode
+    }
+
+    // Return whether a mode with the resolution and a frame buffer is set.
+    pub fn set_resolution(&self, horizontal_resolution: u32, vertical_resolution: u32) -> bool {
+        (0..self.mode.max_mode)
+            .find(|mode_number| match self.query_mode(*mode_number) {
+                Some(information) => information.horizontal_resolution == horizontal_resolution
+                    && information.vertical_resolution == vertical_resolution
+                    && !matches!(information.pixel_format, PixelFormat::PixelBltOnly),
+                None => false,
+            })
+            .map_or(false, |mode_number| self.set_mode.0(self, mode_number) == status::SUCCESS)
+    }
+
+    fn query_mode(&self, mode_number: u32) -> Option<&ModeInformation> {
+        let size_of_info: usize = 0;
+        let information = void::Void::new();
+        let information: &void::Void = &information;
+        let information: *const void::Void = &*information;
+        let information: usize = information as usize;
+        let information: *const ModeInformation = information as *const ModeInformation;
+        let mut information: &ModeInformation = unsafe {
+            &*information
+        };
+        match self.query_mode.0(self, mode_number, &size_of_info, &mut information) {
+            status::SUCCESS => Some(information),
+            _ => None,
+        }
+    }
 }
 
 #[derive(WrappedFunction)]
@@ -77,6 +132,35 @@ pub struct ModeInformation {
     pixels_per_scan_line: u32,
 }
 
+impl ModeInformation {
+    pub fn horizontal_resolution(&self) -> u32 {
+        self.horizontal_resolution
+    }
+
+    pub fn pixel_bit_mask(&self) -> &PixelBitMask {
+        &self.pixel_information
+    }
+
+    // EFI_GRAPHICS_PIXEL_FORMAT
+    pub fn pixel_format(&self) -> u32 {
+        match self.pixel_format {
+            PixelFormat::RedGreenBlueReserved8BitPerColor => 0,
+            PixelFormat::BlueGreenRedReserved8BitPerColor => 1,
+            PixelFormat::PixelBitMask => 2,
+            PixelFormat::PixelBltOnly => 3,
+            PixelFormat::PixelFormatMax => 4,
+        }
+    }
+
+    pub fn pixels_per_scan_line(&self) -> u32 {
+        self.pixels_per_scan_line
+    }
+
+    pub fn vertical_resolution(&self) -> u32 {
+        self.vertical_resolution
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 #[repr(C)]
@@ -97,6 +181,24 @@ pub struct PixelBitMask {
     reserved_mask: u32,
 }
 
+impl PixelBitMask {
+    pub fn red_mask(&self) -> u32 {
+        self.red_mask
+    }
+
+    pub fn green_mask(&self) -> u32 {
+        self.green_mask
+    }
+
+    pub fn blue_mask(&self) -> u32 {
+        self.blue_mask
+    }
+
+    pub fn reserved_mask(&self) -> u32 {
+        self.reserved_mask
+    }
+}
+
 #[derive(WrappedFunction)]
 #[repr(C)]
 struct SetMode(pub extern "efiapi" fn(&GraphicsOutput, u32) -> status::Status);
@@ -136,6 +238,7 @@ pub enum BltOperation {
 #[repr(C)]
 pub struct Mode<'a> {
     max_mode: u32,
+    mode: u32,
     info: &'a ModeInformation,
     size_of_info: usize,
     frame_buffer_base: memory_allocation::PhysicalAddress,
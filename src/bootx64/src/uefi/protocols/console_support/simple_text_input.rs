@@ -17,6 +17,27 @@ pub struct SimpleTextInput<'a> {
     wait_for_key: event::Event<'a>,
 }
 
+impl SimpleTextInput<'_> {
+    // Return None if no key is pressed.
+    pub fn read_key_stroke(&self) -> Option<InputKey> {
+        let mut input_key = InputKey {
+            scan_code: 0,
+            unicode_char: 0,
+        };
+        match self.read_key_stroke.0(self, &mut input_key) {
+            status::SUCCESS => Some(input_key),
+            _ => None,
+        }
+    }
+
+    pub fn reset(&self, extended_verification: bool) -> Result<(), status::Status> {
+        match self.reset.0(self, extended_verification) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+}
+
 #[derive(WrappedFunction)]
 #[repr(C)]
 struct InputReset(pub extern "efiapi" fn(&SimpleTextInput, bool) -> status::Status);
@@ -25,9 +46,30 @@ struct InputReset(pub extern "efiapi" fn(&SimpleTextInput, bool) -> status::Stat
 #[repr(C)]
 struct InputReadKey(pub extern "efiapi" fn(&SimpleTextInput, &mut InputKey) -> status::Status);
 
+#[derive(Debug)]
 #[repr(C)]
-struct InputKey {
+pub struct InputKey {
     scan_code: u16,
     unicode_char: u16,
 }
 
+// Scan codes
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// Table 107. EFI Scan Codes for EFI_SIMPLE_TEXT_INPUT_PROTOCOL
+pub const SCAN_UP: u16 = 0x0001;
+pub const SCAN_DOWN: u16 = 0x0002;
+pub const SCAN_ESC: u16 = 0x0017;
+
+impl InputKey {
+    pub fn scan_code(&self) -> u16 {
+        self.scan_code
+    }
+
+    pub fn unicode_char(&self) -> Option<char> {
+        match self.unicode_char {
+            0 => None,
+            unicode_char => char::from_u32(unicode_char as u32),
+        }
+    }
+}
+
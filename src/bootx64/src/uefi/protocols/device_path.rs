@@ -2,6 +2,23 @@
 // https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
 // 10 Device Path Protocol
 
+use {
+    alloc::vec::Vec,
+    core::{
+        iter,
+        ptr,
+        slice,
+    },
+    super::super::{
+        services::boot::protocol_handler,
+        tables::system,
+        types::{
+            handle,
+            void,
+        },
+    },
+};
+
 // References
 // https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
 // 10.2 Device Path Protocol
@@ -13,3 +30,156 @@ pub struct DevicePathProtocol {
     length: [u8; 2],
 }
 
+impl DevicePathProtocol {
+    // 10.3.1 Generic Device Path Structures
+    const END_OF_HARDWARE_DEVICE_PATH: u8 = 0x7f;
+    const END_ENTIRE_DEVICE_PATH: u8 = 0xff;
+    const HEADER_LENGTH: usize = 0x04;
+    // 10.3.5 Media Device Path
+    const MEDIA_DEVICE_PATH: u8 = 0x04;
+    const HARD_DRIVE: u8 = 0x01;
+    const FILE_PATH: u8 = 0x04;
+    const HARD_DRIVE_LENGTH: usize = 0x2a;
+    const PARTITION_SIGNATURE_OFFSET: usize = 0x18;
+    const SIGNATURE_TYPE_OFFSET: usize = 0x29;
+    const GUID_SIGNATURE: u8 = 0x02;
+
+    pub fn new<'a>(handle: handle::Handle) -> Option<&'a Self> {
+        let guid = protocol_handler::Guid::new(
+            0x09576e91,
+            0x6d3f,
+            0x11d2,
+            [
+                0x8e,
+                0x39,
+                0x00,
+                0xa0,
+                0xc9,
+                0x69,
+                0x72,
+                0x3b
+            ],
+        );
+        let device_path = void::Void::new();
+        let mut device_path: &void::Void = &device_path;
+        system::system()
+            .boot_services
+            .open_protocol(
+                handle,
+                &guid,
+                &mut device_path,
+                system::image(),
+                void::Void::null(),
+                protocol_handler::OPEN_PROTOCOL_BY_HANDLE_PROTOCOL,
+            )
+            .ok()?;
+        let device_path: *const void::Void = &*device_path;
+        let device_path: usize = device_path as usize;
+        let device_path: *const Self = device_path as *const Self;
+        Some(unsafe {
+            &*device_path
+        })
+    }
+
+    // Nodes are not aligned, so the bytes can be anywhere.
+    pub fn from_bytes(bytes: &[u8]) -> &Self {
+        let device_path: *const u8 = bytes.as_ptr();
+        let device_path: *const Self = device_path as *const Self;
+        unsafe {
+            &*device_path
+        }
+    }
+
+    // The bytes of the device path followed by a file path node of the path on the device.
+    // Return None if the device path is malformed.
+    pub fn append_file_path(&self, path: &str) -> Option<Vec<u8>> {
+        let path: Vec<u8> = path
+            .chars()
+            .map(|character| match character {
+                '/' => '\\',
+                character => character,
+            })
+            .flat_map(|character| {
+                let mut buffer: [u16; 2] = [0; 2];
+                character
+                    .encode_utf16(&mut buffer)
+                    .to_vec()
+            })
+            .chain(iter::once(0))
+            .flat_map(|character| character.to_le_bytes())
+            .collect();
+        let file_path_length: u16 = (Self::HEADER_LENGTH + path.len()) as u16;
+        let end_length: u16 = Self::HEADER_LENGTH as u16;
+        let mut device_path: Vec<u8> = self.bytes_before_end()?.to_vec();
+        device_path.extend([Self::MEDIA_DEVICE_PATH, Self::FILE_PATH]);
+        device_path.extend(file_path_length.to_le_bytes());
+        device_path.extend(path);
+        device_path.extend([Self::END_OF_HARDWARE_DEVICE_PATH, Self::END_ENTIRE_DEVICE_PATH]);
+        device_path.extend(end_length.to_le_bytes());
+        Some(device_path)
+    }
+
+    // The unique partition GUID of the GPT partition the device path ends at.
+    pub fn partition_guid(&self) -> Option<protocol_handler::Guid> {
+        let mut node: &Self = self;
+        while node.device_path_protocol_type != Self::END_OF_HARDWARE_DEVICE_PATH {
+            if node.device_path_protocol_type == Self::MEDIA_DEVICE_PATH
+                && node.device_path_protocol_subtype == Self::HARD_DRIVE
+                && Self::HARD_DRIVE_LENGTH <= node.length()
+                && node.byte(Self::SIGNATURE_TYPE_OFFSET) == Self::GUID_SIGNATURE {
+                let guid: *const Self = node as *const Self;
+                let guid: usize = guid as usize + Self::PARTITION_SIGNATURE_OFFSET;
+                let guid: *const protocol_handler::Guid = guid as *const protocol_handler::Guid;
+                // Device path nodes are not aligned.
+                return Some(unsafe {
+                    ptr::read_unaligned(guid)
+                });
+            }
+            node = node.next()?;
+        }
+        None
+    }
+
+    // Return None if the device path is malformed.
+    fn bytes_before_end(&self) -> Option<&[u8]> {
+        let mut node: &Self = self;
+        let mut length: usize = 0;
+        while node.device_path_protocol_type != Self::END_OF_HARDWARE_DEVICE_PATH {
+            length += node.length();
+            node = node.next()?;
+        }
+        let bytes: *const Self = self as *const Self;
+        let bytes: *const u8 = bytes as *const u8;
+        Some(unsafe {
+            slice::from_raw_parts(bytes, length)
+        })
+    }
+
+    fn byte(&self, offset: usize) -> u8 {
+        let byte: *const Self = self as *const Self;
+        let byte: usize = byte as usize + offset;
+        let byte: *const u8 = byte as *const u8;
+        unsafe {
+            *byte
+        }
+    }
+
+    fn length(&self) -> usize {
+        u16::from_le_bytes(self.length) as usize
+    }
+
+    // Return None if the device path is malformed.
+    fn next(&self) -> Option<&Self> {
+        match self.length() {
+            0..=3 => None,
+            length => {
+                let next: *const Self = self as *const Self;
+                let next: usize = next as usize + length;
+                let next: *const Self = next as *const Self;
+                Some(unsafe {
+                    &*next
+                })
+            },
+        }
+    }
+}
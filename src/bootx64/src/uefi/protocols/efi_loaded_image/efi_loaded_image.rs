@@ -3,6 +3,7 @@
 // 9.1 EFI Loaded Image Protocol
 
 use {
+    core::mem,
     super::super::{
         device_path,
         super::{
@@ -21,6 +22,23 @@ use {
     wrapped_function::WrappedFunction,
 };
 
+// EFI_LOADED_IMAGE_PROTOCOL_GUID
+const GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x5b1b31a1,
+    0x9562,
+    0x11d2,
+    [
+        0x8e,
+        0x3f,
+        0x00,
+        0xa0,
+        0xc9,
+        0x69,
+        0x72,
+        0x3b
+    ],
+);
+
 #[derive(Debug)]
 #[repr(C)]
 pub struct EfiLoadedImage<'a> {
@@ -41,45 +59,46 @@ pub struct EfiLoadedImage<'a> {
 
 impl<'a> EfiLoadedImage<'a> {
     pub fn new() -> &'a Self {
-        let guid = protocol_handler::Guid::new(
-            0x5b1b31a1,
-            0x9562,
-            0x11d2,
-            [
-                0x8e,
-                0x3f,
-                0x00,
-                0xa0,
-                0xc9,
-                0x69,
-                0x72,
-                0x3b
-            ],
-        );
+        Self::of(system::image()).expect("Can't get an EFI loaded image protocol!")
+    }
+
+    // The loaded image protocol of an image loaded by the loader.
+    pub fn of(image_handle: handle::Handle) -> Option<&'a mut Self> {
         let efi_loaded_image = void::Void::new();
         let mut efi_loaded_image: &void::Void = &efi_loaded_image;
         system::system()
             .boot_services
             .open_protocol(
-                system::image(),
-                &guid,
+                image_handle,
+                &GUID,
                 &mut efi_loaded_image,
                 system::image(),
                 void::Void::null(),
                 protocol_handler::OPEN_PROTOCOL_BY_HANDLE_PROTOCOL,
             )
-            .expect("Can't get an EFI loaded image protocol!");
+            .ok()?;
         let efi_loaded_image: *const void::Void = &*efi_loaded_image;
         let efi_loaded_image: usize = efi_loaded_image as usize;
-        let efi_loaded_image: *const Self = efi_loaded_image as *const Self;
-        unsafe {
-            &*efi_loaded_image
-        }
+        let efi_loaded_image: &mut void::Void = efi_loaded_image.into();
+        let efi_loaded_image: *mut void::Void = efi_loaded_image;
+        let efi_loaded_image: *mut Self = efi_loaded_image as *mut Self;
+        Some(unsafe {
+            &mut *efi_loaded_image
+        })
     }
 
     pub fn device_handle(&self) -> handle::Handle<'a> {
         self.device_handle
     }
+
+    // The load options must live until the image exits.
+    pub fn set_load_options(&mut self, load_options: &'a [u16]) {
+        let load_options_size: usize = load_options.len() * mem::size_of::<u16>();
+        let load_options: *const u16 = load_options.as_ptr();
+        let load_options: usize = load_options as usize;
+        self.load_options_size = load_options_size as u32;
+        self.load_options = load_options.into();
+    }
 }
 
 #[derive(WrappedFunction)]
@@ -7,6 +7,7 @@ use {
         collections::btree_map::BTreeMap,
         vec::Vec,
     },
+    core::ops::RangeInclusive,
     super::{
         database,
         font_ex,
@@ -32,12 +33,15 @@ use {
 pub struct Font {
     max_width: u16,
     max_height: u16,
+    // The width of a narrow glyph. A wide glyph is twice as wide.
+    narrow_width: u16,
     character2glyph: BTreeMap<char, Glyph>,
 }
 
 impl Font {
-    pub fn new() -> Self {
-        let characters: &str = "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+    // Characters the font doesn't have are skipped.
+    // Characters out of the basic multilingual plane are skipped because the font protocol takes UCS-2.
+    pub fn new(ranges: &[RangeInclusive<char>]) -> Self {
         let font_protocol = FontProtocol::new();
         let fonts: Vec<&font_ex::FontDisplayInfo> = font_protocol
             .iter()
@@ -46,9 +50,13 @@ impl Font {
             .into_iter()
             .next()
             .expect("Can't get a font!");
-        let character2glyph: BTreeMap<char, Glyph> = characters
-            .chars()
-            .map(|character| (character, font_protocol.get_glyph(font, character)))
+        let character2glyph: BTreeMap<char, Glyph> = ranges
+            .iter()
+            .cloned()
+            .flatten()
+            .filter_map(|character| font_protocol
+                .get_glyph(font, character)
+                .map(|glyph| (character, glyph)))
             .collect();
         let max_width: u16 = character2glyph
             .values()
@@ -60,19 +68,58 @@ impl Font {
             .map(|glyph| glyph.height)
             .max()
             .expect("Can't get font max height!");
+        let narrow_width: u16 = character2glyph
+            .values()
+            .map(|glyph| glyph.width)
+            .filter(|width| 0 < *width)
+            .min()
+            .expect("Can't get font narrow width!");
         Self {
             max_width,
             max_height,
+            narrow_width,
             character2glyph,
         }
     }
+
+    // Pairs of a character and its glyph sorted by the characters.
+    pub fn glyphs(&self) -> impl Iterator<Item = (&char, &Glyph)> {
+        self.character2glyph.iter()
+    }
+
+    pub fn max_height(&self) -> u16 {
+        self.max_height
+    }
+
+    pub fn max_width(&self) -> u16 {
+        self.max_width
+    }
+
+    pub fn narrow_width(&self) -> u16 {
+        self.narrow_width
+    }
 }
 
+// Each row begins at a byte boundary and the most significant bit is the leftmost pixel.
 #[derive(Debug)]
 pub struct Glyph {
     width: u16,
     height: u16,
-    image: BTreeMap<Coordinates, bool>,
+    rows: Vec<u8>,
+}
+
+impl Glyph {
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn rows(&self) -> &[u8] {
+        &self.rows
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
 }
 
 // EFI_HII_FONT_PROTOCOL
@@ -120,42 +167,62 @@ impl FontProtocol {
         }
     }
 
-    pub fn get_glyph(&self, font: &font_ex::FontDisplayInfo<'_>, character: char) -> Glyph {
+    // Return None if the font doesn't have the character.
+    pub fn get_glyph(&self, font: &font_ex::FontDisplayInfo<'_>, character: char) -> Option<Glyph> {
         let character: u32 = character as u32;
         let character: char16::Char16 = character
             .try_into()
-            .expect("Can't get a glyph");
-        let blt: usize = 0;
-        let blt: *const font_ex::ImageOutput = blt as *const font_ex::ImageOutput;
-        let mut blt: &font_ex::ImageOutput = unsafe {
-            &*blt
-        };
+            .ok()?;
+        let mut blt: &font_ex::ImageOutput = font_ex::ImageOutput::null();
         let baseline: usize = 0;
         let baseline: *mut usize = baseline as *mut usize;
         let baseline: &mut usize = unsafe {
             &mut *baseline
         };
-        match self.get_glyph.0(
+        let status: status::Status = self.get_glyph.0(
             self,
             character,
             font,
             &mut blt,
             baseline,
-        ) {
-            status::SUCCESS => (),
-            _ => panic!("Can't get a glyph!"),
-        };
+        );
+        let blt_address: *const font_ex::ImageOutput = blt as *const font_ex::ImageOutput;
+        let blt_address: usize = blt_address as usize;
+        if blt_address == 0 {
+            return None;
+        }
         let width: u16 = blt.width();
         let height: u16 = blt.height();
         let bitmap: &[graphics_output::BltPixel] = blt.bitmap();
-        let image: BTreeMap<Coordinates, bool> = (0..width)
-            .flat_map(|x| (0..height)
-                .map(move |y| (Coordinates::new(x, y), bitmap.get((x + y * width) as usize) == Some(font.foreground_color()))))
+        let row_bytes: usize = (width as usize + 7) / 8;
+        let mut rows: Vec<u8> = (0..row_bytes * height as usize)
+            .map(|_| 0)
             .collect();
-        Glyph {
-            width,
-            height,
-            image,
+        (0..height)
+            .flat_map(|y| (0..width)
+                .map(move |x| (x, y)))
+            .filter(|(x, y)| bitmap.get((x + y * width) as usize) == Some(font.foreground_color()))
+            .for_each(|(x, y)| rows[y as usize * row_bytes + x as usize / 8] |= 0x80 >> (x % 8));
+        // The caller frees the image output allocated by the firmware.
+        let boot_services = &system::system().boot_services;
+        if !bitmap.is_empty() {
+            let bitmap: *const graphics_output::BltPixel = bitmap.as_ptr();
+            let bitmap: usize = bitmap as usize;
+            boot_services
+                .free_pool(bitmap.into())
+                .expect("Can't free a glyph!");
+        }
+        boot_services
+            .free_pool(blt_address.into())
+            .expect("Can't free a glyph!");
+        // The firmware returns a replacement glyph with a warning for an unknown character.
+        match status {
+            status::SUCCESS => Some(Glyph {
+                width,
+                height,
+                rows,
+            }),
+            _ => None,
         }
     }
 
@@ -237,19 +304,3 @@ pub struct RowInfo {
     line_width: usize,
     base_line_offset: usize,
 }
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Coordinates {
-    x: u16,
-    y: u16,
-}
-
-impl Coordinates {
-    pub fn new(x: u16, y: u16) -> Self {
-        Self {
-            x,
-            y,
-        }
-    }
-}
-
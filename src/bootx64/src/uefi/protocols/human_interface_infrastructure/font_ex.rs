@@ -94,7 +94,15 @@ pub struct ImageOutput<'a> {
     bitmap_or_screen: ImageOutputUnion<'a>,
 }
 
-impl ImageOutput<'_> {
+impl<'a> ImageOutput<'a> {
+    pub fn null() -> &'a Self {
+        let null: usize = 0;
+        let null: *const Self = null as *const Self;
+        unsafe {
+            &*null
+        }
+    }
+
     pub fn width(&self) -> u16 {
         self.width
     }
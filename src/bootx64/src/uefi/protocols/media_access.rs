@@ -2,6 +2,8 @@
 // https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
 // 13 Media Access
 
+pub mod block_io;
+pub mod disk_io;
 pub mod file_protocol;
 pub mod simple_file_system;
 
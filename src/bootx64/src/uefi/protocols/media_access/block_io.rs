@@ -0,0 +1,171 @@
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 13.9 Block I/O Protocol
+
+use {
+    alloc::vec::Vec,
+    super::super::super::{
+        services::boot::protocol_handler,
+        super::allocator,
+        tables::system,
+        types::{
+            handle,
+            status,
+            void,
+        },
+    },
+    wrapped_function::WrappedFunction,
+};
+
+// EFI_BLOCK_IO_PROTOCOL_GUID
+const GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x964e5b21,
+    0x6459,
+    0x11d2,
+    [
+        0x8e,
+        0x39,
+        0x00,
+        0xa0,
+        0xc9,
+        0x69,
+        0x72,
+        0x3b
+    ],
+);
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct BlockIo<'a> {
+    revision: u64,
+    media: &'a BlockIoMedia,
+    reset: BlockReset,
+    read_blocks: BlockRead,
+    write_blocks: BlockWrite,
+    flush_blocks: BlockFlush,
+}
+
+impl<'a> BlockIo<'a> {
+    // Handles of all block devices including partitions.
+    pub fn handles() -> Vec<handle::Handle<'static>> {
+        system::system()
+            .boot_services
+            .locate_handle_buffer(protocol_handler::LocateSearchType::ByProtocol, &GUID)
+            .unwrap_or_default()
+    }
+
+    pub fn of(handle: handle::Handle) -> Option<&'a Self> {
+        let block_io = void::Void::new();
+        let mut block_io: &void::Void = &block_io;
+        system::system()
+            .boot_services
+            .open_protocol(
+                handle,
+                &GUID,
+                &mut block_io,
+                system::image(),
+                void::Void::null(),
+                protocol_handler::OPEN_PROTOCOL_BY_HANDLE_PROTOCOL,
+            )
+            .ok()?;
+        let block_io: *const void::Void = &*block_io;
+        let block_io: usize = block_io as usize;
+        let block_io: *const Self = block_io as *const Self;
+        Some(unsafe {
+            &*block_io
+        })
+    }
+
+    pub fn media(&self) -> &'a BlockIoMedia {
+        self.media
+    }
+
+    // Read blocks into a buffer aligned as the device requires.
+    pub fn read_blocks(&self, lba: u64, blocks: usize) -> Result<Vec<u8>, status::Status> {
+        let buffer_size: usize = blocks * self.media.block_size as usize;
+        if buffer_size == 0 {
+            return Ok(Vec::new());
+        }
+        let mut allocated = allocator::Allocated::new(buffer_size, self.media.io_align as usize);
+        let buffer: &mut [u8] = allocated.get_mut();
+        let buffer: &mut u8 = &mut buffer[0];
+        let buffer: *mut u8 = &mut *buffer;
+        let buffer: usize = buffer as usize;
+        let buffer: &mut void::Void = buffer.into();
+        match self.read_blocks.0(
+            self,
+            self.media.media_id,
+            lba,
+            buffer_size,
+            buffer,
+        ) {
+            status::SUCCESS => Ok(allocated.get_ref().to_vec()),
+            error => Err(error),
+        }
+    }
+}
+
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 13.9 Block I/O Protocol
+// EFI_BLOCK_IO_MEDIA
+#[derive(Debug)]
+#[repr(C)]
+pub struct BlockIoMedia {
+    media_id: u32,
+    removable_media: bool,
+    media_present: bool,
+    logical_partition: bool,
+    read_only: bool,
+    write_caching: bool,
+    block_size: u32,
+    io_align: u32,
+    last_block: u64,
+}
+
+impl BlockIoMedia {
+    pub fn block_size(&self) -> u32 {
+        self.block_size
+    }
+
+    pub fn last_block(&self) -> u64 {
+        self.last_block
+    }
+
+    // A partition of a disk rather than a whole disk.
+    pub fn logical_partition(&self) -> bool {
+        self.logical_partition
+    }
+
+    pub fn media_id(&self) -> u32 {
+        self.media_id
+    }
+
+    pub fn media_present(&self) -> bool {
+        self.media_present
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn removable_media(&self) -> bool {
+        self.removable_media
+    }
+}
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct BlockReset(pub extern "efiapi" fn(&BlockIo, bool) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct BlockRead(pub extern "efiapi" fn(&BlockIo, u32, u64, usize, &mut void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct BlockWrite(pub extern "efiapi" fn(&BlockIo, u32, u64, usize, &void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct BlockFlush(pub extern "efiapi" fn(&BlockIo) -> status::Status);
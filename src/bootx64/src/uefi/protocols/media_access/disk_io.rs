@@ -0,0 +1,99 @@
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 13.7 Disk I/O Protocol
+
+use {
+    alloc::vec::Vec,
+    super::super::super::{
+        services::boot::protocol_handler,
+        tables::system,
+        types::{
+            handle,
+            status,
+            void,
+        },
+    },
+    wrapped_function::WrappedFunction,
+};
+
+// EFI_DISK_IO_PROTOCOL_GUID
+const GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0xce345171,
+    0xba0b,
+    0x11d2,
+    [
+        0x8e,
+        0x4f,
+        0x00,
+        0xa0,
+        0xc9,
+        0x69,
+        0x72,
+        0x3b
+    ],
+);
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct DiskIo {
+    revision: u64,
+    read_disk: DiskRead,
+    write_disk: DiskWrite,
+}
+
+impl DiskIo {
+    // The Disk I/O protocol is produced on every handle with the Block I/O protocol.
+    pub fn of<'a>(handle: handle::Handle) -> Option<&'a Self> {
+        let disk_io = void::Void::new();
+        let mut disk_io: &void::Void = &disk_io;
+        system::system()
+            .boot_services
+            .open_protocol(
+                handle,
+                &GUID,
+                &mut disk_io,
+                system::image(),
+                void::Void::null(),
+                protocol_handler::OPEN_PROTOCOL_BY_HANDLE_PROTOCOL,
+            )
+            .ok()?;
+        let disk_io: *const void::Void = &*disk_io;
+        let disk_io: usize = disk_io as usize;
+        let disk_io: *const Self = disk_io as *const Self;
+        Some(unsafe {
+            &*disk_io
+        })
+    }
+
+    // Read bytes at any offset without alignment restrictions.
+    pub fn read(&self, media_id: u32, offset: u64, length: usize) -> Result<Vec<u8>, status::Status> {
+        let mut buffer: Vec<u8> = (0..length)
+            .map(|_| 0)
+            .collect();
+        if length == 0 {
+            return Ok(buffer);
+        }
+        let buffer_pointer: &mut u8 = &mut buffer[0];
+        let buffer_pointer: *mut u8 = &mut *buffer_pointer;
+        let buffer_pointer: usize = buffer_pointer as usize;
+        let buffer_pointer: &mut void::Void = buffer_pointer.into();
+        match self.read_disk.0(
+            self,
+            media_id,
+            offset,
+            length,
+            buffer_pointer,
+        ) {
+            status::SUCCESS => Ok(buffer),
+            error => Err(error),
+        }
+    }
+}
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct DiskRead(pub extern "efiapi" fn(&DiskIo, u32, u64, usize, &mut void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct DiskWrite(pub extern "efiapi" fn(&DiskIo, u32, u64, usize, &void::Void) -> status::Status);
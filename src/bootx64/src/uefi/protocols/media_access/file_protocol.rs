@@ -7,7 +7,11 @@ use {
         string::String,
         vec::Vec,
     },
-    core::fmt,
+    core::{
+        fmt,
+        iter,
+        mem,
+    },
     super::{
         simple_file_system,
         super::super::{
@@ -48,6 +52,94 @@ pub struct FileProtocol {
 }
 
 impl FileProtocol {
+    // The file is closed and the protocol must not be used after that.
+    pub fn delete(&self) -> Result<(), status::Status> {
+        match self.delete.0(self) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    // Return None if the file system doesn't provide EFI_FILE_SYSTEM_INFO.
+    pub fn file_system_information<'a>(&self) -> Option<FileSystemInformation<'a>> {
+        let guid = protocol_handler::Guid::new(
+            0x09576e93,
+            0x6d3f,
+            0x11d2,
+            [
+                0x8e,
+                0x39,
+                0x00,
+                0xa0,
+                0xc9,
+                0x69,
+                0x72,
+                0x3b
+            ],
+        );
+        self.get_info(&guid).map(|allocated| allocated.into())
+    }
+
+    pub fn flush(&self) -> Result<(), status::Status> {
+        match self.flush.0(self) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    // Return None if the file doesn't provide EFI_FILE_INFO.
+    pub fn information<'a>(&self) -> Option<FileInformation<'a>> {
+        let guid = protocol_handler::Guid::new(
+            0x09576e92,
+            0x6d3f,
+            0x11d2,
+            [
+                0x8e,
+                0x39,
+                0x00,
+                0xa0,
+                0xc9,
+                0x69,
+                0x72,
+                0x3b
+            ],
+        );
+        self.get_info(&guid).map(|allocated| allocated.into())
+    }
+
+    pub fn open(
+        &self,
+        name: &str,
+        open_mode: &OpenMode,
+        attributes: &Attributes
+    ) -> Result<&Self, status::Status> {
+        let file_protocol = void::Void::new();
+        let file_protocol: &void::Void = &file_protocol;
+        let file_protocol: *const void::Void = &*file_protocol;
+        let file_protocol: usize = file_protocol as usize;
+        let file_protocol: *const Self = file_protocol as *const Self;
+        let mut file_protocol: &Self = unsafe {
+            &*file_protocol
+        };
+        let file_name: Vec<char16::Char16> = name
+            .encode_utf16()
+            .chain(iter::once(0))
+            .collect();
+        let file_name = char16::String::new(&file_name[0]);
+        let open_mode: u64 = open_mode.into();
+        let attributes: u64 = attributes.into();
+        match self.open.0(
+            self,
+            &mut file_protocol,
+            file_name,
+            open_mode,
+            attributes,
+        ) {
+            status::SUCCESS => Ok(file_protocol),
+            error => Err(error),
+        }
+    }
+
     pub fn open_child(
         &self,
         file_information: &FileInformation,
@@ -101,6 +193,97 @@ impl FileProtocol {
         }
         buffer.to_vec()
     }
+
+    // Reading a directory starts from the first entry again.
+    pub fn rewind(&self) -> Result<(), status::Status> {
+        match self.set_position.0(self, 0) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    // Return None if the file system doesn't provide EFI_FILE_SYSTEM_VOLUME_LABEL.
+    pub fn volume_label(&self) -> Option<String> {
+        let guid = protocol_handler::Guid::new(
+            0xdb47d7d3,
+            0xfe81,
+            0x11d3,
+            [
+                0x9a,
+                0x35,
+                0x00,
+                0x90,
+                0x27,
+                0x3f,
+                0xc1,
+                0x4d
+            ],
+        );
+        let allocated: allocator::Allocated = self.get_info(&guid)?;
+        let volume_label: &[u8] = allocated.get_ref();
+        let volume_label: &u8 = &volume_label[0];
+        let volume_label: *const u8 = &*volume_label;
+        let volume_label: *const char16::Char16 = volume_label as *const char16::Char16;
+        let volume_label: &char16::Char16 = unsafe {
+            &*volume_label
+        };
+        Some(char16::String::new(volume_label).into())
+    }
+
+    // Return the number of written bytes.
+    pub fn write(&self, bytes: &[u8]) -> Result<usize, status::Status> {
+        let mut buffer_size: usize = bytes.len();
+        let buffer: &u8 = match bytes.first() {
+            Some(buffer) => buffer,
+            None => return Ok(0),
+        };
+        let buffer: *const u8 = &*buffer;
+        let buffer: usize = buffer as usize;
+        let buffer: &void::Void = buffer.into();
+        match self.write.0(
+            self,
+            &mut buffer_size,
+            buffer,
+        ) {
+            status::SUCCESS => Ok(buffer_size),
+            error => Err(error),
+        }
+    }
+
+    // The first call gets the size of the information and the second call gets the information.
+    fn get_info<'a>(&self, guid: &protocol_handler::Guid) -> Option<allocator::Allocated<'a>> {
+        let mut buffer = void::Void::new();
+        let mut buffer_size: usize = 0;
+        self.get_info.0(
+            self,
+            guid,
+            &mut buffer_size,
+            &mut buffer,
+        );
+        match buffer_size {
+            0 => None,
+            mut buffer_size => {
+                let mut allocated = allocator::Allocated::new(buffer_size, mem::align_of::<u64>());
+                let buffer: &mut [u8] = allocated.get_mut();
+                let buffer: &mut u8 = &mut buffer[0];
+                let buffer: *mut u8 = &mut *buffer;
+                let buffer: usize = buffer as usize;
+                let buffer: *mut void::Void = buffer as *mut void::Void;
+                let buffer: &mut void::Void = unsafe {
+                    &mut *buffer
+                };
+                match self.get_info.0(
+                    self,
+                    guid,
+                    &mut buffer_size,
+                    buffer,
+                ) {
+                    status::SUCCESS => Some(allocated),
+                    _ => None,
+                }
+            },
+        }
+    }
 }
 
 impl<'a> Iterator for &'a FileProtocol {
@@ -134,18 +317,7 @@ impl<'a> Iterator for &'a FileProtocol {
                     status::SUCCESS => (),
                     _ => panic!("Can't read a file protocol!"),
                 }
-                let buffer: *const void::Void = &*buffer;
-                let buffer: usize = buffer as usize;
-                let file_info: *const FileInfo = buffer as *const FileInfo;
-                let file_info: &FileInfo = unsafe {
-                    &*file_info
-                };
-                let file_name: String = char16::String::new(&(file_info.file_name)).into();
-                Some(FileInformation {
-                    allocated,
-                    file_name,
-                    file_info,
-                })
+                Some(allocated.into())
             },
         }
     }
@@ -275,7 +447,7 @@ struct FileRead(pub extern "efiapi" fn(&FileProtocol, &mut usize, &mut void::Voi
 
 #[derive(WrappedFunction)]
 #[repr(C)]
-struct FileWrite(pub extern "efiapi" fn(&FileProtocol, &usize, &void::Void) -> status::Status);
+struct FileWrite(pub extern "efiapi" fn(&FileProtocol, &mut usize, &void::Void) -> status::Status);
 
 #[derive(WrappedFunction)]
 #[repr(C)]
@@ -343,6 +515,8 @@ pub struct FileInformation<'a> {
 }
 
 impl FileInformation<'_> {
+    const DIRECTORY: u64 = 0x0000000000000010;
+
     pub fn file_name(&self) -> String {
         self.file_name.clone()
     }
@@ -350,6 +524,28 @@ impl FileInformation<'_> {
     pub fn file_size(&self) -> usize {
         self.file_info.file_size as usize
     }
+
+    pub fn is_directory(&self) -> bool {
+        self.file_info.attribute & Self::DIRECTORY != 0
+    }
+}
+
+impl<'a> From<allocator::Allocated<'a>> for FileInformation<'a> {
+    fn from(allocated: allocator::Allocated<'a>) -> Self {
+        let file_info: &[u8] = allocated.get_ref();
+        let file_info: &u8 = &file_info[0];
+        let file_info: *const u8 = &*file_info;
+        let file_info: *const FileInfo = file_info as *const FileInfo;
+        let file_info: &FileInfo = unsafe {
+            &*file_info
+        };
+        let file_name: String = char16::String::new(&(file_info.file_name)).into();
+        Self {
+            allocated,
+            file_name,
+            file_info,
+        }
+    }
 }
 
 impl<'a> fmt::Debug for FileInformation<'a> {
@@ -362,16 +558,86 @@ impl<'a> fmt::Debug for FileInformation<'a> {
     }
 }
 
+// EFI_FILE_SYSTEM_INFO
+#[derive(Debug)]
+#[repr(C)]
+pub struct FileSystemInfo {
+    size: u64,
+    read_only: bool,
+    volume_size: u64,
+    free_space: u64,
+    block_size: u32,
+    volume_label: u16,
+}
+
+#[allow(dead_code)]
+pub struct FileSystemInformation<'a> {
+    allocated: allocator::Allocated<'a>,
+    volume_label: String,
+    file_system_info: &'a FileSystemInfo,
+}
+
+impl FileSystemInformation<'_> {
+    pub fn block_size(&self) -> u32 {
+        self.file_system_info.block_size
+    }
+
+    pub fn free_space(&self) -> u64 {
+        self.file_system_info.free_space
+    }
+
+    pub fn read_only(&self) -> bool {
+        self.file_system_info.read_only
+    }
+
+    pub fn volume_label(&self) -> String {
+        self.volume_label.clone()
+    }
+
+    pub fn volume_size(&self) -> u64 {
+        self.file_system_info.volume_size
+    }
+}
+
+impl<'a> From<allocator::Allocated<'a>> for FileSystemInformation<'a> {
+    fn from(allocated: allocator::Allocated<'a>) -> Self {
+        let file_system_info: &[u8] = allocated.get_ref();
+        let file_system_info: &u8 = &file_system_info[0];
+        let file_system_info: *const u8 = &*file_system_info;
+        let file_system_info: *const FileSystemInfo = file_system_info as *const FileSystemInfo;
+        let file_system_info: &FileSystemInfo = unsafe {
+            &*file_system_info
+        };
+        let volume_label: String = char16::String::new(&(file_system_info.volume_label)).into();
+        Self {
+            allocated,
+            volume_label,
+            file_system_info,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for FileSystemInformation<'a> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("FileSystemInformation")
+            .field("volume_label", &self.volume_label)
+            .field("file_system_info", &self.file_system_info)
+            .finish()
+    }
+}
+
 pub struct Node<'a> {
     information: FileInformation<'a>,
     protocol: &'a FileProtocol,
 }
 
-impl Node<'_> {
-    pub fn child(mut self, name: &str) -> Self {
-        let information: FileInformation = self.protocol
-            .find(|information| information.file_name().as_str() == name)
-            .expect("A child is nou found!");
+impl<'a> Node<'a> {
+    pub fn child(self, name: &str) -> Option<Self> {
+        let information: FileInformation = self
+            .children()
+            .into_iter()
+            .find(|information| information.file_name().as_str() == name)?;
         let read = true;
         let write = false;
         let create = false;
@@ -396,25 +662,29 @@ impl Node<'_> {
         );
         let protocol: &FileProtocol = self.protocol
             .open_child(&information, &open_mode, &attributes)
-            .expect("Can't open a root child!");
-        Self {
+            .expect("Can't open a child!");
+        Some(Self {
             information,
             protocol,
-        }
+        })
     }
 
-    pub fn read_file(self) -> Vec<u8> {
-        self.protocol.read(&self.information)
+    // Entries of the directory except "." and "..".
+    pub fn children(&self) -> Vec<FileInformation<'a>> {
+        self.protocol
+            .rewind()
+            .expect("Can't rewind a directory!");
+        self.protocol
+            .filter(|information| !matches!(information.file_name().as_str(), "." | ".."))
+            .collect()
     }
 
-    pub fn root_child(file_system: &simple_file_system::SimpleFileSystem, name: &str) -> Self {
-        let mut root: &FileProtocol = file_system.open_volume();
-        let information: FileInformation = root
-            .find(|information| information.file_name().as_str() == name)
-            .expect("A root child is nou found!");
+    // Create an empty file or a directory.
+    // An existing file is truncated by deleting it.
+    pub fn create_child(self, name: &str, directory: bool) -> Option<Self> {
         let read = true;
-        let write = false;
-        let create = false;
+        let write = true;
+        let create = true;
         let open_mode = OpenMode::new(
             read,
             write,
@@ -424,8 +694,7 @@ impl Node<'_> {
         let hidden: bool = false;
         let system: bool = false;
         let reserved: bool = false;
-        let directory: bool = false;
-        let archive: bool = false;
+        let archive: bool = !directory;
         let attributes = Attributes::new(
             read_only,
             hidden,
@@ -434,13 +703,51 @@ impl Node<'_> {
             directory,
             archive,
         );
-        let protocol: &FileProtocol = root
-            .open_child(&information, &open_mode, &attributes)
-            .expect("Can't open a root child!");
-        Self {
+        let protocol: &FileProtocol = self.protocol
+            .open(name, &open_mode, &attributes)
+            .ok()?;
+        let information: FileInformation = protocol.information()?;
+        let (information, protocol): (FileInformation, &FileProtocol) = if information.is_directory() || information.file_size() == 0 {
+            (information, protocol)
+        } else {
+            protocol
+                .delete()
+                .ok()?;
+            let protocol: &FileProtocol = self.protocol
+                .open(name, &open_mode, &attributes)
+                .ok()?;
+            (protocol.information()?, protocol)
+        };
+        Some(Self {
             information,
             protocol,
-        }
+        })
     }
-}
 
+    pub fn flush(&self) -> Result<(), status::Status> {
+        self.protocol.flush()
+    }
+
+    pub fn information(&self) -> &FileInformation<'a> {
+        &self.information
+    }
+
+    pub fn read_file(self) -> Vec<u8> {
+        self.protocol.read(&self.information)
+    }
+
+    // Return None if the root directory doesn't provide EFI_FILE_INFO.
+    pub fn root(file_system: &simple_file_system::SimpleFileSystem) -> Option<Self> {
+        let protocol: &FileProtocol = file_system.open_volume();
+        let information: FileInformation = protocol.information()?;
+        Some(Self {
+            information,
+            protocol,
+        })
+    }
+
+    // Return the number of written bytes.
+    pub fn write(&self, bytes: &[u8]) -> Result<usize, status::Status> {
+        self.protocol.write(bytes)
+    }
+}
@@ -3,16 +3,20 @@
 // 13.4 Simple File System Protocol
 
 use {
-    alloc::vec::Vec,
-    core::str,
+    alloc::{
+        string::String,
+        vec::Vec,
+    },
     super::{
         file_protocol,
         super::{
+            device_path,
             efi_loaded_image::efi_loaded_image,
             super::{
                 services::boot::protocol_handler,
                 tables::system,
                 types::{
+                    handle,
                     status,
                     void,
                 },
@@ -22,6 +26,23 @@ use {
     wrapped_function::WrappedFunction,
 };
 
+// EFI_SIMPLE_FILE_SYSTEM_PROTOCOL_GUID
+const GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x964e5b22,
+    0x6459,
+    0x11d2,
+    [
+        0x8e,
+        0x39,
+        0x00,
+        0xa0,
+        0xc9,
+        0x69,
+        0x72,
+        0x3b
+    ],
+);
+
 #[derive(Debug)]
 #[repr(C)]
 pub struct SimpleFileSystem {
@@ -30,43 +51,74 @@ pub struct SimpleFileSystem {
 }
 
 impl SimpleFileSystem {
+    // The volume the loader came from.
     pub fn new<'a>() -> &'a Self {
-        let guid = protocol_handler::Guid::new(
-            0x964e5b22,
-            0x6459,
-            0x11d2,
-            [
-                0x8e,
-                0x39,
-                0x00,
-                0xa0,
-                0xc9,
-                0x69,
-                0x72,
-                0x3b
-            ],
-        );
+        Self::of(efi_loaded_image::EfiLoadedImage::new().device_handle())
+            .expect("Can't get a simple file system protocol!")
+    }
+
+    // Return None if no volume matches.
+    pub fn find<'a>(volume: &Volume) -> Option<&'a Self> {
+        Self::find_handle(volume).and_then(|handle| Self::of(handle))
+    }
+
+    // Return None if no volume matches.
+    pub fn find_handle<'a>(volume: &Volume) -> Option<handle::Handle<'a>> {
+        system::system()
+            .boot_services
+            .locate_handle_buffer(protocol_handler::LocateSearchType::ByProtocol, &GUID)
+            .ok()?
+            .into_iter()
+            .find(|handle| match volume {
+                Volume::PartitionGuid(partition_guid) => device_path::DevicePathProtocol::new(*handle)
+                    .and_then(|device_path| device_path.partition_guid())
+                    .map_or(false, |guid| guid == *partition_guid),
+                Volume::Label(label) => Self::of(*handle)
+                    .and_then(|simple_file_system| simple_file_system
+                        .open_volume()
+                        .volume_label())
+                    .map_or(false, |volume_label| volume_label == *label),
+            })
+    }
+
+    pub fn of<'a>(handle: handle::Handle) -> Option<&'a Self> {
         let simple_file_system = void::Void::new();
         let mut simple_file_system: &void::Void = &simple_file_system;
         system::system()
             .boot_services
             .open_protocol(
-                efi_loaded_image::EfiLoadedImage::new().device_handle(),
-                &guid,
+                handle,
+                &GUID,
                 &mut simple_file_system,
                 system::image(),
                 void::Void::null(),
                 protocol_handler::OPEN_PROTOCOL_BY_HANDLE_PROTOCOL,
             )
-            .expect("Can't get a simple file system protocol!");
+            .ok()?;
         let simple_file_system: *const void::Void = &*simple_file_system;
         let simple_file_system: usize = simple_file_system as usize;
         let simple_file_system: *const Self = simple_file_system as *const Self;
-        unsafe {
+        Some(unsafe {
             &*simple_file_system
+        })
+    }
+
+    // The volume the loader came from if no volume is specified.
+    pub fn select<'a>(volume: Option<&Volume>) -> Option<&'a Self> {
+        match volume {
+            Some(volume) => Self::find(volume),
+            None => Some(Self::new()),
+        }
+    }
+
+    // The handle of the volume the loader came from if no volume is specified.
+    pub fn select_handle<'a>(volume: Option<&Volume>) -> Option<handle::Handle<'a>> {
+        match volume {
+            Some(volume) => Self::find_handle(volume),
+            None => Some(efi_loaded_image::EfiLoadedImage::new().device_handle()),
         }
     }
-    
+
     pub fn open_volume<'a>(&self) -> &'a file_protocol::FileProtocol {
         let volume = void::Void::new();
         let volume: &void::Void = &volume;
@@ -82,16 +134,47 @@ impl SimpleFileSystem {
         }
     }
 
-    pub fn read_file(&self, path: &str) -> Vec<u8> {
-        let mut path: str::Split<char> = path.split('/');
-        path.next().expect("Can't read a file!");
-        let name: &str = path.next().expect("Can't read a file!");
+    // Return None if the directory doesn't exist.
+    pub fn directory<'a>(&'a self, path: &str) -> Option<Vec<file_protocol::FileInformation<'a>>> {
+        self.node(path)
+            .filter(|node| node
+                .information()
+                .is_directory())
+            .map(|node| node.children())
+    }
+
+    // Return None if the file doesn't exist.
+    pub fn try_read_file(&self, path: &str) -> Option<Vec<u8>> {
+        self.node(path)
+            .filter(|node| !node
+                .information()
+                .is_directory())
+            .map(|node| node.read_file())
+    }
+
+    // Create or truncate the file and write the bytes.
+    // Return None if the parent directory doesn't exist or the file can't be written.
+    pub fn try_write_file(&self, path: &str, bytes: &[u8]) -> Option<()> {
+        let (directory, name): (&str, &str) = path.rsplit_once('/')?;
+        let file: file_protocol::Node = self
+            .node(directory)?
+            .create_child(name, false)?;
+        file
+            .write(bytes)
+            .ok()
+            .filter(|written| *written == bytes.len())?;
+        file.flush().ok()
+    }
+
+    // "/" is the root directory.
+    fn node(&self, path: &str) -> Option<file_protocol::Node<'_>> {
         path
+            .split('/')
+            .filter(|name| !name.is_empty())
             .fold(
-                file_protocol::Node::root_child(self, name),
-                |node, name| node.child(name)
+                file_protocol::Node::root(self),
+                |node, name| node.and_then(|node| node.child(name))
             )
-            .read_file()
     }
 }
 
@@ -99,3 +182,25 @@ impl SimpleFileSystem {
 #[repr(C)]
 struct OpenVolume(pub extern "efiapi" fn(&SimpleFileSystem, &mut &file_protocol::FileProtocol) -> status::Status);
 
+
+// A volume named in the boot configuration.
+#[derive(Clone, Debug)]
+pub enum Volume {
+    PartitionGuid(protocol_handler::Guid),
+    Label(String),
+}
+
+// "PARTUUID=xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx" or "LABEL=name"
+impl TryFrom<&str> for Volume {
+    type Error = ();
+
+    fn try_from(volume: &str) -> Result<Self, Self::Error> {
+        match volume.split_once('=') {
+            Some(("PARTUUID", guid)) => guid
+                .try_into()
+                .map(Self::PartitionGuid),
+            Some(("LABEL", label)) if !label.is_empty() => Ok(Self::Label(label.into())),
+            _ => Err(()),
+        }
+    }
+}
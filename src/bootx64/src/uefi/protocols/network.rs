@@ -0,0 +1,5 @@
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 24 Network Protocols - SNP, PXE, BIS and HTTP Boot
+
+pub mod pxe_base_code;
@@ -0,0 +1,289 @@
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 24.3 PXE Base Code Protocol
+
+use {
+    alloc::vec::Vec,
+    core::{
+        fmt,
+        iter,
+    },
+    super::super::{
+        efi_loaded_image::efi_loaded_image,
+        super::{
+            services::boot::protocol_handler,
+            tables::system,
+            types::{
+                status,
+                void,
+            },
+        },
+    },
+    wrapped_function::WrappedFunction,
+};
+
+// EFI_PXE_BASE_CODE_PROTOCOL_GUID
+const GUID: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x03c4e603,
+    0xac28,
+    0x11d3,
+    [
+        0x9a,
+        0x2d,
+        0x00,
+        0x90,
+        0x27,
+        0x3f,
+        0xc1,
+        0x4d
+    ],
+);
+
+// EFI_ALREADY_STARTED
+const ALREADY_STARTED: status::Status = 0x8000000000000014;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct PxeBaseCode<'a> {
+    revision: u64,
+    start: Start,
+    stop: Stop,
+    dhcp: Dhcp,
+    discover: Discover,
+    mtftp: Mtftp,
+    udp_write: UdpWrite,
+    udp_read: UdpRead,
+    set_ip_filter: SetIpFilter,
+    arp: Arp,
+    set_parameters: SetParameters,
+    set_station_ip: SetStationIp,
+    set_packets: SetPackets,
+    mode: &'a Mode,
+}
+
+impl<'a> PxeBaseCode<'a> {
+    // Prefer the network interface the loader came from.
+    // Return None if no network interface provides the protocol.
+    pub fn new() -> Option<&'a Self> {
+        let pxe_base_code = void::Void::new();
+        let mut pxe_base_code: &void::Void = &pxe_base_code;
+        let boot_services = &system::system().boot_services;
+        boot_services
+            .open_protocol(
+                efi_loaded_image::EfiLoadedImage::new().device_handle(),
+                &GUID,
+                &mut pxe_base_code,
+                system::image(),
+                void::Void::null(),
+                protocol_handler::OPEN_PROTOCOL_BY_HANDLE_PROTOCOL,
+            )
+            .or_else(|_| boot_services.locate_protocol(
+                &GUID,
+                void::Void::null(),
+                &mut pxe_base_code,
+            ))
+            .ok()?;
+        let pxe_base_code: *const void::Void = &*pxe_base_code;
+        let pxe_base_code: usize = pxe_base_code as usize;
+        let pxe_base_code: *const Self = pxe_base_code as *const Self;
+        Some(unsafe {
+            &*pxe_base_code
+        })
+    }
+
+    // Start the protocol and get an IPv4 address by DHCP unless the firmware has already done it for a network boot.
+    pub fn configure(&self) -> Result<(), status::Status> {
+        let use_ipv6: bool = false;
+        match self.start.0(self, use_ipv6) {
+            status::SUCCESS | ALREADY_STARTED => (),
+            error => return Err(error),
+        }
+        if !self.mode.dhcp_ack_received {
+            let sort_offers: bool = false;
+            match self.dhcp.0(self, sort_offers) {
+                status::SUCCESS => (),
+                error => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn mode(&self) -> &'a Mode {
+        self.mode
+    }
+
+    // Read a whole file from a TFTP server.
+    pub fn read_file(&self, server: &IpAddress, file_name: &str) -> Result<Vec<u8>, status::Status> {
+        let file_name: Vec<u8> = file_name
+            .bytes()
+            .chain(iter::once(0))
+            .collect();
+        let overwrite: bool = false;
+        let dont_use_buffer: bool = false;
+        let mut buffer_size: u64 = 0;
+        match self.mtftp.0(
+            self,
+            TftpOpcode::GetFileSize,
+            void::Void::mut_null(),
+            overwrite,
+            &mut buffer_size,
+            void::Void::null(),
+            server,
+            &file_name[0],
+            void::Void::null(),
+            dont_use_buffer,
+        ) {
+            status::SUCCESS => (),
+            error => return Err(error),
+        }
+        let mut buffer: Vec<u8> = (0..buffer_size)
+            .map(|_| 0)
+            .collect();
+        if buffer.is_empty() {
+            return Ok(buffer);
+        }
+        let buffer_pointer: &mut u8 = &mut buffer[0];
+        let buffer_pointer: *mut u8 = &mut *buffer_pointer;
+        let buffer_pointer: usize = buffer_pointer as usize;
+        let buffer_pointer: &mut void::Void = buffer_pointer.into();
+        match self.mtftp.0(
+            self,
+            TftpOpcode::ReadFile,
+            buffer_pointer,
+            overwrite,
+            &mut buffer_size,
+            void::Void::null(),
+            server,
+            &file_name[0],
+            void::Void::null(),
+            dont_use_buffer,
+        ) {
+            status::SUCCESS => {
+                buffer.truncate(buffer_size as usize);
+                Ok(buffer)
+            },
+            error => Err(error),
+        }
+    }
+}
+
+// EFI_IP_ADDRESS
+#[derive(Clone)]
+#[repr(C, align(4))]
+pub struct IpAddress {
+    address: [u8; 16],
+}
+
+impl From<[u8; 4]> for IpAddress {
+    fn from(ipv4: [u8; 4]) -> Self {
+        let mut address: [u8; 16] = [0; 16];
+        address[..ipv4.len()].copy_from_slice(&ipv4);
+        Self {
+            address,
+        }
+    }
+}
+
+// Only IPv4 is used.
+impl fmt::Debug for IpAddress {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}.{}.{}.{}", self.address[0], self.address[1], self.address[2], self.address[3])
+    }
+}
+
+// EFI_PXE_BASE_CODE_MODE
+// Fields after the subnet mask are not used.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Mode {
+    started: bool,
+    ipv6_available: bool,
+    ipv6_supported: bool,
+    using_ipv6: bool,
+    bis_supported: bool,
+    bis_detected: bool,
+    auto_arp: bool,
+    send_guid: bool,
+    dhcp_discover_valid: bool,
+    dhcp_ack_received: bool,
+    proxy_offer_received: bool,
+    pxe_discover_valid: bool,
+    pxe_reply_received: bool,
+    pxe_bis_reply_received: bool,
+    icmp_error_received: bool,
+    tftp_error_received: bool,
+    make_callbacks: bool,
+    ttl: u8,
+    to_s: u8,
+    station_ip: IpAddress,
+    subnet_mask: IpAddress,
+}
+
+impl Mode {
+    pub fn station_ip(&self) -> &IpAddress {
+        &self.station_ip
+    }
+}
+
+// EFI_PXE_BASE_CODE_TFTP_OPCODE
+#[allow(dead_code)]
+#[repr(C)]
+enum TftpOpcode {
+    First,
+    GetFileSize,
+    ReadFile,
+    WriteFile,
+    ReadDirectory,
+    MtftpGetFileSize,
+    MtftpReadFile,
+    MtftpReadDirectory,
+    Last,
+}
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct Start(pub extern "efiapi" fn(&PxeBaseCode, bool) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct Stop(pub extern "efiapi" fn(&PxeBaseCode) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct Dhcp(pub extern "efiapi" fn(&PxeBaseCode, bool) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct Discover(pub extern "efiapi" fn(&PxeBaseCode, u16, &mut u16, bool, &void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct Mtftp(pub extern "efiapi" fn(&PxeBaseCode, TftpOpcode, &mut void::Void, bool, &mut u64, &void::Void, &IpAddress, &u8, &void::Void, bool) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct UdpWrite(pub extern "efiapi" fn(&PxeBaseCode, u16, &IpAddress, &u16, &IpAddress, &IpAddress, &mut u16, &usize, &void::Void, &usize, &void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct UdpRead(pub extern "efiapi" fn(&PxeBaseCode, u16, &mut IpAddress, &mut u16, &mut IpAddress, &mut u16, &usize, &mut void::Void, &mut usize, &mut void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct SetIpFilter(pub extern "efiapi" fn(&PxeBaseCode, &void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct Arp(pub extern "efiapi" fn(&PxeBaseCode, &IpAddress, &mut void::Void) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct SetParameters(pub extern "efiapi" fn(&PxeBaseCode, &bool, &bool, &u8, &u8, &bool) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct SetStationIp(pub extern "efiapi" fn(&PxeBaseCode, &IpAddress, &IpAddress) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct SetPackets(pub extern "efiapi" fn(&PxeBaseCode, &bool, &bool, &bool, &bool, &bool, &bool, &void::Void, &void::Void, &void::Void, &void::Void, &void::Void, &void::Void) -> status::Status);
@@ -0,0 +1,82 @@
+// References
+// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
+// 37.5 EFI RNG Protocol
+
+use {
+    core::mem,
+    super::super::{
+        services::boot::protocol_handler,
+        tables::system,
+        types::{
+            status,
+            void,
+        },
+    },
+    wrapped_function::WrappedFunction,
+};
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct RandomNumberGenerator {
+    get_info: GetInfo,
+    get_rng: GetRng,
+}
+
+impl RandomNumberGenerator {
+    // Return None if the firmware doesn't provide the protocol.
+    pub fn new() -> Option<&'static Self> {
+        let guid = protocol_handler::Guid::new(
+            0x3152bca5,
+            0xeade,
+            0x433d,
+            [
+                0x86,
+                0x2e,
+                0xc0,
+                0x1c,
+                0xdc,
+                0x29,
+                0x1f,
+                0x44
+            ],
+        );
+        let random_number_generator = void::Void::new();
+        let mut random_number_generator: &void::Void = &random_number_generator;
+        system::system()
+            .boot_services
+            .locate_protocol(
+                &guid,
+                void::Void::null(),
+                &mut random_number_generator,
+            )
+            .ok()?;
+        let random_number_generator: *const void::Void = &*random_number_generator;
+        let random_number_generator: usize = random_number_generator as usize;
+        let random_number_generator: *const Self = random_number_generator as *const Self;
+        Some(unsafe {
+            &*random_number_generator
+        })
+    }
+
+    // Get a random number by the default algorithm of the firmware.
+    pub fn get_u64(&self) -> Option<u64> {
+        let algorithm: usize = 0;
+        let algorithm: *const protocol_handler::Guid = algorithm as *const protocol_handler::Guid;
+        let algorithm: &protocol_handler::Guid = unsafe {
+            &*algorithm
+        };
+        let mut value: [u8; mem::size_of::<u64>()] = [0; mem::size_of::<u64>()];
+        match self.get_rng.0(self, algorithm, value.len(), &mut value[0]) {
+            status::SUCCESS => Some(u64::from_le_bytes(value)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct GetInfo(pub extern "efiapi" fn(&RandomNumberGenerator, &mut usize, &mut protocol_handler::Guid) -> status::Status);
+
+#[derive(WrappedFunction)]
+#[repr(C)]
+struct GetRng(pub extern "efiapi" fn(&RandomNumberGenerator, &protocol_handler::Guid, usize, &mut u8) -> status::Status);
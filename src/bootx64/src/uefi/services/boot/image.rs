@@ -17,11 +17,11 @@ use {
 
 #[derive(WrappedFunction)]
 #[repr(C)]
-pub struct LoadImage(pub extern "efiapi" fn(bool, handle::Handle<'_>, &device_path::DevicePathProtocol, &void::Void, usize, &mut handle::Handle<'_>) -> status::Status);
+pub struct LoadImage(pub extern "efiapi" fn(bool, handle::Handle<'_>, Option<&device_path::DevicePathProtocol>, &void::Void, usize, &mut handle::Handle<'_>) -> status::Status);
 
 #[derive(WrappedFunction)]
 #[repr(C)]
-pub struct StartImage(pub extern "efiapi" fn(handle::Handle<'_>, &mut usize, &mut char16::String) -> status::Status);
+pub struct StartImage(pub extern "efiapi" fn(handle::Handle<'_>, &mut usize, &mut &void::Void) -> status::Status);
 
 #[derive(WrappedFunction)]
 #[repr(C)]
@@ -238,6 +238,22 @@ impl<'a> Map<'a> {
         }
     }
 
+    pub fn buffer(&self) -> &[u8] {
+        self.buffer.get_ref()
+    }
+
+    pub fn descriptors(&self) -> usize {
+        self.descriptors
+    }
+
+    pub fn descriptor_size(&self) -> usize {
+        self.descriptor_size
+    }
+
+    pub fn descriptor_version(&self) -> u32 {
+        self.descriptor_version
+    }
+
     pub fn key(&self) -> usize {
         self.key
     }
@@ -307,39 +323,6 @@ impl Into<Vec<MemoryDescriptor>> for &Map<'_> {
     }
 }
 
-#[allow(dead_code)]
-pub struct PassedMap<'a> {
-    buffer: &'a [u8],
-    key: usize,
-    descriptors: usize,
-    descriptor_size: usize,
-    descriptor_version: u32,
-}
-
-impl<'a> From<&'a Map<'a>> for PassedMap<'a> {
-    fn from(map: &'a Map<'a>) -> Self {
-        let Map {
-            buffer,
-            key,
-            descriptors,
-            descriptor_size,
-            descriptor_version,
-        } = map;
-        let buffer: &[u8] = buffer.get_ref();
-        let key: usize = *key;
-        let descriptors: usize = *descriptors;
-        let descriptor_size: usize = *descriptor_size;
-        let descriptor_version: u32 = *descriptor_version;
-        Self {
-            buffer,
-            key,
-            descriptors,
-            descriptor_size,
-            descriptor_version,
-        }
-    }
-}
-
 #[derive(Clone)]
 pub struct MemoryDescriptors<'a> {
     buffer: &'a [u8],
@@ -3,6 +3,11 @@
 // 7.3 Protocol Hander Services
 
 use {
+    alloc::{
+        string::String,
+        vec::Vec,
+    },
+    core::fmt,
     super::super::super::{
         protocols::device_path,
         types::{
@@ -19,7 +24,7 @@ use {
 #[repr(C)]
 pub struct InstallProtocolInterface(pub extern "efiapi" fn(&mut handle::Handle<'_>, &Guid, InterfaceType, &void::Void) -> status::Status);
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[repr(C)]
 pub struct Guid {
     data1: u32,
@@ -29,7 +34,7 @@ pub struct Guid {
 }
 
 impl Guid {
-    pub fn new(
+    pub const fn new(
         data1: u32,
         data2: u16,
         data3: u16,
@@ -44,6 +49,65 @@ impl Guid {
     }
 }
 
+// The mixed endian binary format in GPT and device paths.
+impl From<&[u8; 16]> for Guid {
+    fn from(guid: &[u8; 16]) -> Self {
+        let data1: u32 = u32::from_le_bytes(guid[0x0..0x4]
+            .try_into()
+            .expect("Can't read a GUID!"));
+        let data2: u16 = u16::from_le_bytes(guid[0x4..0x6]
+            .try_into()
+            .expect("Can't read a GUID!"));
+        let data3: u16 = u16::from_le_bytes(guid[0x6..0x8]
+            .try_into()
+            .expect("Can't read a GUID!"));
+        let data4: [u8; 8] = guid[0x8..0x10]
+            .try_into()
+            .expect("Can't read a GUID!");
+        Self::new(data1, data2, data3, data4)
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-", self.data1, self.data2, self.data3, self.data4[0], self.data4[1])?;
+        self.data4[2..]
+            .iter()
+            .try_for_each(|byte| write!(formatter, "{:02x}", byte))
+    }
+}
+
+// The registry format "xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx".
+impl TryFrom<&str> for Guid {
+    type Error = ();
+
+    fn try_from(guid: &str) -> Result<Self, Self::Error> {
+        let fields: Vec<&str> = guid
+            .trim()
+            .split('-')
+            .collect();
+        let lengths: [usize; 5] = [8, 4, 4, 4, 12];
+        if fields.len() != lengths.len() || fields
+            .iter()
+            .zip(lengths.iter())
+            .any(|(field, length)| field.len() != *length || !field.chars().all(|digit| digit.is_ascii_hexdigit())) {
+            return Err(());
+        }
+        let data1: u32 = u32::from_str_radix(fields[0], 0x10).map_err(|_| ())?;
+        let data2: u16 = u16::from_str_radix(fields[1], 0x10).map_err(|_| ())?;
+        let data3: u16 = u16::from_str_radix(fields[2], 0x10).map_err(|_| ())?;
+        let data4: String = fields[3..].concat();
+        let data4: Vec<u8> = (0..data4.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&data4[index..index + 2], 0x10).map_err(|_| ()))
+            .collect::<Result<Vec<u8>, ()>>()?;
+        let data4: [u8; 8] = data4
+            .try_into()
+            .map_err(|_| ())?;
+        Ok(Self::new(data1, data2, data3, data4))
+    }
+}
+
 #[allow(dead_code)]
 #[repr(C)]
 pub enum InterfaceType {
@@ -3,17 +3,169 @@
 // 8.2 Variable Services
 
 use {
+    alloc::{
+        string::String,
+        vec::Vec,
+    },
+    core::marker::PhantomData,
     super::super::{
         boot::protocol_handler,
-        super::types::{
-            char16,
-            status,
-            void,
+        super::{
+            tables::system,
+            types::{
+                char16,
+                status,
+                void,
+            },
         },
     },
     wrapped_function::WrappedFunction,
 };
 
+// EFI_VARIABLE_NON_VOLATILE
+pub const NON_VOLATILE: u32 = 0x00000001;
+// EFI_VARIABLE_BOOTSERVICE_ACCESS
+pub const BOOTSERVICE_ACCESS: u32 = 0x00000002;
+// EFI_VARIABLE_RUNTIME_ACCESS
+pub const RUNTIME_ACCESS: u32 = 0x00000004;
+
+// EFI_GLOBAL_VARIABLE
+pub const GLOBAL_VARIABLE: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x8be4df61,
+    0x93ca,
+    0x11d2,
+    [
+        0xaa,
+        0x0d,
+        0x00,
+        0xe0,
+        0x98,
+        0x03,
+        0x2b,
+        0x8c
+    ],
+);
+
+// A variable holding a value of type T.
+pub struct Variable<'a, T: Value> {
+    name: &'a str,
+    vendor: &'a protocol_handler::Guid,
+    attributes: u32,
+    value: PhantomData<T>,
+}
+
+impl<'a, T: Value> Variable<'a, T> {
+    pub const fn new(name: &'a str, vendor: &'a protocol_handler::Guid, attributes: u32) -> Self {
+        let value: PhantomData<T> = PhantomData;
+        Self {
+            name,
+            vendor,
+            attributes,
+            value,
+        }
+    }
+
+    pub fn delete(&self) -> Result<(), status::Status> {
+        match system::system()
+            .runtime_services
+            .set_variable(self.name, self.vendor, self.attributes, &[]) {
+            Ok(()) | Err(status::NOT_FOUND) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    // Return None if the variable doesn't exist or can't be decoded.
+    pub fn get(&self) -> Option<T> {
+        system::system()
+            .runtime_services
+            .get_variable(self.name, self.vendor)
+            .ok()
+            .and_then(|(_attributes, data)| T::decode(&data))
+    }
+
+    pub fn set(&self, value: &T) -> Result<(), status::Status> {
+        system::system()
+            .runtime_services
+            .set_variable(self.name, self.vendor, self.attributes, &value.encode())
+    }
+}
+
+// A value stored as bytes of a variable.
+pub trait Value: Sized {
+    fn decode(data: &[u8]) -> Option<Self>;
+    fn encode(&self) -> Vec<u8>;
+}
+
+impl Value for bool {
+    fn decode(data: &[u8]) -> Option<Self> {
+        match data {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        [*self as u8].to_vec()
+    }
+}
+
+impl Value for u16 {
+    fn decode(data: &[u8]) -> Option<Self> {
+        data
+            .try_into()
+            .ok()
+            .map(Self::from_le_bytes)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.to_le_bytes().to_vec()
+    }
+}
+
+impl Value for u32 {
+    fn decode(data: &[u8]) -> Option<Self> {
+        data
+            .try_into()
+            .ok()
+            .map(Self::from_le_bytes)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.to_le_bytes().to_vec()
+    }
+}
+
+// A UCS-2 string without a null terminator.
+impl Value for String {
+    fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() % 2 != 0 {
+            return None;
+        }
+        let string: Vec<char16::Char16> = data
+            .chunks(2)
+            .map(|character| char16::Char16::from_le_bytes([character[0], character[1]]))
+            .collect();
+        String::from_utf16(&string).ok()
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.encode_utf16()
+            .flat_map(|character| character.to_le_bytes())
+            .collect()
+    }
+}
+
+impl Value for Vec<u8> {
+    fn decode(data: &[u8]) -> Option<Self> {
+        Some(data.to_vec())
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
 #[derive(WrappedFunction)]
 #[repr(C)]
 pub struct GetVariable(pub extern "efiapi" fn(char16::String, &protocol_handler::Guid, &mut u32, &mut usize, &mut void::Void) -> status::Status);
@@ -24,7 +176,7 @@ pub struct GetNextVariableName(pub extern "efiapi" fn(&mut usize, char16::MutStr
 
 #[derive(WrappedFunction)]
 #[repr(C)]
-pub struct SetVariable(pub extern "efiapi" fn(char16::String, &protocol_handler::Guid, &mut u32, &mut usize, &mut void::Void) -> status::Status);
+pub struct SetVariable(pub extern "efiapi" fn(char16::String, &protocol_handler::Guid, u32, usize, &void::Void) -> status::Status);
 
 #[derive(WrappedFunction)]
 #[repr(C)]
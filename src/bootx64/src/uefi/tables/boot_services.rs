@@ -1,17 +1,23 @@
-use super::{
-    header,
+use {
+    alloc::vec::Vec,
+    core::slice,
     super::{
-        services::boot::{
-            event,
-            image,
-            memory_allocation,
-            protocol_handler,
-            self,
-        },
-        types::{
-            handle,
-            status,
-            void,
+        header,
+        super::{
+            protocols::device_path,
+            services::boot::{
+                event,
+                image,
+                memory_allocation,
+                protocol_handler,
+                self,
+            },
+            types::{
+                char16,
+                handle,
+                status,
+                void,
+            },
         },
     },
 };
@@ -104,6 +110,22 @@ impl BootServices<'_> {
         }
     }
 
+    pub fn calculate_crc32(&self, data: &[u8]) -> Result<u32, status::Status> {
+        let mut crc32: u32 = 0;
+        let data_size: usize = data.len();
+        let data: *const u8 = data.as_ptr();
+        let data: usize = data as usize;
+        let data: &void::Void = data.into();
+        match self.calculate_crc32.0(
+            data,
+            data_size,
+            &mut crc32,
+        ) {
+            status::SUCCESS => Ok(crc32),
+            error => Err(error),
+        }
+    }
+
     pub fn exit_boot_services(
         &self,
         image_handle: handle::Handle,
@@ -178,6 +200,70 @@ impl BootServices<'_> {
         }
     }
 
+    // Load an image from the bytes.
+    // The device path tells the image where it came from.
+    pub fn load_image<'b>(
+        &self,
+        parent_image_handle: handle::Handle,
+        device_path: Option<&device_path::DevicePathProtocol>,
+        source: &[u8],
+    ) -> Result<handle::Handle<'b>, status::Status> {
+        let boot_policy: bool = false;
+        let source_size: usize = source.len();
+        let source: *const u8 = source.as_ptr();
+        let source: usize = source as usize;
+        let source: &void::Void = source.into();
+        let mut image_handle: handle::Handle<'b> = void::Void::null();
+        match self.load_image.0(
+            boot_policy,
+            parent_image_handle,
+            device_path,
+            source,
+            source_size,
+            &mut image_handle,
+        ) {
+            status::SUCCESS => Ok(image_handle),
+            // The image is loaded but the platform policy forbids starting it.
+            status::SECURITY_VIOLATION => {
+                self.unload_image(image_handle)?;
+                Err(status::SECURITY_VIOLATION)
+            },
+            error => Err(error),
+        }
+    }
+
+    // The handles are copied and the buffer allocated by the firmware is freed.
+    pub fn locate_handle_buffer<'b>(
+        &self,
+        search_type: protocol_handler::LocateSearchType,
+        protocol: &protocol_handler::Guid,
+    ) -> Result<Vec<handle::Handle<'b>>, status::Status> {
+        let mut no_handles: usize = 0;
+        let buffer = void::Void::new();
+        let buffer: &void::Void = &buffer;
+        let mut buffer: &handle::Handle = &buffer;
+        match self.locate_handle_buffer.0(
+            search_type,
+            protocol,
+            void::Void::null(),
+            &mut no_handles,
+            &mut buffer,
+        ) {
+            status::SUCCESS => {
+                let handles: *const handle::Handle = buffer as *const handle::Handle;
+                let handles: *const handle::Handle<'b> = handles as usize as *const handle::Handle<'b>;
+                let handles: Vec<handle::Handle<'b>> = unsafe {
+                    slice::from_raw_parts(handles, no_handles)
+                }.to_vec();
+                let buffer: *const handle::Handle = buffer as *const handle::Handle;
+                let buffer: &void::Void = (buffer as usize).into();
+                self.free_pool(buffer)?;
+                Ok(handles)
+            },
+            error => Err(error),
+        }
+    }
+
     pub fn locate_protocol(
         &self,
         protocol: &protocol_handler::Guid,
@@ -215,5 +301,49 @@ impl BootServices<'_> {
             error => Err(error),
         }
     }
+
+    pub fn set_watchdog_timer(&self, timeout: usize) -> Result<(), status::Status> {
+        let watchdog_code: u64 = 0;
+        let data_size: usize = 0;
+        let watchdog_data = char16::String::null();
+        match self.set_watchdoc_timer.0(timeout, watchdog_code, data_size, watchdog_data) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    pub fn stall(&self, microseconds: usize) -> Result<(), status::Status> {
+        match self.stall.0(microseconds) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    // Run the image until it exits and return the exit status of the image.
+    // The exit data allocated by the image is freed.
+    pub fn start_image(&self, image_handle: handle::Handle) -> Result<(), status::Status> {
+        let mut exit_data_size: usize = 0;
+        let mut exit_data: &void::Void = void::Void::null();
+        let status: status::Status = self.start_image.0(
+            image_handle,
+            &mut exit_data_size,
+            &mut exit_data,
+        );
+        let exit_data_address: usize = exit_data.into();
+        if exit_data_address != 0 {
+            self.free_pool(exit_data)?;
+        }
+        match status {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    pub fn unload_image(&self, image_handle: handle::Handle) -> Result<(), status::Status> {
+        match self.unload_image.0(image_handle) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
 }
 
@@ -17,6 +17,16 @@ pub struct Configuration<'a> {
     vendor_table: &'a void::Void,
 }
 
+impl Configuration<'_> {
+    pub fn vendor_guid(&self) -> &protocol_handler::Guid {
+        &self.vendor_guid
+    }
+
+    pub fn vendor_table(&self) -> &void::Void {
+        self.vendor_table
+    }
+}
+
 #[derive(Clone)]
 pub struct Configurations<'a> {
     configurations: &'a Configuration<'a>,
@@ -1,10 +1,31 @@
-use super::{
-    header,
-    super::services::runtime::{
-        self,
-        time,
-        variable,
-        virtual_memory,
+use {
+    alloc::{
+        string::String,
+        vec,
+        vec::Vec,
+    },
+    core::{
+        iter,
+        mem,
+    },
+    super::{
+        header,
+        super::{
+            services::{
+                boot::protocol_handler,
+                runtime::{
+                    self,
+                    time,
+                    variable,
+                    virtual_memory,
+                },
+            },
+            types::{
+                char16,
+                status,
+                void,
+            },
+        },
     },
 };
 
@@ -31,3 +52,98 @@ pub struct RuntimeServices {
     query_variable_info: variable::QueryVariableInfo,
 }
 
+impl RuntimeServices {
+    // Return the attributes and the data of a variable.
+    pub fn get_variable(&self, name: &str, vendor: &protocol_handler::Guid) -> Result<(u32, Vec<u8>), status::Status> {
+        let name: Vec<char16::Char16> = encode(name);
+        let mut attributes: u32 = 0;
+        let mut data_size: usize = 0;
+        let mut data: Vec<u8> = Vec::new();
+        loop {
+            let data_pointer: &mut void::Void = if data.is_empty() {
+                void::Void::mut_null()
+            } else {
+                let data_pointer: *mut u8 = data.as_mut_ptr();
+                let data_pointer: usize = data_pointer as usize;
+                data_pointer.into()
+            };
+            match self.get_variable.0(
+                char16::String::new(&name[0]),
+                vendor,
+                &mut attributes,
+                &mut data_size,
+                data_pointer,
+            ) {
+                status::SUCCESS => {
+                    data.truncate(data_size);
+                    return Ok((attributes, data));
+                },
+                status::BUFFER_TOO_SMALL => data = vec![0; data_size],
+                error => return Err(error),
+            }
+        }
+    }
+
+    // Return the maximum storage, the remaining storage and the maximum variable size for variables with the attributes.
+    pub fn query_variable_info(&self, attributes: u32) -> Result<(u64, u64, u64), status::Status> {
+        let mut maximum_variable_storage_size: u64 = 0;
+        let mut remaining_variable_storage_size: u64 = 0;
+        let mut maximum_variable_size: u64 = 0;
+        match self.query_variable_info.0(
+            attributes,
+            &mut maximum_variable_storage_size,
+            &mut remaining_variable_storage_size,
+            &mut maximum_variable_size,
+        ) {
+            status::SUCCESS => Ok((maximum_variable_storage_size, remaining_variable_storage_size, maximum_variable_size)),
+            error => Err(error),
+        }
+    }
+
+    // An empty data deletes the variable.
+    pub fn set_variable(&self, name: &str, vendor: &protocol_handler::Guid, attributes: u32, data: &[u8]) -> Result<(), status::Status> {
+        let name: Vec<char16::Char16> = encode(name);
+        let data_size: usize = data.len();
+        let data: *const u8 = data.as_ptr();
+        let data: usize = data as usize;
+        let data: &void::Void = data.into();
+        match self.set_variable.0(
+            char16::String::new(&name[0]),
+            vendor,
+            attributes,
+            data_size,
+            data,
+        ) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    // Names and vendors of all variables.
+    pub fn variable_names(&self) -> Vec<(String, protocol_handler::Guid)> {
+        let mut names: Vec<(String, protocol_handler::Guid)> = Vec::new();
+        let mut name: Vec<char16::Char16> = vec![0; 0x100];
+        let mut vendor = protocol_handler::Guid::new(0, 0, 0, [0; 8]);
+        loop {
+            let mut name_size: usize = name.len() * mem::size_of::<char16::Char16>();
+            match self.get_next_variable_name.0(
+                &mut name_size,
+                char16::MutString::new(&mut name[0]),
+                &mut vendor,
+            ) {
+                status::SUCCESS => names.push((char16::String::new(&name[0]).into(), vendor.clone())),
+                // The name of the previous variable has to be kept to continue.
+                status::BUFFER_TOO_SMALL => name.resize(name_size / mem::size_of::<char16::Char16>(), 0),
+                _ => return names,
+            }
+        }
+    }
+}
+
+// A null terminated UCS-2 string.
+fn encode(string: &str) -> Vec<char16::Char16> {
+    string
+        .encode_utf16()
+        .chain(iter::once(0))
+        .collect()
+}
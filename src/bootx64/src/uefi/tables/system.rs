@@ -1,4 +1,5 @@
 use {
+    crate::serial,
     core::fmt::{
         self,
         Write,
@@ -13,10 +14,14 @@ use {
                 simple_text_input,
                 simple_text_output,
             },
-            services::boot::memory_allocation,
+            services::boot::{
+                memory_allocation,
+                protocol_handler,
+            },
             types::{
                 char16,
                 handle,
+                void,
             },
         },
     },
@@ -34,6 +39,7 @@ macro_rules! uefi_println {
 }
 
 pub fn print(args: fmt::Arguments) {
+    serial::record(args);
     system()
         .write_fmt(args)
         .expect("Can't output to the screen!");
@@ -95,6 +101,14 @@ pub struct System<'a> {
 }
 
 impl<'a> System<'a> {
+    // The vendor table of the configuration table with the GUID.
+    pub fn configuration_table(&self, guid: &protocol_handler::Guid) -> Option<&void::Void> {
+        let mut configurations: configuration::Configurations = self.into();
+        configurations
+            .find(|configuration| configuration.vendor_guid() == guid)
+            .map(|configuration| configuration.vendor_table())
+    }
+
     pub fn move_to_higher_half(&'a self, highest_parallel_offset: usize) -> &'a Self {
         let system: *const Self = self as *const Self;
         let system: usize = system as usize;
@@ -79,6 +79,12 @@ impl fmt::Debug for String<'_> {
 #[repr(C)]
 pub struct MutString<'a>(&'a mut Char16);
 
+impl<'a> MutString<'a> {
+    pub fn new(string: &'a mut Char16) -> Self {
+        Self(string)
+    }
+}
+
 impl<'a> Into<String<'a>> for MutString<'a> {
     fn into(self) -> String<'a> {
         String::<'a>(self.0)
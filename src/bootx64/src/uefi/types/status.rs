@@ -7,3 +7,6 @@ pub type Status = usize;
 
 pub const SUCCESS: Status = 0;
 
+pub const BUFFER_TOO_SMALL: Status = 0x8000000000000005;
+pub const NOT_FOUND: Status = 0x800000000000000e;
+pub const SECURITY_VIOLATION: Status = 0x800000000000001a;
@@ -19,6 +19,17 @@ pub fn get_rsp() -> usize {
     rsp
 }
 
+pub fn get_rbp() -> usize {
+    let mut rbp: usize;
+    unsafe {
+        asm!(
+            "mov rax, rbp",
+            out("rax") rbp,
+        );
+    }
+    rbp
+}
+
 pub fn hlt() {
     unsafe {
         asm!("hlt");
@@ -1,3 +1,5 @@
+use super::super::rdmsr;
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct Ia32Efer {
@@ -8,6 +10,33 @@ pub struct Ia32Efer {
 }
 
 impl Ia32Efer {
+    const ADDRESS: u32 = 0xc0000080;
+
+    const SCE_SHIFT: usize = 0;
+    const LME_SHIFT: usize = 8;
+    const LMA_SHIFT: usize = 10;
+    const NXE_SHIFT: usize = 11;
+
+    const SCE_MASK: u64 = 1 << Self::SCE_SHIFT;
+    const LME_MASK: u64 = 1 << Self::LME_SHIFT;
+    const LMA_MASK: u64 = 1 << Self::LMA_SHIFT;
+    const NXE_MASK: u64 = 1 << Self::NXE_SHIFT;
+
+    // IA32_EFER is always present in 64-bit mode.
+    pub fn get() -> Self {
+        let value: u64 = rdmsr(Self::ADDRESS);
+        let sce: bool = value & Self::SCE_MASK != 0;
+        let lme: bool = value & Self::LME_MASK != 0;
+        let lma: bool = value & Self::LMA_MASK != 0;
+        let nxe: bool = value & Self::NXE_MASK != 0;
+        Self {
+            sce,
+            lme,
+            lma,
+            nxe,
+        }
+    }
+
     #[allow(dead_code)]
     pub fn lme(&self) -> bool {
         self.lme
@@ -0,0 +1,155 @@
+// A backtrace is symbolized by the symbol table passed by the bootloader.
+// Frames are walked by saved RBPs, so the kernel is built with frame pointers.
+
+use {
+    core::{
+        mem,
+        ptr,
+        str,
+    },
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    super::{
+        asm,
+        module,
+    },
+};
+
+static mut SYMBOL_TABLE: Option<SymbolTable<'static>> = None;
+static mut STACK_FLOOR: usize = 0;
+const MAX_FRAMES: usize = 0x40;
+
+// The symbol table is relocated by the difference between the runtime and link-time addresses of main.
+pub fn init(debug_sections: &'static [module::Module], stack_floor: usize, main: usize) {
+    let symtab: Option<&[u8]> = debug_sections
+        .iter()
+        .find(|debug_section| debug_section.name() == ".symtab")
+        .map(|symtab| symtab.bytes());
+    let strtab: Option<&[u8]> = debug_sections
+        .iter()
+        .find(|debug_section| debug_section.name() == ".strtab")
+        .map(|strtab| strtab.bytes());
+    let symbol_table: Option<SymbolTable> = symtab
+        .zip(strtab)
+        .and_then(|(symtab, strtab)| {
+            let symbol_table = SymbolTable {
+                symtab,
+                strtab,
+                bias: 0,
+            };
+            let bias: Option<usize> = symbol_table
+                .symbols()
+                .find(|symbol| symbol_table.name(symbol) == Some("main"))
+                .map(|symbol| main.wrapping_sub(symbol.st_value));
+            bias.map(|bias| SymbolTable {
+                bias,
+                ..symbol_table
+            })
+        });
+    if symbol_table.is_none() {
+        serial_println!("No symbol table is available for backtraces.");
+    }
+    unsafe {
+        SYMBOL_TABLE = symbol_table;
+        STACK_FLOOR = stack_floor;
+    }
+}
+
+pub fn print() {
+    let symbol_table: Option<&SymbolTable> = unsafe {
+        (*ptr::addr_of!(SYMBOL_TABLE)).as_ref()
+    };
+    let stack_floor: usize = unsafe {
+        STACK_FLOOR
+    };
+    let stack_ceiling: usize = asm::get_rsp();
+    serial_println!("Backtrace");
+    let mut rbp: usize = asm::get_rbp();
+    for frame in 0..MAX_FRAMES {
+        // Stop at a frame out of the kernel stack.
+        if rbp < stack_ceiling || stack_floor <= rbp + 2 * mem::size_of::<usize>() || rbp % mem::size_of::<usize>() != 0 {
+            break;
+        }
+        let return_address: usize = unsafe {
+            *((rbp + mem::size_of::<usize>()) as *const usize)
+        };
+        match symbol_table.and_then(|symbol_table| symbol_table.symbolize(return_address)) {
+            Some((name, offset)) => serial_println!("#{} {:#x} {}+{:#x}", frame, return_address, name, offset),
+            None => serial_println!("#{} {:#x}", frame, return_address),
+        }
+        rbp = unsafe {
+            *(rbp as *const usize)
+        };
+    }
+}
+
+struct SymbolTable<'a> {
+    symtab: &'a [u8],
+    strtab: &'a [u8],
+    bias: usize,
+}
+
+impl SymbolTable<'_> {
+    fn name(&self, symbol: &Symbol) -> Option<&str> {
+        self.strtab
+            .get(symbol.st_name as usize..)?
+            .split(|byte| *byte == 0x00)
+            .next()
+            .and_then(|name| str::from_utf8(name).ok())
+    }
+
+    fn symbolize(&self, address: usize) -> Option<(&str, usize)> {
+        let address: usize = address.wrapping_sub(self.bias);
+        self.symbols()
+            .find(|symbol| symbol.is_function() && symbol.st_value <= address && address < symbol.st_value + symbol.st_size)
+            .and_then(|symbol| self
+                .name(&symbol)
+                .map(|name| (name, address - symbol.st_value)))
+    }
+
+    fn symbols(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.symtab
+            .chunks_exact(SYMBOL_SIZE)
+            .map(|symbol| symbol.into())
+    }
+}
+
+// Elf64_Sym
+struct Symbol {
+    st_name: u32,
+    st_info: u8,
+    st_value: usize,
+    st_size: usize,
+}
+
+const SYMBOL_SIZE: usize = 0x18;
+const STT_FUNC: u8 = 0x02;
+
+impl Symbol {
+    fn is_function(&self) -> bool {
+        self.st_info & 0x0f == STT_FUNC
+    }
+}
+
+impl From<&[u8]> for Symbol {
+    fn from(symbol: &[u8]) -> Self {
+        let st_name = u32::from_le_bytes(symbol[0x00..0x04]
+            .try_into()
+            .expect("Can't read a symbol!"));
+        let st_info: u8 = symbol[0x04];
+        let st_value = usize::from_le_bytes(symbol[0x08..0x10]
+            .try_into()
+            .expect("Can't read a symbol!"));
+        let st_size = usize::from_le_bytes(symbol[0x10..0x18]
+            .try_into()
+            .expect("Can't read a symbol!"));
+        Self {
+            st_name,
+            st_info,
+            st_value,
+            st_size,
+        }
+    }
+}
@@ -0,0 +1,351 @@
+// The boot information is the only interface between a bootloader and the kernel.
+// See bootx64/src/boot_info.rs for the layout.
+
+use {
+    core::{
+        mem,
+        slice,
+        str,
+    },
+    super::{
+        module,
+        serial,
+        uefi::{
+            services::boot::memory_allocation,
+            tables::system,
+            types::handle,
+        },
+    },
+};
+
+// "THEOSBI\0"
+pub const MAGIC: u64 = u64::from_le_bytes(*b"THEOSBI\0");
+pub const VERSION: u32 = 4;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct BootInfo {
+    magic: u64,
+    version: u32,
+    size: u32,
+    system: u64,
+    image: u64,
+    memory_size: u64,
+    highest_parallel_offset: u64,
+    memory_map: MemoryMap,
+    physical_page_present_bit_map: Array,
+    stack_floor: u64,
+    frame_buffer: FrameBuffer,
+    rsdp: u64,
+    smbios: u64,
+    com1: SerialPort,
+    com2: SerialPort,
+    command_line: Array,
+    modules: Array,
+    debug_sections: Array,
+    font: Font,
+    // Version 2
+    device_tree: u64,
+    memory_attributes: u64,
+    // Version 3
+    phases: Array,
+    boot_log: Array,
+    // Version 4
+    narrow_width: u16,
+}
+
+impl BootInfo {
+    // A newer bootloader may append fields.
+    pub fn is_compatible(&self) -> bool {
+        self.magic == MAGIC && VERSION <= self.version && mem::size_of::<Self>() <= self.size as usize
+    }
+
+    // Messages printed by the bootloader from the oldest one.
+    // The oldest message may be partially overwritten.
+    pub fn boot_log(&self) -> &'static [u8] {
+        self.boot_log.get()
+    }
+
+    pub fn com1(&self) -> serial::Serial {
+        (&self.com1).into()
+    }
+
+    pub fn com2(&self) -> serial::Serial {
+        (&self.com2).into()
+    }
+
+    pub fn command_line(&self) -> &'static str {
+        str::from_utf8(self.command_line.get())
+            .expect("Can't get a command line!")
+    }
+
+    pub fn debug_sections(&self) -> &'static [module::Module] {
+        self.debug_sections.get()
+    }
+
+    pub fn device_tree(&self) -> Option<usize> {
+        match self.device_tree {
+            0 => None,
+            device_tree => Some(device_tree as usize),
+        }
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    pub fn frame_buffer(&self) -> &FrameBuffer {
+        &self.frame_buffer
+    }
+
+    pub fn highest_parallel_offset(&self) -> usize {
+        self.highest_parallel_offset as usize
+    }
+
+    pub fn image(&self) -> Option<handle::Handle<'static>> {
+        match self.image {
+            0 => None,
+            image => Some((image as usize).into()),
+        }
+    }
+
+    pub fn memory_map(&self) -> memory_allocation::MemoryDescriptors<'static> {
+        (&self.memory_map).into()
+    }
+
+    pub fn memory_attributes(&self) -> Option<usize> {
+        match self.memory_attributes {
+            0 => None,
+            memory_attributes => Some(memory_attributes as usize),
+        }
+    }
+
+    pub fn memory_size(&self) -> usize {
+        self.memory_size as usize
+    }
+
+    pub fn modules(&self) -> &'static [module::Module] {
+        self.modules.get()
+    }
+
+    // The width of a character cell. A wide glyph occupies two cells.
+    pub fn narrow_width(&self) -> u16 {
+        self.narrow_width
+    }
+
+    pub fn phases(&self) -> &'static [Phase] {
+        self.phases.get()
+    }
+
+    pub fn physical_page_present_bit_map(&self) -> &'static mut [u8] {
+        self.physical_page_present_bit_map.get_mut()
+    }
+
+    pub fn rsdp(&self) -> Option<usize> {
+        match self.rsdp {
+            0 => None,
+            rsdp => Some(rsdp as usize),
+        }
+    }
+
+    pub fn smbios(&self) -> Option<usize> {
+        match self.smbios {
+            0 => None,
+            smbios => Some(smbios as usize),
+        }
+    }
+
+    pub fn stack_floor(&self) -> usize {
+        self.stack_floor as usize
+    }
+
+    pub fn system(&self) -> Option<&'static mut system::System<'static>> {
+        let system: *mut system::System = self.system as *mut system::System;
+        unsafe {
+            system.as_mut()
+        }
+    }
+}
+
+// An address and a number of elements.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Array {
+    address: u64,
+    length: u64,
+}
+
+impl Array {
+    pub fn get<T>(&self) -> &'static [T] {
+        let address: *const T = self.address as *const T;
+        unsafe {
+            slice::from_raw_parts(address, self.length as usize)
+        }
+    }
+
+    pub fn get_mut<T>(&self) -> &'static mut [T] {
+        let address: *mut T = self.address as *mut T;
+        unsafe {
+            slice::from_raw_parts_mut(address, self.length as usize)
+        }
+    }
+}
+
+// A bootloader phase measured by TSC ticks.
+#[derive(Debug)]
+#[repr(C)]
+pub struct Phase {
+    name: Array,
+    begin: u64,
+    end: u64,
+}
+
+impl Phase {
+    pub fn name(&self) -> &str {
+        str::from_utf8(self.name.get())
+            .expect("Can't get a phase name!")
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.end - self.begin
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct MemoryMap {
+    buffer: Array,
+    descriptors: u64,
+    descriptor_size: u64,
+    descriptor_version: u32,
+}
+
+impl Into<memory_allocation::MemoryDescriptors<'static>> for &MemoryMap {
+    fn into(self) -> memory_allocation::MemoryDescriptors<'static> {
+        let buffer: &[u8] = self.buffer.get();
+        let descriptors: usize = self.descriptors as usize;
+        let descriptor_size: usize = self.descriptor_size as usize;
+        memory_allocation::MemoryDescriptors::new(buffer, descriptors, descriptor_size)
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct FrameBuffer {
+    base: u64,
+    size: u64,
+    horizontal_resolution: u32,
+    vertical_resolution: u32,
+    pixels_per_scan_line: u32,
+    pixel_format: u32,
+    red_mask: u32,
+    green_mask: u32,
+    blue_mask: u32,
+    reserved_mask: u32,
+}
+
+impl FrameBuffer {
+    // EFI_GRAPHICS_PIXEL_FORMAT
+    const RED_GREEN_BLUE_RESERVED_8_BIT_PER_COLOR: u32 = 0;
+    const BLUE_GREEN_RED_RESERVED_8_BIT_PER_COLOR: u32 = 1;
+    const PIXEL_BIT_MASK: u32 = 2;
+
+    pub fn write_pixel(&self, x: u32, y: u32, red: u8, green: u8, blue: u8) {
+        if self.horizontal_resolution <= x || self.vertical_resolution <= y {
+            return;
+        }
+        let red: u32 = red as u32;
+        let green: u32 = green as u32;
+        let blue: u32 = blue as u32;
+        let color: u32 = match self.pixel_format {
+            Self::RED_GREEN_BLUE_RESERVED_8_BIT_PER_COLOR => red + (green << 8) + (blue << 16),
+            Self::BLUE_GREEN_RED_RESERVED_8_BIT_PER_COLOR => blue + (green << 8) + (red << 16),
+            Self::PIXEL_BIT_MASK => (red << self.red_mask.trailing_zeros() & self.red_mask)
+                + (green << self.green_mask.trailing_zeros() & self.green_mask)
+                + (blue << self.blue_mask.trailing_zeros() & self.blue_mask),
+            // PixelBltOnly has no frame buffer.
+            _ => return,
+        };
+        let offset: u32 = x + y * self.pixels_per_scan_line;
+        let offset: usize = offset as usize;
+        *self.frame_buffer()
+            .get_mut(offset)
+            .expect("Can't write pixel!") = color;
+    }
+
+    fn frame_buffer(&self) -> &mut [u32] {
+        let base: *mut u32 = self.base as *mut u32;
+        let size: usize = self.size as usize / mem::size_of::<u32>();
+        unsafe {
+            slice::from_raw_parts_mut(base, size)
+        }
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct SerialPort {
+    port: u16,
+    baud: u32,
+}
+
+impl Into<serial::Serial> for &SerialPort {
+    fn into(self) -> serial::Serial {
+        serial::Serial::new(self.port, self.baud)
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Font {
+    glyphs: Array,
+    bitmap: Array,
+    max_width: u16,
+    max_height: u16,
+}
+
+impl Font {
+    pub fn glyph(&self, character: char) -> Option<&Glyph> {
+        let glyphs: &[Glyph] = self.glyphs.get();
+        glyphs
+            .binary_search_by_key(&(character as u32), |glyph| glyph.character)
+            .ok()
+            .map(|index| &glyphs[index])
+    }
+
+    // Coordinates of the glyph and whether the pixel is the foreground.
+    pub fn image<'a>(&'a self, glyph: &'a Glyph) -> impl Iterator<Item = (u16, u16, bool)> + 'a {
+        let bitmap: &[u8] = self.bitmap.get();
+        let row_bytes: usize = (glyph.width as usize + 7) / 8;
+        (0..glyph.height)
+            .flat_map(move |y| (0..glyph.width)
+                .map(move |x| {
+                    let byte: usize = glyph.offset as usize + y as usize * row_bytes + x as usize / 8;
+                    let foreground: bool = bitmap[byte] & (0x80 >> (x % 8)) != 0;
+                    (x, y, foreground)
+                }))
+    }
+
+    pub fn max_height(&self) -> u16 {
+        self.max_height
+    }
+
+    pub fn max_width(&self) -> u16 {
+        self.max_width
+    }
+}
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Glyph {
+    character: u32,
+    width: u16,
+    height: u16,
+    offset: u64,
+}
+
+impl Glyph {
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+}
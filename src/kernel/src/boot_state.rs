@@ -0,0 +1,42 @@
+// The loader counts boots in a UEFI variable.
+// The kernel deletes the counter after booting successfully so that the loader keeps booting the same entry.
+
+use {
+    crate::{
+        serial_print,
+        serial_println,
+    },
+    super::uefi::{
+        services::boot::protocol_handler,
+        tables::system,
+        types::status,
+    },
+};
+
+// The vendor GUID of THEOS variables shared with the loader.
+const VENDOR: protocol_handler::Guid = protocol_handler::Guid::new(
+    0x5c0f5e6a,
+    0x6f1d,
+    0x4b8e,
+    [
+        0x9d,
+        0x3a,
+        0x7e,
+        0x4f,
+        0x2b,
+        0x1c,
+        0x8a,
+        0x90
+    ],
+);
+const BOOT_ATTEMPTS: &str = "TheosBootAttempts";
+
+pub fn succeed() {
+    let attributes: u32 = 0;
+    match system::system()
+        .runtime_services
+        .set_variable(BOOT_ATTEMPTS, &VENDOR, attributes, &[]) {
+        Ok(()) | Err(status::NOT_FOUND) => (),
+        Err(status) => serial_println!("Can't reset boot attempts. status = {:#x?}", status),
+    }
+}
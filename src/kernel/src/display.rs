@@ -1,23 +1,22 @@
 use {
     core::ops::Add,
-    super::uefi::protocols::{
-        console_support::graphics_output,
-        human_interface_infrastructure::font,
-    },
+    super::boot_info,
 };
 
 pub struct Display<'a> {
-    frame_buffer: &'a graphics_output::GraphicsOutput<'a>,
-    font: &'a font::Font,
+    frame_buffer: &'a boot_info::FrameBuffer,
+    font: &'a boot_info::Font,
+    narrow_width: u16,
 }
 
 impl<'a> Display<'a> {
     const TAB_LENGTH: usize = 4;
 
-    pub fn new(frame_buffer: &'a graphics_output::GraphicsOutput<'a>, font: &'a font::Font) -> Self {
+    pub fn new(frame_buffer: &'a boot_info::FrameBuffer, font: &'a boot_info::Font, narrow_width: u16) -> Self {
         Self {
             frame_buffer,
             font,
+            narrow_width,
         }
     }
 
@@ -42,13 +41,12 @@ impl<'a> Display<'a> {
         character: char) {
             self.font
                 .glyph(character)
-                .map(|glyph| glyph
-                    .image()
-                    .iter()
-                    .for_each(|(pixel_coordinates, foreground)| {
-                        let pixel_coordinates: Coordinates = pixel_coordinates.into();
+                .map(|glyph| self.font
+                    .image(glyph)
+                    .for_each(|(x, y, foreground)| {
+                        let pixel_coordinates = Coordinates::new(x as u32, y as u32);
                         let coordinates = coordinates + &pixel_coordinates;
-                        let color: &Color = if *foreground {
+                        let color: &Color = if foreground {
                             foreground_color
                         } else {
                             background_color
@@ -83,15 +81,19 @@ impl<'a> Display<'a> {
                         x = 0;
                     },
                     character => {
-                        let max_width: u16 = self.font.max_width();
-                        let max_width: u32 = max_width as u32;
+                        let narrow_width: u16 = self.narrow_width;
+                        let narrow_width: u32 = narrow_width as u32;
                         let max_height: u16 = self.font.max_height();
                         let max_height: u32 = max_height as u32;
-                        let character_x: u32 = x as u32 * max_width;
+                        let character_x: u32 = x as u32 * narrow_width;
                         let character_y: u32 = y as u32 * max_height;
                         let character_coordinates: Coordinates = coordinates + &Coordinates::new(character_x, character_y);
                         self.put_char(&character_coordinates, background_color, foreground_color, character);
-                        x += 1;
+                        // A wide glyph occupies two cells.
+                        let cells: u32 = self.font
+                            .glyph(character)
+                            .map_or(1, |glyph| (glyph.width() as u32 + narrow_width - 1) / narrow_width);
+                        x += cells.max(1) as usize;
                     },
                 }
             });
@@ -136,16 +138,6 @@ impl Coordinates {
     }
 }
 
-impl From<&font::Coordinates> for Coordinates {
-    fn from(font: &font::Coordinates) -> Self {
-        let x: u16 = font.x();
-        let x: u32 = x as u32;
-        let y: u16 = font.y();
-        let y: u32 = y as u32;
-        Self::new(x, y)
-    }
-}
-
 impl Add for &Coordinates {
     type Output = Coordinates;
 
@@ -5,8 +5,12 @@ extern crate alloc;
 
 mod allocator;
 mod asm;
+mod backtrace;
+mod boot_info;
+mod boot_state;
 mod display;
 mod memory;
+mod module;
 mod pci;
 mod serial;
 mod uefi;
@@ -20,10 +24,6 @@ use {
     core::panic::PanicInfo,
     memory::physical_page,
     uefi::{
-        protocols::{
-            console_support::graphics_output,
-            human_interface_infrastructure::font,
-        },
         services::boot::memory_allocation,
         tables::system,
         types::{
@@ -34,50 +34,66 @@ use {
 };
 
 #[no_mangle]
-pub extern "C" fn main(kernel_arguments: &'static mut KernelArguments) -> ! {
-    let KernelArguments {
-        image,
-        system,
-        memory_size,
-        highest_parallel_offset,
-        physical_page_present_bit_map,
-        memory_map,
-        stack_floor,
-        cr0,
-        cr2,
-        cr3,
-        cr4,
-        ia32_efer,
-        com1,
-        com2,
-        graphics_output,
-        font,
-    } = kernel_arguments;
-    serial::Serial::init_com1(com1);
-    serial::Serial::init_com2(com2);
+pub extern "C" fn main(boot_info: &'static boot_info::BootInfo) -> ! {
+    // Nothing can be printed before the serial ports are known.
+    if !boot_info.is_compatible() {
+        loop {
+            asm::hlt();
+        }
+    }
+    serial::Serial::init_com1(&boot_info.com1());
+    serial::Serial::init_com2(&boot_info.com2());
     serial_println!("Hello, kernel.elf!");
+    let stack_floor: usize = boot_info.stack_floor();
     serial_println!("stack_floor = {:#x?}", stack_floor);
     serial_println!("RSP = {:#x?}", asm::get_rsp());
-    system::init_system(image, *system);
-    let image: handle::Handle = system::image();
-    let image: *const void::Void = image as *const void::Void;
-    let image: usize = image as usize;
-    serial_println!("image = {:#x?}", image);
-    serial_println!("system = {:#x?}", system::system());
-    serial_println!("memory_size = {:#x?}", memory_size);
-    serial_println!("highest_parallel_offset = {:#x?}", highest_parallel_offset);
-    let physical_page_present_bit_map: &'static mut [u8] = *physical_page_present_bit_map;
+    // Runtime services are unavailable if the kernel isn't booted by UEFI.
+    let uefi: bool = match (boot_info.image(), boot_info.system()) {
+        (Some(image), Some(system)) => {
+            system::init_system(image, system);
+            let image: handle::Handle = system::image();
+            let image: *const void::Void = image as *const void::Void;
+            let image: usize = image as usize;
+            serial_println!("image = {:#x?}", image);
+            serial_println!("system = {:#x?}", system::system());
+            true
+        },
+        _ => false,
+    };
+    serial_println!("uefi = {:?}", uefi);
+    serial_println!("memory_size = {:#x?}", boot_info.memory_size());
+    serial_println!("highest_parallel_offset = {:#x?}", boot_info.highest_parallel_offset());
+    serial_println!("rsdp = {:#x?}", boot_info.rsdp());
+    serial_println!("smbios = {:#x?}", boot_info.smbios());
+    serial_println!("device_tree = {:#x?}", boot_info.device_tree());
+    serial_println!("memory_attributes = {:#x?}", boot_info.memory_attributes());
+    serial_println!("command_line = {:?}", boot_info.command_line());
+    let modules: &[module::Module] = boot_info.modules();
+    serial_println!("modules = {:#x?}", modules);
+    modules
+        .iter()
+        .for_each(|module| serial_println!("module {} has {:#x} bytes", module.name(), module.bytes().len()));
+    boot_info
+        .phases()
+        .iter()
+        .for_each(|phase| serial_println!("bootloader phase {} took {} TSC ticks", phase.name(), phase.ticks()));
+    serial_println!("boot_log has {:#x} bytes", boot_info.boot_log().len());
+    let debug_sections: &'static [module::Module] = boot_info.debug_sections();
+    serial_println!("debug_sections = {:#x?}", debug_sections);
+    let main: extern "C" fn(&'static boot_info::BootInfo) -> ! = main;
+    backtrace::init(debug_sections, stack_floor, main as usize);
+    let physical_page_present_bit_map: &'static mut [u8] = boot_info.physical_page_present_bit_map();
     serial_println!("physical_page_present_bit_map");
-    let memory_map: memory_allocation::MemoryDescriptors = (*memory_map).into();
+    let memory_map: memory_allocation::MemoryDescriptors = boot_info.memory_map();
     serial_println!("memory_map");
     physical_page::Manager::init(physical_page_present_bit_map, &memory_map);
-    serial_println!("cr0 = {:#x?}", cr0);
-    serial_println!("cr2 = {:#x?}", cr2);
-    serial_println!("cr3 = {:#x?}", cr3);
-    serial_println!("cr4 = {:#x?}", cr4);
-    serial_println!("ia32_efer = {:#x?}", ia32_efer);
-    serial_println!("graphics_output = {:#x?}", graphics_output);
-    let display: display::Display = display::Display::new(graphics_output, font);
+    serial_println!("cr0 = {:#x?}", control::register0::Cr0::get());
+    serial_println!("cr2 = {:#x?}", control::register2::Cr2::get());
+    serial_println!("cr3 = {:#x?}", control::register3::Cr3::get());
+    serial_println!("cr4 = {:#x?}", control::register4::Cr4::get());
+    serial_println!("ia32_efer = {:#x?}", ia32_efer::Ia32Efer::get());
+    serial_println!("frame_buffer = {:#x?}", boot_info.frame_buffer());
+    let display: display::Display = display::Display::new(boot_info.frame_buffer(), boot_info.font(), boot_info.narrow_width());
     let x: u32 = 0;
     let y: u32 = 0;
     let coordinates = display::Coordinates::new(x, y);
@@ -92,33 +108,19 @@ pub extern "C" fn main(kernel_arguments: &'static mut KernelArguments) -> ! {
     let address2device: BTreeMap<pci::configuration::Address, pci::configuration::Device> = pci::configuration::Device::get_all_devices();
     serial_println!("address2device = {:#x?}", address2device);
     display.print(&coordinates, &background_color, &foreground_color, "Hello, World!");
+    // The loader boots the same entry next time.
+    if uefi {
+        boot_state::succeed();
+    }
     loop {
         asm::hlt();
     }
 }
 
-pub struct KernelArguments<'a> {
-    image: handle::Handle<'static>,
-    system: &'a mut system::System<'a>,
-    memory_size: usize,
-    highest_parallel_offset: usize,
-    physical_page_present_bit_map: &'a mut [u8],
-    memory_map: &'a memory_allocation::PassedMap<'a>,
-    stack_floor: &'a void::Void,
-    cr0: &'a control::register0::Cr0,
-    cr2: &'a control::register2::Cr2,
-    cr3: &'a control::register3::Cr3,
-    cr4: &'a control::register4::Cr4,
-    ia32_efer: &'a Option<ia32_efer::Ia32Efer>,
-    com1: &'a serial::Serial,
-    com2: &'a serial::Serial,
-    graphics_output: &'a graphics_output::GraphicsOutput<'a>,
-    font: &'a font::Font,
-}
-
 #[panic_handler]
 fn panic(panic: &PanicInfo) -> ! {
     serial_println!("{}", panic);
+    backtrace::print();
     loop {
         asm::hlt();
     }
@@ -0,0 +1,32 @@
+use {
+    core::{
+        slice,
+        str,
+    },
+    super::boot_info,
+};
+
+// A file loaded alongside the kernel by the bootloader.
+#[allow(dead_code)]
+#[derive(Debug)]
+#[repr(C)]
+pub struct Module {
+    name: boot_info::Array,
+    physical_address: u64,
+    virtual_address: u64,
+    size: u64,
+}
+
+impl Module {
+    pub fn bytes(&self) -> &[u8] {
+        let bytes: *const u8 = self.virtual_address as *const u8;
+        unsafe {
+            slice::from_raw_parts(bytes, self.size as usize)
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        str::from_utf8(self.name.get())
+            .expect("Can't get a module name!")
+    }
+}
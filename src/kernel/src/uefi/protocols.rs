@@ -17,7 +17,6 @@
 
 pub mod console_support;
 pub mod efi_loaded_image;
-pub mod human_interface_infrastructure;
 pub mod device_path;
 pub mod media_access;
 
@@ -317,46 +317,23 @@ impl Into<Vec<MemoryDescriptor>> for &Map<'_> {
     }
 }
 
-#[allow(dead_code)]
-pub struct PassedMap<'a> {
+#[derive(Clone)]
+pub struct MemoryDescriptors<'a> {
     buffer: &'a [u8],
-    key: usize,
     descriptors: usize,
     descriptor_size: usize,
-    descriptor_version: u32,
 }
 
-impl<'a> From<&'a Map<'a>> for PassedMap<'a> {
-    fn from(map: &'a Map<'a>) -> Self {
-        let Map {
-            buffer,
-            key,
-            descriptors,
-            descriptor_size,
-            descriptor_version,
-        } = map;
-        let buffer: &[u8] = buffer.get_ref();
-        let key: usize = *key;
-        let descriptors: usize = *descriptors;
-        let descriptor_size: usize = *descriptor_size;
-        let descriptor_version: u32 = *descriptor_version;
+impl<'a> MemoryDescriptors<'a> {
+    pub fn new(buffer: &'a [u8], descriptors: usize, descriptor_size: usize) -> Self {
         Self {
             buffer,
-            key,
             descriptors,
             descriptor_size,
-            descriptor_version,
         }
     }
 }
 
-#[derive(Clone)]
-pub struct MemoryDescriptors<'a> {
-    buffer: &'a [u8],
-    descriptors: usize,
-    descriptor_size: usize,
-}
-
 impl fmt::Debug for MemoryDescriptors<'_> {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         formatter
@@ -366,26 +343,6 @@ impl fmt::Debug for MemoryDescriptors<'_> {
     }
 }
 
-impl<'a> From<&'a PassedMap<'a>> for MemoryDescriptors<'a> {
-    fn from(map: &PassedMap<'a>) -> Self {
-        let PassedMap {
-            buffer,
-            key: _,
-            descriptors,
-            descriptor_size,
-            descriptor_version: _,
-        } = map;
-        let buffer: &[u8] = *buffer;
-        let descriptors: usize = *descriptors;
-        let descriptor_size: usize = *descriptor_size;
-        MemoryDescriptors {
-            buffer,
-            descriptors,
-            descriptor_size,
-        }
-    }
-}
-
 impl Iterator for MemoryDescriptors<'_> {
     type Item = MemoryDescriptor;
 
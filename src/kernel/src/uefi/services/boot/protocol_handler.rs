@@ -29,7 +29,7 @@ pub struct Guid {
 }
 
 impl Guid {
-    pub fn new(
+    pub const fn new(
         data1: u32,
         data2: u16,
         data3: u16,
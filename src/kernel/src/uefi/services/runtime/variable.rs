@@ -24,7 +24,7 @@ pub struct GetNextVariableName(pub extern "efiapi" fn(&mut usize, char16::MutStr
 
 #[derive(WrappedFunction)]
 #[repr(C)]
-pub struct SetVariable(pub extern "efiapi" fn(char16::String, &protocol_handler::Guid, &mut u32, &mut usize, &mut void::Void) -> status::Status);
+pub struct SetVariable(pub extern "efiapi" fn(char16::String, &protocol_handler::Guid, u32, usize, &void::Void) -> status::Status);
 
 #[derive(WrappedFunction)]
 #[repr(C)]
@@ -1,10 +1,24 @@
-use super::{
-    header,
-    super::services::runtime::{
-        self,
-        time,
-        variable,
-        virtual_memory,
+use {
+    alloc::vec::Vec,
+    core::iter,
+    super::{
+        header,
+        super::{
+            services::{
+                boot::protocol_handler,
+                runtime::{
+                    self,
+                    time,
+                    variable,
+                    virtual_memory,
+                },
+            },
+            types::{
+                char16,
+                status,
+                void,
+            },
+        },
     },
 };
 
@@ -31,3 +45,27 @@ pub struct RuntimeServices {
     query_variable_info: variable::QueryVariableInfo,
 }
 
+
+impl RuntimeServices {
+    // An empty data deletes the variable.
+    pub fn set_variable(&self, name: &str, vendor: &protocol_handler::Guid, attributes: u32, data: &[u8]) -> Result<(), status::Status> {
+        let name: Vec<char16::Char16> = name
+            .encode_utf16()
+            .chain(iter::once(0))
+            .collect();
+        let data_size: usize = data.len();
+        let data: *const u8 = data.as_ptr();
+        let data: usize = data as usize;
+        let data: &void::Void = data.into();
+        match self.set_variable.0(
+            char16::String::new(&name[0]),
+            vendor,
+            attributes,
+            data_size,
+            data,
+        ) {
+            status::SUCCESS => Ok(()),
+            error => Err(error),
+        }
+    }
+}
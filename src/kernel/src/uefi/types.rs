@@ -2,7 +2,6 @@
 // https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
 // 2.3.1 Data Types
 
-pub mod char8;
 pub mod char16;
 pub mod event;
 pub mod handle;
@@ -1,6 +0,0 @@
-// Reference
-// https://uefi.org/sites/default/files/resources/UEFI_Spec_2_9_2021_03_18.pdf
-// 2.3.1 Data Types
-// CHAR8
-pub type Char8 = u8;
-
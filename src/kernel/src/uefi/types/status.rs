@@ -7,3 +7,4 @@ pub type Status = usize;
 
 pub const SUCCESS: Status = 0;
 
+pub const NOT_FOUND: Status = 0x800000000000000e;